@@ -84,6 +84,14 @@ pub const DEFAULT_SINK_PARTITION_STRATEGY: Config<&str> = Config::new(
     "The default sink partitioning strategy for an environment. It defaults to 'v0'.",
 );
 
+/// Comma-separated names of the clusters whose compute collections should not
+/// compact.
+pub const COMPACTION_PAUSED_CLUSTERS: Config<&str> = Config::new(
+    "compaction_paused_clusters",
+    "",
+    "Comma-separated names of clusters whose indexes and materialized views are held at their current read frontier, pausing compaction. Intended for investigating correctness reports; the held collections grow until compaction is resumed.",
+);
+
 /// Adds the full set of all compute `Config`s.
 pub fn all_dyncfgs(configs: ConfigSet) -> ConfigSet {
     configs
@@ -97,4 +105,5 @@ pub fn all_dyncfgs(configs: ConfigSet) -> ConfigSet {
         .add(&ENABLE_INTROSPECTION_SUBSCRIBES)
         .add(&PLAN_INSIGHTS_NOTICE_FAST_PATH_CLUSTERS_OPTIMIZE_DURATION)
         .add(&DEFAULT_SINK_PARTITION_STRATEGY)
+        .add(&COMPACTION_PAUSED_CLUSTERS)
 }
@@ -24,10 +24,11 @@ use mz_catalog::builtin::{
     MZ_KAFKA_CONNECTIONS, MZ_KAFKA_SINKS, MZ_KAFKA_SOURCES, MZ_LIST_TYPES, MZ_MAP_TYPES,
     MZ_MATERIALIZED_VIEWS, MZ_MATERIALIZED_VIEW_REFRESH_STRATEGIES, MZ_MYSQL_SOURCE_TABLES,
     MZ_OBJECT_DEPENDENCIES, MZ_OPERATORS, MZ_PENDING_CLUSTER_REPLICAS, MZ_POSTGRES_SOURCES,
-    MZ_POSTGRES_SOURCE_TABLES, MZ_PSEUDO_TYPES, MZ_ROLES, MZ_ROLE_MEMBERS, MZ_ROLE_PARAMETERS,
-    MZ_SCHEMAS, MZ_SECRETS, MZ_SESSIONS, MZ_SINKS, MZ_SOURCES, MZ_SSH_TUNNEL_CONNECTIONS,
-    MZ_STORAGE_USAGE_BY_SHARD, MZ_SUBSCRIPTIONS, MZ_SYSTEM_PRIVILEGES, MZ_TABLES, MZ_TYPES,
-    MZ_TYPE_PG_METADATA, MZ_VIEWS, MZ_WEBHOOKS_SOURCES,
+    MZ_POSTGRES_SOURCE_TABLES, MZ_PSEUDO_TYPES, MZ_READ_HOLDS, MZ_READ_HOLD_WAITS, MZ_ROLES,
+    MZ_ROLE_MEMBERS, MZ_ROLE_PARAMETERS, MZ_SCHEMAS, MZ_SECRETS, MZ_SESSIONS, MZ_SINKS, MZ_SOURCES,
+    MZ_SSH_TUNNEL_CONNECTIONS, MZ_STORAGE_USAGE_BY_SHARD, MZ_SUBSCRIPTIONS, MZ_SYSTEM_PRIVILEGES,
+    MZ_TABLES, MZ_TIMELINE_TIMESTAMPS, MZ_TYPES, MZ_TYPE_PG_METADATA, MZ_VIEWS,
+    MZ_WEBHOOKS_SOURCES,
 };
 use mz_catalog::config::AwsPrincipalContext;
 use mz_catalog::memory::error::{Error, ErrorKind};
@@ -72,6 +73,7 @@ use mz_storage_types::connections::KafkaConnection;
 use mz_storage_types::sinks::{KafkaSinkConnection, StorageSinkConnection};
 use mz_storage_types::sources::{
     GenericSourceConnection, KafkaSourceConnection, PostgresSourceConnection, SourceConnection,
+    Timeline,
 };
 
 // DO NOT add any more imports from `crate` outside of `crate::catalog`.
@@ -1857,6 +1859,76 @@ impl CatalogState {
         }
     }
 
+    pub fn pack_timeline_timestamps_update(
+        &self,
+        timeline: &Timeline,
+        read_ts: Timestamp,
+        write_ts: Timestamp,
+        read_hold: Option<Timestamp>,
+        diff: Diff,
+    ) -> BuiltinTableUpdate<&'static BuiltinTable> {
+        BuiltinTableUpdate {
+            id: &*MZ_TIMELINE_TIMESTAMPS,
+            row: Row::pack_slice(&[
+                Datum::String(&timeline.to_string()),
+                Datum::MzTimestamp(read_ts),
+                Datum::MzTimestamp(write_ts),
+                read_hold.map_or(Datum::Null, Datum::MzTimestamp),
+            ]),
+            diff,
+        }
+    }
+
+    pub fn pack_read_hold_update(
+        &self,
+        object_id: GlobalId,
+        cluster_id: Option<ClusterId>,
+        holder: &str,
+        connection_id: Option<u32>,
+        read_frontier: Option<Timestamp>,
+        diff: Diff,
+    ) -> BuiltinTableUpdate<&'static BuiltinTable> {
+        let cluster_id = cluster_id.map(|id| id.to_string());
+        BuiltinTableUpdate {
+            id: &*MZ_READ_HOLDS,
+            row: Row::pack_slice(&[
+                Datum::String(&object_id.to_string()),
+                cluster_id.as_deref().map_or(Datum::Null, Datum::String),
+                Datum::String(holder),
+                connection_id.map_or(Datum::Null, Datum::UInt32),
+                read_frontier.map_or(Datum::Null, Datum::MzTimestamp),
+            ]),
+            diff,
+        }
+    }
+
+    pub fn pack_read_hold_wait_update(
+        &self,
+        waiter: &str,
+        waiter_connection_id: Option<u32>,
+        object_id: GlobalId,
+        cluster_id: Option<ClusterId>,
+        holder: Option<&str>,
+        holder_connection_id: Option<u32>,
+        timestamp: Option<Timestamp>,
+        diff: Diff,
+    ) -> BuiltinTableUpdate<&'static BuiltinTable> {
+        let cluster_id = cluster_id.map(|id| id.to_string());
+        BuiltinTableUpdate {
+            id: &*MZ_READ_HOLD_WAITS,
+            row: Row::pack_slice(&[
+                Datum::String(waiter),
+                waiter_connection_id.map_or(Datum::Null, Datum::UInt32),
+                Datum::String(&object_id.to_string()),
+                cluster_id.as_deref().map_or(Datum::Null, Datum::String),
+                holder.map_or(Datum::Null, Datum::String),
+                holder_connection_id.map_or(Datum::Null, Datum::UInt32),
+                timestamp.map_or(Datum::Null, Datum::MzTimestamp),
+            ]),
+            diff,
+        }
+    }
+
     pub fn pack_default_privileges_update(
         &self,
         default_privilege_object: &DefaultPrivilegeObject,
@@ -173,8 +173,10 @@ use crate::coord::cluster_scheduling::SchedulingDecision;
 use crate::coord::id_bundle::CollectionIdBundle;
 use crate::coord::introspection::IntrospectionSubscribe;
 use crate::coord::peek::PendingPeek;
-use crate::coord::read_policy::ReadHoldsInner;
-use crate::coord::timeline::{TimelineContext, TimelineState};
+use crate::coord::read_policy::{
+    dropped_read_holds_channel, DroppedReadHoldsRx, DroppedReadHoldsTx, ReadHoldsInner,
+};
+use crate::coord::timeline::{TimelineContext, TimelineState, TimelineTimestamps};
 use crate::coord::timestamp_selection::{TimestampContext, TimestampDetermination};
 use crate::coord::validity::PlanValidity;
 use crate::error::AdapterError;
@@ -215,6 +217,10 @@ mod sequencer;
 mod sql;
 mod validity;
 
+/// How often to look for transaction read holds of connections that are no
+/// longer active.
+const SWEEP_TXN_READ_HOLDS_INTERVAL: Duration = Duration::from_secs(60);
+
 #[derive(Debug)]
 pub enum Message<T = mz_repr::Timestamp> {
     Command(OpenTelemetryContext, Command),
@@ -239,6 +245,9 @@ pub enum Message<T = mz_repr::Timestamp> {
     DeferredStatementReady,
     AdvanceTimelines,
     DropReadHolds(Vec<ReadHoldsInner<Timestamp>>),
+    /// Releases the transaction read holds of connections that are no longer
+    /// active.
+    SweepTxnReadHolds,
     ClusterEvent(ClusterEvent),
     CancelPendingPeeks {
         conn_id: ConnectionId,
@@ -309,6 +318,10 @@ pub enum Message<T = mz_repr::Timestamp> {
     PrivateLinkVpcEndpointEvents(Vec<VpcEndpointEvent>),
     CheckSchedulingPolicies,
 
+    /// The timestamps of each timeline, to be reported in
+    /// `mz_internal.mz_timeline_timestamps`.
+    TimelineTimestamps(BTreeMap<Timeline, TimelineTimestamps>),
+
     /// Scheduling policy decisions about turning clusters On/Off.
     /// `Vec<(policy name, Vec of decisions by the policy)>`
     /// A cluster will be On if and only if there is at least one On decision for it.
@@ -371,7 +384,9 @@ impl Message {
             Message::AlterConnectionValidationReady(..) => "alter_connection_validation_ready",
             Message::PrivateLinkVpcEndpointEvents(_) => "private_link_vpc_endpoint_events",
             Message::CheckSchedulingPolicies => "check_scheduling_policies",
+            Message::SweepTxnReadHolds => "sweep_txn_read_holds",
             Message::SchedulingDecisions { .. } => "scheduling_decision",
+            Message::TimelineTimestamps(_) => "timeline_timestamps",
             Message::DeferredStatementReady => "deferred_statement_ready",
         }
     }
@@ -1569,11 +1584,15 @@ pub struct Coordinator {
     /// `internal_cmd_tx` so that we can control the priority of working off
     /// dropped read holds. If we sent them as [Message] on the internal cmd
     /// channel, these would always get top priority, which is not necessary.
-    dropped_read_holds_tx: mpsc::UnboundedSender<ReadHoldsInner<Timestamp>>,
+    dropped_read_holds_tx: DroppedReadHoldsTx<Timestamp>,
 
     /// Mechanism for totally ordering write and read timestamps, so that all reads
     /// reflect exactly the set of writes that precede them, and no writes that follow.
     global_timelines: BTreeMap<Timeline, TimelineState<Timestamp>>,
+    /// The timeline timestamps last reported in `mz_internal.mz_timeline_timestamps`.
+    timeline_timestamps: BTreeMap<Timeline, TimelineTimestamps>,
+    /// When `timeline_timestamps` was last refreshed.
+    timeline_timestamps_updated_at: Instant,
 
     /// A generator for transient [`GlobalId`]s, shareable with other threads.
     transient_id_gen: Arc<TransientIdGen>,
@@ -1607,6 +1626,28 @@ pub struct Coordinator {
     /// in `self.read_capability[id]`, using the `release_read_holds` method.
     txn_read_holds: BTreeMap<ConnectionId, read_policy::ReadHolds<Timestamp>>,
 
+    /// The read holds that pause compaction on the compute collections of the
+    /// clusters named by the `compaction_paused_clusters` config, by cluster.
+    ///
+    /// See [`Coordinator::update_compaction_pause_holds`].
+    compaction_pause_holds: BTreeMap<ComputeInstanceId, read_policy::ReadHolds<Timestamp>>,
+    /// The names in the `compaction_paused_clusters` config that did not
+    /// resolve to a cluster the last time it was reconciled, which have
+    /// already been warned about.
+    unknown_compaction_paused_clusters: BTreeSet<String>,
+
+    /// The read holds and waits last reported in `mz_internal.mz_read_holds`
+    /// and `mz_internal.mz_read_hold_waits`.
+    ///
+    /// See [`Coordinator::update_read_holds_introspection`].
+    read_holds_introspection: read_policy::ReadHoldsIntrospection,
+
+    /// The collections whose read policies have yet to be sent to the
+    /// controllers.
+    ///
+    /// Access to this field should be restricted to methods in the [`read_policy`] API.
+    pending_read_policy_updates: read_policy::PendingReadPolicyUpdates,
+
     /// Access to the peek fields should be restricted to methods in the [`peek`] API.
     /// A map from pending peek ids to the queue into which responses are sent, and
     /// the connection id of the client that initiated the peek.
@@ -1709,6 +1750,10 @@ pub struct Coordinator {
     /// clusters whether they are hydrated.
     check_clusters_hydrated_interval: tokio::time::Interval,
 
+    /// Periodically releases the transaction read holds of connections that
+    /// are no longer active.
+    sweep_txn_read_holds_interval: tokio::time::Interval,
+
     /// A trigger that signals that all clusters have been hydrated. Only used
     /// during 0dt deployment, while in read-only mode.
     clusters_hydrated_trigger: Option<trigger::Trigger>,
@@ -2127,11 +2172,7 @@ impl Coordinator {
         }
 
         // Having installed all entries, creating all constraints, we can now relax read policies.
-        //
-        // TODO -- Improve `initialize_read_policies` API so we can avoid calling this in a loop.
-        for (cw, policies) in policies_to_set {
-            self.initialize_read_policies(&policies, cw).await;
-        }
+        self.initialize_read_policies_bulk(policies_to_set).await;
 
         // Expose mapping from T-shirt sizes to actual sizes
         builtin_table_updates.extend(
@@ -2925,7 +2966,7 @@ impl Coordinator {
         mut self,
         mut internal_cmd_rx: mpsc::UnboundedReceiver<Message>,
         mut strict_serializable_reads_rx: mpsc::UnboundedReceiver<(ConnectionId, PendingReadTxn)>,
-        mut dropped_read_holds_rx: mpsc::UnboundedReceiver<ReadHoldsInner<Timestamp>>,
+        mut dropped_read_holds_rx: DroppedReadHoldsRx<Timestamp>,
         mut cmd_rx: mpsc::UnboundedReceiver<(OpenTelemetryContext, Command)>,
         group_commit_rx: appends::GroupCommitWaiter,
     ) -> LocalBoxFuture<'static, ()> {
@@ -3002,7 +3043,39 @@ impl Coordinator {
                 .system_config()
                 .coord_slow_message_warn_threshold();
 
+            // Release any read holds that were dropped during bootstrap before we start
+            // taking on new work.
+            let dropped_read_holds = dropped_read_holds_rx.try_recv_all();
+            if !dropped_read_holds.is_empty() {
+                tracing::debug!(
+                    count = dropped_read_holds.len(),
+                    "releasing read holds dropped during bootstrap"
+                );
+                self.release_read_holds(dropped_read_holds);
+            }
+
             loop {
+                // Send the read policy changes of the previous message, or of
+                // bootstrap, to the controllers before waiting for the next
+                // one.
+                self.flush_read_policy_updates();
+                // Refreshes `mz_internal.mz_read_holds` and
+                // `mz_internal.mz_read_hold_waits`, at most once per interval.
+                self.update_read_holds_introspection();
+
+                // Dropped read holds normally have low priority, but if they back up past
+                // their capacity we release them before anything else, so that a busy
+                // coordinator can't indefinitely hold back compaction.
+                if dropped_read_holds_rx.over_capacity() {
+                    let dropped_read_holds = dropped_read_holds_rx.try_recv_all();
+                    tracing::debug!(
+                        count = dropped_read_holds.len(),
+                        "dropped read holds over capacity, releasing eagerly"
+                    );
+                    self.release_read_holds(dropped_read_holds);
+                    continue;
+                }
+
                 // Before adding a branch to this select loop, please ensure that the branch is
                 // cancellation safe and add a comment explaining why. You can refer here for more
                 // info: https://docs.rs/tokio/latest/tokio/macro.select.html#cancellation-safety
@@ -3070,13 +3143,8 @@ impl Coordinator {
                         }
                         Message::LinearizeReads
                     }
-                    // `recv()` on `UnboundedReceiver` is cancellation safe:
-                    // https://docs.rs/tokio/1.8.0/tokio/sync/mpsc/struct.UnboundedReceiver.html#cancel-safety
-                    Some(dropped_read_hold) = dropped_read_holds_rx.recv() => {
-                        let mut dropped_read_holds = vec![dropped_read_hold];
-                        while let Ok(dropped_read_hold) = dropped_read_holds_rx.try_recv() {
-                            dropped_read_holds.push(dropped_read_hold);
-                        }
+                    // See [`DroppedReadHoldsRx::recv_batch`] for notes on why this is cancel safe.
+                    Some(dropped_read_holds) = dropped_read_holds_rx.recv_batch() => {
                         Message::DropReadHolds(dropped_read_holds)
                     }
                     // `tick()` on `Interval` is cancel-safe:
@@ -3107,6 +3175,11 @@ impl Coordinator {
 
                         continue;
                     },
+                    // `tick()` on `Interval` is cancel-safe:
+                    // https://docs.rs/tokio/1.19.2/tokio/time/struct.Interval.html#cancel-safety
+                    _ = self.sweep_txn_read_holds_interval.tick() => {
+                        Message::SweepTxnReadHolds
+                    },
 
                     // Process the idle metric at the lowest priority to sample queue non-idle time.
                     // `recv()` on `Receiver` is cancellation safe:
@@ -3641,7 +3714,6 @@ pub fn serve(
         let (group_commit_tx, group_commit_rx) = appends::notifier();
         let (strict_serializable_reads_tx, strict_serializable_reads_rx) =
             mpsc::unbounded_channel();
-        let (dropped_read_holds_tx, dropped_read_holds_rx) = mpsc::unbounded_channel();
 
         // Validate and process availability zones.
         if !availability_zones.iter().all_unique() {
@@ -3794,6 +3866,8 @@ pub fn serve(
 
         let metrics = Metrics::register_into(&metrics_registry);
         let metrics_clone = metrics.clone();
+        let (dropped_read_holds_tx, dropped_read_holds_rx) =
+            dropped_read_holds_channel(&metrics, now.clone());
         let optimizer_metrics = OptimizerMetrics::register_into(&metrics_registry);
         let segment_client_clone = segment_client.clone();
         let coord_now = now.clone();
@@ -3805,6 +3879,10 @@ pub fn serve(
         );
         check_scheduling_policies_interval.set_missed_tick_behavior(MissedTickBehavior::Delay);
 
+        let mut sweep_txn_read_holds_interval =
+            tokio::time::interval(SWEEP_TXN_READ_HOLDS_INTERVAL);
+        sweep_txn_read_holds_interval.set_missed_tick_behavior(MissedTickBehavior::Delay);
+
         let check_clusters_hydrated_interval = if read_only_controllers {
             let dyncfgs = catalog.system_config().dyncfgs();
             let interval = WITH_0DT_DEPLOYMENT_HYDRATION_CHECK_INTERVAL.get(dyncfgs);
@@ -3865,11 +3943,17 @@ pub fn serve(
                     strict_serializable_reads_tx,
                     dropped_read_holds_tx,
                     global_timelines: timestamp_oracles,
+                    timeline_timestamps: BTreeMap::new(),
+                    timeline_timestamps_updated_at: Instant::now(),
                     transient_id_gen: Arc::new(TransientIdGen::new()),
                     active_conns: BTreeMap::new(),
                     storage_read_capabilities: Default::default(),
                     compute_read_capabilities: Default::default(),
                     txn_read_holds: Default::default(),
+                    compaction_pause_holds: Default::default(),
+                    unknown_compaction_paused_clusters: Default::default(),
+                    read_holds_introspection: Default::default(),
+                    pending_read_policy_updates: Default::default(),
                     pending_peeks: BTreeMap::new(),
                     client_pending_peeks: BTreeMap::new(),
                     pending_linearize_read_txns: BTreeMap::new(),
@@ -3897,6 +3981,7 @@ pub fn serve(
                     check_cluster_scheduling_policies_interval: check_scheduling_policies_interval,
                     cluster_scheduling_decisions: BTreeMap::new(),
                     check_clusters_hydrated_interval,
+                    sweep_txn_read_holds_interval,
                     installed_watch_sets: BTreeMap::new(),
                     connection_watch_sets: BTreeMap::new(),
                     cluster_replica_statuses: ClusterReplicaStatuses::new(),
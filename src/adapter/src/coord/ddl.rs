@@ -19,6 +19,7 @@ use futures::Future;
 use maplit::{btreemap, btreeset};
 use mz_adapter_types::compaction::SINCE_GRANULARITY;
 use mz_adapter_types::connection::ConnectionId;
+use mz_adapter_types::dyncfgs::COMPACTION_PAUSED_CLUSTERS;
 use mz_audit_log::VersionedEvent;
 use mz_catalog::memory::objects::{
     CatalogItem, Connection, DataSourceDesc, Index, MaterializedView, Sink,
@@ -216,6 +217,7 @@ impl Coordinator {
         let mut update_cluster_scheduling_config = false;
         let mut update_arrangement_exert_proportionality = false;
         let mut update_http_config = false;
+        let mut update_compaction_pause = false;
         let mut log_indexes_to_drop = Vec::new();
 
         for op in &ops {
@@ -325,6 +327,7 @@ impl Coordinator {
                     update_arrangement_exert_proportionality |=
                         name == vars::ARRANGEMENT_EXERT_PROPORTIONALITY.name();
                     update_http_config |= vars::is_http_config_var(name);
+                    update_compaction_pause |= name == COMPACTION_PAUSED_CLUSTERS.name();
                 }
                 catalog::Op::ResetAllSystemConfiguration => {
                     // Assume they all need to be updated.
@@ -339,6 +342,7 @@ impl Coordinator {
                     update_cluster_scheduling_config = true;
                     update_arrangement_exert_proportionality = true;
                     update_http_config = true;
+                    update_compaction_pause = true;
                 }
                 catalog::Op::RenameItem { id, .. } => {
                     let item = self.catalog().get_entry(id);
@@ -643,6 +647,16 @@ impl Coordinator {
                     }
                 }
             }
+            // Release the holds that pause the compaction of dropped compute
+            // collections, so they don't keep the collections from being
+            // cleaned up.
+            self.release_compaction_pause_holds(
+                indexes_to_drop
+                    .iter()
+                    .chain(materialized_views_to_drop.iter())
+                    .copied(),
+                clusters_to_drop.iter().copied(),
+            );
             if !indexes_to_drop.is_empty() {
                 self.drop_indexes(indexes_to_drop);
             }
@@ -767,6 +781,9 @@ impl Coordinator {
             if update_http_config {
                 self.update_http_config();
             }
+            if update_compaction_pause {
+                self.update_compaction_pause_holds();
+            }
         }
         .instrument(info_span!("coord::catalog_transact_with::finalize"))
         .await;
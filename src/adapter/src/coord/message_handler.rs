@@ -225,12 +225,20 @@ impl Coordinator {
                             .await;
                     }
                 }
+                Message::SweepTxnReadHolds => {
+                    self.sweep_txn_read_holds();
+                }
                 Message::CheckSchedulingPolicies => {
                     self.check_scheduling_policies().await;
                 }
                 Message::SchedulingDecisions(decisions) => {
                     self.handle_scheduling_decisions(decisions).await;
                 }
+                Message::TimelineTimestamps(timeline_timestamps) => {
+                    if !self.controller.read_only() {
+                        self.update_timeline_timestamps_introspection(timeline_timestamps);
+                    }
+                }
                 Message::DeferredStatementReady => {
                     self.handle_deferred_statement().await;
                 }
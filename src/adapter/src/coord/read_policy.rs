@@ -20,28 +20,38 @@
 //! `mz_ore` wrapper either.
 #![allow(clippy::disallowed_types)]
 
-use std::collections::{btree_map, hash_map, BTreeMap, BTreeSet, HashMap};
+use std::collections::{btree_map, hash_map, BTreeMap, BTreeSet, HashMap, VecDeque};
 use std::fmt::Debug;
 use std::hash::Hash;
 use std::ops::Deref;
+use std::sync::{Arc, Mutex};
+use std::time::{Duration, Instant};
 
 use differential_dataflow::lattice::Lattice;
 use itertools::Itertools;
 use mz_adapter_types::compaction::{CompactionWindow, ReadCapability};
+use mz_adapter_types::connection::{ConnectionId, ConnectionIdType};
+use mz_adapter_types::dyncfgs::COMPACTION_PAUSED_CLUSTERS;
 use mz_compute_types::ComputeInstanceId;
 use mz_ore::instrument;
+use mz_ore::now::{EpochMillis, NowFn};
 use mz_repr::{GlobalId, Timestamp};
 use mz_sql::session::metadata::SessionMetadata;
 use mz_storage_types::read_holds::ReadHold as StorageReadHold;
 use mz_storage_types::read_policy::ReadPolicy;
-use serde::Serialize;
+use prometheus::IntGauge;
+use serde::{Deserialize, Serialize};
 use timely::progress::frontier::MutableAntichain;
 use timely::progress::Antichain;
 use timely::progress::Timestamp as TimelyTimestamp;
+use timely::PartialOrder;
+use tokio::sync::mpsc;
 
 use crate::coord::id_bundle::CollectionIdBundle;
 use crate::coord::timeline::{TimelineContext, TimelineState};
+use crate::coord::timestamp_selection::TimestampProvider;
 use crate::coord::Coordinator;
+use crate::metrics::Metrics;
 use crate::session::Session;
 use crate::util::ResultExt;
 
@@ -82,6 +92,11 @@ impl<T: Eq + Hash + Ord> TimelineReadHolds<T> {
         self.holds.keys()
     }
 
+    /// Returns the least time at which a read hold exists, if any.
+    pub fn least_time(&self) -> Option<&T> {
+        self.times().flat_map(|time| time.iter()).min()
+    }
+
     /// Return a `CollectionIdBundle` containing all the IDs in the
     /// [TimelineReadHolds].
     pub fn id_bundle(&self) -> CollectionIdBundle {
@@ -94,7 +109,6 @@ impl<T: Eq + Hash + Ord> TimelineReadHolds<T> {
     }
 
     /// Returns an iterator over all storage ids and the time at which their read hold exists.
-    #[allow(unused)]
     pub fn storage_ids(&self) -> impl Iterator<Item = (&Antichain<T>, &GlobalId)> {
         self.holds
             .iter()
@@ -176,14 +190,14 @@ impl<T: Eq + Hash + Ord> TimelineReadHolds<T> {
 /// _are_ released automatically when being dropped.
 pub struct ReadHolds<T: TimelyTimestamp> {
     pub inner: ReadHoldsInner<T>,
-    dropped_read_holds_tx: tokio::sync::mpsc::UnboundedSender<ReadHoldsInner<T>>,
+    dropped_read_holds_tx: DroppedReadHoldsTx<T>,
 }
 
 impl<T: TimelyTimestamp> ReadHolds<T> {
     /// Return empty `ReadHolds`.
     pub fn new(
         read_holds: ReadHoldsInner<T>,
-        dropped_read_holds_tx: tokio::sync::mpsc::UnboundedSender<ReadHoldsInner<T>>,
+        dropped_read_holds_tx: DroppedReadHoldsTx<T>,
     ) -> Self {
         ReadHolds {
             inner: read_holds,
@@ -226,13 +240,188 @@ impl<T: TimelyTimestamp> Drop for ReadHolds<T> {
             inner_holds.compute_holds.keys()
         );
 
-        let res = self.dropped_read_holds_tx.send(inner_holds);
-        if let Err(e) = res {
-            tracing::warn!("error when trying to drop ReadHold: {:?}", e)
+        self.dropped_read_holds_tx.send(inner_holds);
+    }
+}
+
+/// The number of dropped [ReadHolds] that may be queued individually.
+///
+/// Dropping [ReadHolds] can't block, so once the queue is full, further
+/// dropped holds are merged into a single overflow entry instead. This bounds
+/// the queue to this many entries plus one, whose size depends on the number
+/// of held collections rather than the number of drops. While there is
+/// overflow, the coordinator releases the queued holds before selecting its
+/// next message, so that a busy coordinator can't indefinitely hold back
+/// compaction.
+pub const DROPPED_READ_HOLDS_CAPACITY: usize = 1024;
+
+/// Creates a queue for releasing dropped [ReadHolds], which reports its depth
+/// and the age of its oldest entry according to `now` in `metrics`.
+pub fn dropped_read_holds_channel<T: TimelyTimestamp>(
+    metrics: &Metrics,
+    now: NowFn,
+) -> (DroppedReadHoldsTx<T>, DroppedReadHoldsRx<T>) {
+    let (tx, rx) = mpsc::channel(DROPPED_READ_HOLDS_CAPACITY);
+    let queue = Arc::new(DroppedReadHoldsQueue {
+        state: Mutex::new(DroppedReadHoldsState {
+            enqueued_at: VecDeque::new(),
+            overflow: None,
+        }),
+        depth: metrics.dropped_read_holds_queue_depth.clone(),
+        oldest_enqueued_at: Arc::clone(&metrics.dropped_read_holds_oldest_enqueued_at),
+        now,
+    });
+
+    let tx = DroppedReadHoldsTx {
+        tx,
+        queue: Arc::clone(&queue),
+    };
+    let rx = DroppedReadHoldsRx { rx, queue };
+    (tx, rx)
+}
+
+/// Bookkeeping on the entries of the queue of dropped [ReadHolds], shared by
+/// its two halves.
+struct DroppedReadHoldsQueue<T: TimelyTimestamp> {
+    state: Mutex<DroppedReadHoldsState<T>>,
+    depth: IntGauge,
+    /// When the oldest queued hold was dropped, from which the metrics compute
+    /// its age according to `now` when they are scraped.
+    oldest_enqueued_at: Arc<Mutex<Option<(EpochMillis, NowFn)>>>,
+    /// The clock that timestamps the queued holds.
+    now: NowFn,
+}
+
+struct DroppedReadHoldsState<T: TimelyTimestamp> {
+    /// When each entry in the channel was sent, oldest first.
+    enqueued_at: VecDeque<EpochMillis>,
+    /// The holds dropped while the channel was full.
+    overflow: Option<DroppedReadHoldsOverflow<T>>,
+}
+
+/// Dropped [ReadHolds] merged into a single entry of the queue.
+struct DroppedReadHoldsOverflow<T: TimelyTimestamp> {
+    /// When the first of the merged holds was dropped.
+    enqueued_at: EpochMillis,
+    /// The number of merged holds.
+    count: usize,
+    read_holds: ReadHoldsInner<T>,
+}
+
+impl<T: TimelyTimestamp> DroppedReadHoldsQueue<T> {
+    /// Updates the metrics to reflect `state`.
+    fn update_metrics(&self, state: &DroppedReadHoldsState<T>) {
+        let overflow_count = state.overflow.as_ref().map_or(0, |o| o.count);
+        let depth = state.enqueued_at.len() + overflow_count;
+        self.depth.set(i64::try_from(depth).unwrap_or(i64::MAX));
+        let oldest = state
+            .enqueued_at
+            .front()
+            .copied()
+            .into_iter()
+            .chain(state.overflow.as_ref().map(|o| o.enqueued_at))
+            .min();
+        *self.oldest_enqueued_at.lock().expect("lock poisoned") =
+            oldest.map(|at| (at, self.now.clone()));
+    }
+}
+
+/// The sending half of the queue of dropped [ReadHolds].
+pub struct DroppedReadHoldsTx<T: TimelyTimestamp> {
+    tx: mpsc::Sender<ReadHoldsInner<T>>,
+    queue: Arc<DroppedReadHoldsQueue<T>>,
+}
+
+impl<T: TimelyTimestamp> DroppedReadHoldsTx<T> {
+    fn send(&self, read_holds: ReadHoldsInner<T>) {
+        // Hold the lock across the send so that the timestamps stay in the
+        // same order as the channel, and so that the receiver, which takes the
+        // overflow after draining the channel, can't miss it.
+        let mut state = self.queue.state.lock().expect("lock poisoned");
+        match self.tx.try_send(read_holds) {
+            Ok(()) => state.enqueued_at.push_back((self.queue.now)()),
+            Err(mpsc::error::TrySendError::Full(read_holds)) => match &mut state.overflow {
+                Some(overflow) => {
+                    overflow.count += 1;
+                    overflow.read_holds.merge(read_holds);
+                }
+                None => {
+                    state.overflow = Some(DroppedReadHoldsOverflow {
+                        enqueued_at: (self.queue.now)(),
+                        count: 1,
+                        read_holds,
+                    })
+                }
+            },
+            Err(e @ mpsc::error::TrySendError::Closed(_)) => {
+                tracing::warn!("error when trying to drop ReadHold: {:?}", e)
+            }
+        }
+        self.queue.update_metrics(&state);
+    }
+}
+
+impl<T: TimelyTimestamp> Clone for DroppedReadHoldsTx<T> {
+    fn clone(&self) -> Self {
+        DroppedReadHoldsTx {
+            tx: self.tx.clone(),
+            queue: Arc::clone(&self.queue),
         }
     }
 }
 
+impl<T: TimelyTimestamp> Debug for DroppedReadHoldsTx<T> {
+    fn fmt(&self, f: &mut std::fmt::Formatter<'_>) -> std::fmt::Result {
+        f.debug_struct("DroppedReadHoldsTx").finish_non_exhaustive()
+    }
+}
+
+/// The receiving half of the queue of dropped [ReadHolds].
+pub struct DroppedReadHoldsRx<T: TimelyTimestamp> {
+    rx: mpsc::Receiver<ReadHoldsInner<T>>,
+    queue: Arc<DroppedReadHoldsQueue<T>>,
+}
+
+impl<T: TimelyTimestamp> DroppedReadHoldsRx<T> {
+    /// Waits for at least one dropped hold and returns it, along with any
+    /// others that are already queued. Returns `None` once all senders are
+    /// gone.
+    ///
+    /// This method is cancel safe: it only awaits `Receiver::recv`, which is.
+    pub async fn recv_batch(&mut self) -> Option<Vec<ReadHoldsInner<T>>> {
+        let first = self.rx.recv().await?;
+        Some(self.drain(vec![first]))
+    }
+
+    /// Returns all dropped holds that are currently queued, without waiting.
+    pub fn try_recv_all(&mut self) -> Vec<ReadHoldsInner<T>> {
+        self.drain(Vec::new())
+    }
+
+    /// Reports whether dropped holds have been merged into the overflow entry
+    /// because more than [DROPPED_READ_HOLDS_CAPACITY] of them were queued.
+    pub fn over_capacity(&self) -> bool {
+        let state = self.queue.state.lock().expect("lock poisoned");
+        state.overflow.is_some()
+    }
+
+    /// Adds the holds queued in the channel and the overflow to `batch`, which
+    /// holds those already received from the channel.
+    fn drain(&mut self, mut batch: Vec<ReadHoldsInner<T>>) -> Vec<ReadHoldsInner<T>> {
+        while let Ok(read_holds) = self.rx.try_recv() {
+            batch.push(read_holds);
+        }
+        let mut state = self.queue.state.lock().expect("lock poisoned");
+        let n = std::cmp::min(batch.len(), state.enqueued_at.len());
+        state.enqueued_at.drain(..n);
+        if let Some(overflow) = state.overflow.take() {
+            batch.push(overflow.read_holds);
+        }
+        self.queue.update_metrics(&state);
+        batch
+    }
+}
+
 /// Inner state of [ReadHolds]. We have this separate so that we can send the
 /// inner state along a channel, for releasing when dropped.
 #[derive(Debug)]
@@ -263,6 +452,54 @@ impl<T: TimelyTimestamp> ReadHoldsInner<T> {
 
         res
     }
+
+    /// Return a `CollectionIdBundle` containing the replacement of each ID
+    /// in the [ReadHoldsInner] that appears in `replacements`.
+    ///
+    /// Replacements of compute IDs are placed on the compute instance returned
+    /// by `compute_instance`, and skipped if it returns `None`.
+    pub fn replacement_id_bundle(
+        &self,
+        replacements: &BTreeMap<GlobalId, GlobalId>,
+        compute_instance: impl Fn(GlobalId) -> Option<ComputeInstanceId>,
+    ) -> CollectionIdBundle {
+        let mut res = CollectionIdBundle::default();
+        for id in self.storage_holds.keys() {
+            if let Some(new_id) = replacements.get(id) {
+                res.storage_ids.insert(*new_id);
+            }
+        }
+        for (_instance_id, id) in self.compute_holds.keys() {
+            if let Some(new_id) = replacements.get(id) {
+                if let Some(instance_id) = compute_instance(*new_id) {
+                    res.compute_ids
+                        .entry(instance_id)
+                        .or_default()
+                        .insert(*new_id);
+                }
+            }
+        }
+
+        res
+    }
+
+    pub fn merge(&mut self, other: Self) {
+        for (id, other_hold) in other.storage_holds {
+            let existing_hold = self.storage_holds.entry(id);
+            match existing_hold {
+                hash_map::Entry::Occupied(mut o) => {
+                    o.get_mut().merge_assign(other_hold);
+                }
+                hash_map::Entry::Vacant(v) => {
+                    v.insert(other_hold);
+                }
+            }
+        }
+        for (id, mut other_hold) in other.compute_holds {
+            let hold = self.compute_holds.entry(id).or_default();
+            hold.update_iter(other_hold.updates().cloned());
+        }
+    }
 }
 
 impl<T: TimelyTimestamp + Lattice> ReadHoldsInner<T> {
@@ -302,22 +539,28 @@ impl<T: TimelyTimestamp + Lattice> ReadHoldsInner<T> {
         since
     }
 
-    pub fn merge(&mut self, other: Self) {
-        for (id, other_hold) in other.storage_holds {
-            let existing_hold = self.storage_holds.entry(id);
-            match existing_hold {
-                hash_map::Entry::Occupied(mut o) => {
-                    o.get_mut().merge_assign(other_hold);
-                }
-                hash_map::Entry::Vacant(v) => {
-                    v.insert(other_hold);
-                }
-            }
-        }
-        for (id, mut other_hold) in other.compute_holds {
-            let hold = self.compute_holds.entry(id).or_default();
-            hold.update_iter(other_hold.updates().cloned());
-        }
+    /// Returns the time closest to `target` at which all collections in this
+    /// [ReadHoldsInner] are readable, on a best-effort basis.
+    ///
+    /// This is `target` itself if it is not before the least valid read of
+    /// these read holds, and otherwise the least valid read.
+    pub fn best_effort_read_time(&self, target: T) -> T {
+        let mut time = target;
+        time.advance_by(self.least_valid_read().borrow());
+        time
+    }
+}
+
+impl ReadHoldsInner<Timestamp> {
+    /// Returns the time at which a read that lags `lag` behind the readable
+    /// time `readable` should happen.
+    ///
+    /// This is the time `lag` before `readable` or, if the held collections
+    /// have already been compacted past that time, the earliest time at which
+    /// they are readable instead. See the `default_as_of_lag` session
+    /// variable.
+    pub fn lagged_read_time(&self, readable: Timestamp, lag: Timestamp) -> Timestamp {
+        self.best_effort_read_time(readable.saturating_sub(lag))
     }
 }
 
@@ -327,6 +570,284 @@ impl<T: TimelyTimestamp> Default for ReadHoldsInner<T> {
     }
 }
 
+/// The kind of a read hold on a collection.
+#[derive(Clone, Copy, Debug, PartialEq, Eq, PartialOrd, Ord, Serialize, Deserialize)]
+pub enum ReadHoldKind {
+    /// A hold in the [TimelineReadHolds] of the timeline of the collection.
+    Timeline,
+    /// A hold in the [ReadHolds] of an open transaction.
+    Transaction,
+}
+
+/// A read hold on a collection, as reported by [Coordinator::read_holds_affecting].
+#[derive(Clone, Debug, PartialEq, Eq)]
+pub struct ReadHoldInfo<T> {
+    pub kind: ReadHoldKind,
+    /// The frontier at which the hold keeps the collection readable.
+    pub frontier: Antichain<T>,
+}
+
+/// The read holds affecting a set of collections, as reported by
+/// [Coordinator::read_holds_affecting].
+#[derive(Clone, Debug)]
+pub struct AffectingReadHolds<T> {
+    pub storage: BTreeMap<GlobalId, Vec<ReadHoldInfo<T>>>,
+    pub compute: BTreeMap<(ComputeInstanceId, GlobalId), Vec<ReadHoldInfo<T>>>,
+}
+
+impl<T> Default for AffectingReadHolds<T> {
+    fn default() -> Self {
+        AffectingReadHolds {
+            storage: BTreeMap::new(),
+            compute: BTreeMap::new(),
+        }
+    }
+}
+
+/// How often to refresh `mz_internal.mz_read_holds` and
+/// `mz_internal.mz_read_hold_waits`.
+const READ_HOLDS_INTROSPECTION_INTERVAL: Duration = Duration::from_secs(1);
+
+/// A holder of read holds, as reported in `mz_internal.mz_read_holds`.
+#[derive(Clone, Copy, Debug, PartialEq, Eq, PartialOrd, Ord)]
+pub enum ReadHolder {
+    /// The open transaction of the connection with the given ID.
+    Transaction(ConnectionIdType),
+    /// A pending peek of the connection with the given ID, which the COMPUTE
+    /// controller holds the peeked collection for until it is answered.
+    Peek(ConnectionIdType),
+    /// The compaction pause of the cluster of the held collection.
+    ///
+    /// See [Coordinator::update_compaction_pause_holds].
+    CompactionPause,
+}
+
+impl ReadHolder {
+    /// Returns the name of the kind of holder.
+    pub fn name(&self) -> &'static str {
+        match self {
+            ReadHolder::Transaction(_) => "transaction",
+            ReadHolder::Peek(_) => "peek",
+            ReadHolder::CompactionPause => "compaction-pause",
+        }
+    }
+
+    /// Returns the ID of the connection of the holder, if it has one.
+    pub fn connection_id(&self) -> Option<ConnectionIdType> {
+        match self {
+            ReadHolder::Transaction(conn_id) | ReadHolder::Peek(conn_id) => Some(*conn_id),
+            ReadHolder::CompactionPause => None,
+        }
+    }
+}
+
+/// A waiter on a collection, as reported in `mz_internal.mz_read_hold_waits`.
+#[derive(Clone, Copy, Debug, PartialEq, Eq, PartialOrd, Ord)]
+pub enum ReadHoldWaiter {
+    /// A pending peek of the connection with the given ID, which waits for its
+    /// cluster to answer it.
+    Peek(ConnectionIdType),
+    /// The cleanup of a dropped collection, which waits for a read hold on the
+    /// collection to be released.
+    Drop,
+}
+
+impl ReadHoldWaiter {
+    /// Returns the name of the kind of waiter.
+    pub fn name(&self) -> &'static str {
+        match self {
+            ReadHoldWaiter::Peek(_) => "peek",
+            ReadHoldWaiter::Drop => "drop",
+        }
+    }
+
+    /// Returns the ID of the connection of the waiter, if it has one.
+    pub fn connection_id(&self) -> Option<ConnectionIdType> {
+        match self {
+            ReadHoldWaiter::Peek(conn_id) => Some(*conn_id),
+            ReadHoldWaiter::Drop => None,
+        }
+    }
+}
+
+/// A wait on a collection, as reported in `mz_internal.mz_read_hold_waits`.
+#[derive(Clone, Copy, Debug, PartialEq, Eq, PartialOrd, Ord)]
+pub struct ReadHoldWait {
+    pub waiter: ReadHoldWaiter,
+    pub object_id: GlobalId,
+    /// The cluster of the COMPUTE collection waited on, or `None` for a
+    /// STORAGE collection.
+    pub cluster_id: Option<ComputeInstanceId>,
+    /// The holder whose read hold the waiter waits to be released, or `None`
+    /// for peeks, which wait for their cluster rather than for a holder.
+    pub holder: Option<ReadHolder>,
+    /// For peeks, the time they read at. For drops, the time at which the hold
+    /// they wait on keeps the collection readable.
+    pub timestamp: Option<Timestamp>,
+}
+
+/// A read hold on a collection, as reported in `mz_internal.mz_read_holds`.
+#[derive(Clone, Copy, Debug, PartialEq, Eq, PartialOrd, Ord)]
+pub struct ReadHoldRecord {
+    pub object_id: GlobalId,
+    /// The cluster of the held COMPUTE collection, or `None` for a STORAGE
+    /// collection.
+    pub cluster_id: Option<ComputeInstanceId>,
+    pub holder: ReadHolder,
+    /// The time at which the hold keeps the collection readable, or `None` if
+    /// the hold doesn't hold back compaction anymore.
+    pub read_frontier: Option<Timestamp>,
+}
+
+/// The state of `mz_internal.mz_read_holds` and
+/// `mz_internal.mz_read_hold_waits`, see
+/// [Coordinator::update_read_holds_introspection].
+#[derive(Debug)]
+pub struct ReadHoldsIntrospection {
+    /// The read holds last reported.
+    records: BTreeSet<ReadHoldRecord>,
+    /// The waits last reported.
+    waits: BTreeSet<ReadHoldWait>,
+    /// The waits of dropped collections last logged.
+    dropped: BTreeSet<ReadHoldWait>,
+    /// When `records` was last refreshed.
+    updated_at: Instant,
+}
+
+impl Default for ReadHoldsIntrospection {
+    fn default() -> Self {
+        ReadHoldsIntrospection {
+            records: BTreeSet::new(),
+            waits: BTreeSet::new(),
+            dropped: BTreeSet::new(),
+            updated_at: Instant::now(),
+        }
+    }
+}
+
+/// The read policies of collections, by collection ID.
+type ReadPolicies = Vec<(GlobalId, ReadPolicy<Timestamp>)>;
+
+/// The collections whose read capabilities changed since their read policies
+/// were last sent to the controllers.
+///
+/// Read capabilities change often, e.g., every peek acquires and releases read
+/// holds, and busy periods would produce a burst of controller traffic if each
+/// change was sent right away. The [`Coordinator`] instead records each changed
+/// collection here and sends the then current read policies of all recorded
+/// collections once per message it handles, see
+/// [`Coordinator::flush_read_policy_updates`]. Collections changed several
+/// times within a message are sent once, with the policy the last change would
+/// have sent.
+///
+/// Sending a policy late is harmless if it allows more compaction, as is the
+/// case when holds are released or advanced: the collection is merely kept
+/// readable for longer. A policy that allows less compaction, because a hold
+/// was installed, must not be needed to keep the held times readable until it
+/// has been sent:
+///
+/// * The COMPUTE controller only advances the sinces of collections when the
+///   coordinator calls into it, which happens when it handles a controller
+///   message, after the updates of the previous message have been sent. COMPUTE
+///   holds are installed at or after the current since of their collections,
+///   so those times stay readable until the new policy is in place.
+/// * The STORAGE controller advances sinces in a background task, as uppers
+///   advance and its own read holds are released, so STORAGE read policies can
+///   be stale when it does. Read holds that the coordinator hands out for
+///   STORAGE collections are therefore read holds of the STORAGE controller,
+///   which take effect immediately, and the STORAGE holds of timelines are
+///   advisory. The exception is
+///   [`Coordinator::initialize_read_policies_bulk`], which keeps STORAGE
+///   controller read holds around until it has flushed the policies of new
+///   collections.
+#[derive(Debug, Default)]
+pub struct PendingReadPolicyUpdates {
+    storage: BTreeSet<GlobalId>,
+    compute: BTreeMap<ComputeInstanceId, BTreeSet<GlobalId>>,
+}
+
+impl PendingReadPolicyUpdates {
+    /// Records a change to the read capability of a STORAGE collection.
+    fn record_storage(&mut self, id: GlobalId) {
+        self.storage.insert(id);
+    }
+
+    /// Records a change to the read capability of a COMPUTE collection.
+    fn record_compute(&mut self, instance_id: ComputeInstanceId, id: GlobalId) {
+        self.compute.entry(instance_id).or_default().insert(id);
+    }
+
+    /// Removes all recorded collections and returns their current read
+    /// policies, for STORAGE and for each COMPUTE instance.
+    ///
+    /// Collections without a read capability have been dropped since they
+    /// were recorded and are skipped.
+    fn drain(
+        &mut self,
+        storage_read_capabilities: &BTreeMap<GlobalId, ReadCapability<Timestamp>>,
+        compute_read_capabilities: &BTreeMap<GlobalId, ReadCapability<Timestamp>>,
+    ) -> (ReadPolicies, BTreeMap<ComputeInstanceId, ReadPolicies>) {
+        let PendingReadPolicyUpdates { storage, compute } = std::mem::take(self);
+        let storage_policies = storage
+            .into_iter()
+            .filter_map(|id| {
+                let capability = storage_read_capabilities.get(&id)?;
+                Some((id, capability.policy()))
+            })
+            .collect();
+        let compute_policies = compute
+            .into_iter()
+            .map(|(instance_id, ids)| {
+                let policies: ReadPolicies = ids
+                    .into_iter()
+                    .filter_map(|id| {
+                        let capability = compute_read_capabilities.get(&id)?;
+                        Some((id, capability.policy()))
+                    })
+                    .collect();
+                (instance_id, policies)
+            })
+            .filter(|(_, policies)| !policies.is_empty())
+            .collect();
+        (storage_policies, compute_policies)
+    }
+}
+
+/// Adds the COMPUTE holds in `read_holds` to the read capabilities of their
+/// collections, which must exist, and records the changes in `pending`.
+fn install_compute_holds(
+    compute_read_capabilities: &mut BTreeMap<GlobalId, ReadCapability<Timestamp>>,
+    pending: &mut PendingReadPolicyUpdates,
+    read_holds: &mut ReadHoldsInner<Timestamp>,
+) {
+    for ((compute_instance, id), hold) in read_holds.compute_holds.iter_mut() {
+        let read_needs = compute_read_capabilities
+            .get_mut(id)
+            .expect("capability known to exist");
+        read_needs.holds.update_iter(hold.updates().cloned());
+        pending.record_compute(*compute_instance, *id);
+    }
+}
+
+/// Removes the COMPUTE holds in `read_holds` from the read capabilities of
+/// their collections and records the changes in `pending`.
+///
+/// Collections without a read capability have been dropped already and are
+/// skipped.
+fn release_compute_holds(
+    compute_read_capabilities: &mut BTreeMap<GlobalId, ReadCapability<Timestamp>>,
+    pending: &mut PendingReadPolicyUpdates,
+    read_holds: &mut ReadHoldsInner<Timestamp>,
+) {
+    for ((compute_instance, id), hold) in read_holds.compute_holds.iter_mut() {
+        if let Some(read_needs) = compute_read_capabilities.get_mut(id) {
+            let inverted_hold = hold.updates().map(|(t, diff)| (*t, -diff));
+            read_needs.holds.update_iter(inverted_hold);
+            pending.record_compute(*compute_instance, *id);
+        }
+    }
+}
+
 impl crate::coord::Coordinator {
     /// Initialize the storage read policies.
     ///
@@ -376,11 +897,27 @@ impl crate::coord::Coordinator {
     /// This should be called only after a collection is created, and
     /// ideally very soon afterwards. The collection is otherwise initialized
     /// with a read policy that allows no compaction.
-    #[instrument(name = "coord::initialize_read_policies")]
     pub(crate) async fn initialize_read_policies(
         &mut self,
         id_bundle: &CollectionIdBundle,
         compaction_window: CompactionWindow,
+    ) {
+        let mut policies = BTreeMap::new();
+        policies.insert(compaction_window, id_bundle.clone());
+        self.initialize_read_policies_bulk(policies).await;
+    }
+
+    /// Initialize the storage and compute read policies of the collections in
+    /// each id bundle with the corresponding compaction window.
+    ///
+    /// This is equivalent to calling [`Coordinator::initialize_read_policies`]
+    /// for each entry of `policies`, but reads the timestamp of each timeline
+    /// only once and issues a single `set_read_policy` call per controller,
+    /// which matters when initializing the entire catalog at bootstrap.
+    #[instrument(name = "coord::initialize_read_policies")]
+    pub(crate) async fn initialize_read_policies_bulk(
+        &mut self,
+        policies: BTreeMap<CompactionWindow, CollectionIdBundle>,
     ) {
         // When initializing read policies we acquire a hold from STORAGE. We
         // have to keep those until we install our read policy all the way at
@@ -445,76 +982,92 @@ impl crate::coord::Coordinator {
             read_holds
         };
 
-        let mut compute_policy_updates: BTreeMap<ComputeInstanceId, Vec<_>> = BTreeMap::new();
-        let mut storage_policy_updates = Vec::new();
-        let mut id_bundles: HashMap<_, CollectionIdBundle> = HashMap::new();
-
-        // Update the Coordinator's timeline read hold state and organize all id bundles by time.
-        for (timeline_context, id_bundle) in self.partition_ids_by_timeline_context(id_bundle) {
-            match timeline_context {
-                TimelineContext::TimelineDependent(timeline) => {
-                    let TimelineState { oracle, .. } = self.ensure_timeline_state(&timeline).await;
-                    let read_ts = oracle.read_ts().await;
-                    let new_read_holds = initialize_read_holds(self, read_ts, &id_bundle);
-                    let TimelineState { read_holds, .. } =
-                        self.ensure_timeline_state(&timeline).await;
-                    for (time, id_bundle) in &new_read_holds.holds {
-                        id_bundles
-                            .entry(Some(time.clone()))
-                            .or_default()
-                            .extend(id_bundle);
+        let mut read_timestamps = BTreeMap::new();
+        let mut compute_ids: BTreeMap<_, BTreeSet<_>> = BTreeMap::new();
+
+        for (compaction_window, id_bundle) in policies {
+            for (compute_instance, ids) in &id_bundle.compute_ids {
+                compute_ids
+                    .entry(*compute_instance)
+                    .or_default()
+                    .extend(ids.iter().copied());
+            }
+
+            let mut id_bundles: HashMap<_, CollectionIdBundle> = HashMap::new();
+
+            // Update the Coordinator's timeline read hold state and organize all id bundles by time.
+            for (timeline_context, id_bundle) in self.partition_ids_by_timeline_context(&id_bundle)
+            {
+                match timeline_context {
+                    TimelineContext::TimelineDependent(timeline) => {
+                        let read_ts = match read_timestamps.get(&timeline) {
+                            Some(read_ts) => *read_ts,
+                            None => {
+                                let TimelineState { oracle, .. } =
+                                    self.ensure_timeline_state(&timeline).await;
+                                let read_ts = oracle.read_ts().await;
+                                read_timestamps.insert(timeline.clone(), read_ts);
+                                read_ts
+                            }
+                        };
+                        let new_read_holds = initialize_read_holds(self, read_ts, &id_bundle);
+                        let TimelineState { read_holds, .. } =
+                            self.ensure_timeline_state(&timeline).await;
+                        for (time, id_bundle) in &new_read_holds.holds {
+                            id_bundles
+                                .entry(Some(time.clone()))
+                                .or_default()
+                                .extend(id_bundle);
+                        }
+                        read_holds.extend_with_new(new_read_holds);
+                    }
+                    TimelineContext::TimestampIndependent | TimelineContext::TimestampDependent => {
+                        id_bundles.entry(None).or_default().extend(&id_bundle);
                     }
-                    read_holds.extend_with_new(new_read_holds);
-                }
-                TimelineContext::TimestampIndependent | TimelineContext::TimestampDependent => {
-                    id_bundles.entry(None).or_default().extend(&id_bundle);
                 }
             }
-        }
 
-        // Create read capabilities for all objects.
-        for (time, id_bundle) in id_bundles {
-            for (compute_instance, compute_ids) in id_bundle.compute_ids {
-                for id in compute_ids {
-                    let read_capability = self.ensure_compute_capability(
-                        &compute_instance,
-                        &id,
-                        Some(compaction_window.clone()),
-                    );
+            // Create read capabilities for all objects.
+            for (time, id_bundle) in id_bundles {
+                for (compute_instance, compute_ids) in id_bundle.compute_ids {
+                    for id in compute_ids {
+                        let read_capability = self.ensure_compute_capability(
+                            &compute_instance,
+                            &id,
+                            Some(compaction_window.clone()),
+                        );
+                        if let Some(time) = &time {
+                            read_capability
+                                .holds
+                                .update_iter(time.iter().map(|t| (*t, 1)));
+                        }
+                        self.pending_read_policy_updates
+                            .record_compute(compute_instance, id);
+                    }
+                }
+
+                for id in id_bundle.storage_ids {
+                    let read_capability =
+                        self.ensure_storage_capability(&id, Some(compaction_window.clone()));
                     if let Some(time) = &time {
                         read_capability
                             .holds
                             .update_iter(time.iter().map(|t| (*t, 1)));
                     }
-                    compute_policy_updates
-                        .entry(compute_instance)
-                        .or_default()
-                        .push((id, self.compute_read_capabilities[&id].policy()));
+                    self.pending_read_policy_updates.record_storage(id);
                 }
             }
-
-            for id in id_bundle.storage_ids {
-                let read_capability =
-                    self.ensure_storage_capability(&id, Some(compaction_window.clone()));
-                if let Some(time) = &time {
-                    read_capability
-                        .holds
-                        .update_iter(time.iter().map(|t| (*t, 1)));
-                }
-                storage_policy_updates.push((id, self.storage_read_capabilities[&id].policy()));
-            }
         }
 
-        // Apply read capabilities.
-        for (compute_instance, compute_policy_updates) in compute_policy_updates {
-            self.controller
-                .compute
-                .set_read_policy(compute_instance, compute_policy_updates)
-                .unwrap_or_terminate("cannot fail to set read policy");
-        }
-        self.controller
-            .storage
-            .set_read_policy(storage_policy_updates);
+        // Apply read capabilities. The STORAGE controller compacts in the
+        // background, so the policies must be installed before we relinquish
+        // the holds below rather than when the current message has been
+        // handled.
+        self.flush_read_policy_updates();
+
+        // Hold new collections on clusters whose compaction is paused before
+        // they get a chance to compact.
+        self.pause_compaction_of_new_collections(compute_ids);
 
         // Now that we installed our read policy updates we can relinquish holds
         // that we used to determine and hold collection sinces.
@@ -531,8 +1084,6 @@ impl crate::coord::Coordinator {
         // After this, read_holds.holds is initialized to an empty HashMap.
         let old_holds = std::mem::take(&mut read_holds.holds);
 
-        let mut storage_policy_changes = Vec::new();
-        let mut compute_policy_changes: BTreeMap<_, Vec<_>> = BTreeMap::new();
         let new_time = Antichain::from_elem(new_time);
 
         for (old_time, id_bundle) in old_holds {
@@ -562,7 +1113,7 @@ impl crate::coord::Coordinator {
                         .holds
                         .update_iter(old_time.iter().map(|t| (*t, -1)));
 
-                    storage_policy_changes.push((id, read_needs.policy()));
+                    self.pending_read_policy_updates.record_storage(id);
                 }
 
                 for (compute_instance, compute_ids) in id_bundle.compute_ids {
@@ -590,10 +1141,8 @@ impl crate::coord::Coordinator {
                         read_needs
                             .holds
                             .update_iter(old_time.iter().map(|t| (*t, -1)));
-                        compute_policy_changes
-                            .entry(compute_instance)
-                            .or_default()
-                            .push((id, read_needs.policy()));
+                        self.pending_read_policy_updates
+                            .record_compute(compute_instance, id);
                     }
                 }
             } else {
@@ -604,19 +1153,6 @@ impl crate::coord::Coordinator {
                     .extend(&id_bundle);
             }
         }
-
-        // Update STORAGE read policies.
-        self.controller
-            .storage
-            .set_read_policy(storage_policy_changes);
-
-        // Update COMPUTE read policies
-        for (compute_instance, compute_policy_changes) in compute_policy_changes {
-            self.controller
-                .compute
-                .set_read_policy(compute_instance, compute_policy_changes)
-                .unwrap_or_terminate("cannot fail to set read policy");
-        }
     }
 
     /// If there is not capability for the given object, initialize one at the
@@ -704,41 +1240,28 @@ impl crate::coord::Coordinator {
         &mut self,
         base_policies: Vec<(GlobalId, ReadPolicy<mz_repr::Timestamp>)>,
     ) {
-        let mut policies = Vec::with_capacity(base_policies.len());
         for (id, base_policy) in base_policies {
             let capability = self
                 .storage_read_capabilities
                 .get_mut(&id)
                 .expect("coord out of sync");
             capability.base_policy = base_policy;
-            policies.push((id, capability.policy()))
+            self.pending_read_policy_updates.record_storage(id);
         }
-        self.controller.storage.set_read_policy(policies)
     }
 
     pub(crate) fn update_compute_base_read_policies(
         &mut self,
-        mut base_policies: Vec<(ComputeInstanceId, GlobalId, ReadPolicy<mz_repr::Timestamp>)>,
+        base_policies: Vec<(ComputeInstanceId, GlobalId, ReadPolicy<mz_repr::Timestamp>)>,
     ) {
-        base_policies.sort_by_key(|&(cluster_id, _, _)| cluster_id);
-        for (cluster_id, group) in &base_policies
-            .into_iter()
-            .group_by(|&(cluster_id, _, _)| cluster_id)
-        {
-            let group = group
-                .map(|(_, id, base_policy)| {
-                    let capability = self
-                        .compute_read_capabilities
-                        .get_mut(&id)
-                        .expect("coord out of sync");
-                    capability.base_policy = base_policy;
-                    (id, capability.policy())
-                })
-                .collect::<Vec<_>>();
-            self.controller
-                .compute
-                .set_read_policy(cluster_id, group)
-                .unwrap_or_terminate("cannot fail to set read policy");
+        for (cluster_id, id, base_policy) in base_policies {
+            let capability = self
+                .compute_read_capabilities
+                .get_mut(&id)
+                .expect("coord out of sync");
+            capability.base_policy = base_policy;
+            self.pending_read_policy_updates
+                .record_compute(cluster_id, id);
         }
     }
 
@@ -776,14 +1299,36 @@ impl crate::coord::Coordinator {
         &mut self,
         id_bundle: &CollectionIdBundle,
     ) -> ReadHolds<Timestamp> {
+        self.acquire_read_holds_at(&Antichain::from_elem(Timestamp::MIN), id_bundle, false)
+            .expect("can always acquire non-precise read holds")
+    }
+
+    /// Attempt to acquire read holds on the indicated collections at the
+    /// indicated `frontier`.
+    ///
+    /// If `precise` is `true`, each collection is held at exactly `frontier`,
+    /// and if the since of any of the collections is not less than or equal to
+    /// `frontier`, no holds are acquired and the collections that can't be held
+    /// are returned instead. Otherwise, collections are held at `frontier`
+    /// advanced to their since, and this never fails.
+    ///
+    /// # Panics
+    ///
+    /// Will panic if any of the referenced collections in `id_bundle` don't
+    /// exist.
+    pub(crate) fn acquire_read_holds_at(
+        &mut self,
+        frontier: &Antichain<Timestamp>,
+        id_bundle: &CollectionIdBundle,
+        precise: bool,
+    ) -> Result<ReadHolds<Timestamp>, CollectionIdBundle> {
         // Create a `ReadHoldsInner` that contains a read hold for each id in
-        // `id_bundle`. The time of each read holds is at `time`, if possible
-        // otherwise it is at the lowest possible time.
+        // `id_bundle`.
         //
         // This does not apply the read holds in COMPUTE. The code below applies
         // those in the correct read capability.
         let mut read_holds = ReadHoldsInner::new();
-        let time_antichain = Antichain::from_elem(Timestamp::MIN);
+        let mut unavailable = CollectionIdBundle::default();
 
         let desired_storage_holds = id_bundle.storage_ids.iter().map(|id| *id).collect_vec();
         let storage_read_holds = self
@@ -792,7 +1337,17 @@ impl crate::coord::Coordinator {
             .acquire_read_holds(desired_storage_holds)
             .expect("missing collections");
 
-        for storage_read_hold in storage_read_holds {
+        for mut storage_read_hold in storage_read_holds {
+            let Some(hold_frontier) = hold_frontier(storage_read_hold.since(), frontier, precise)
+            else {
+                unavailable.storage_ids.insert(storage_read_hold.id());
+                continue;
+            };
+            if PartialOrder::less_than(storage_read_hold.since(), &hold_frontier) {
+                storage_read_hold
+                    .try_downgrade(hold_frontier)
+                    .expect("frontier is beyond the since");
+            }
             let prev = read_holds
                 .storage_holds
                 .insert(storage_read_hold.id(), storage_read_hold);
@@ -810,35 +1365,88 @@ impl crate::coord::Coordinator {
                     .compute
                     .collection(*compute_instance, *id)
                     .expect("collection does not exist");
-                let read_frontier = collection.read_capability().clone();
-                let time_antichain = time_antichain.join(&read_frontier);
-                let hold_chain = MutableAntichain::from(time_antichain);
+                let Some(hold_frontier) =
+                    hold_frontier(collection.read_capability(), frontier, precise)
+                else {
+                    unavailable
+                        .compute_ids
+                        .entry(*compute_instance)
+                        .or_default()
+                        .insert(*id);
+                    continue;
+                };
+                let hold_chain = MutableAntichain::from(hold_frontier);
                 read_holds
                     .compute_holds
                     .insert((*compute_instance, *id), hold_chain);
             }
         }
 
-        // Update COMPUTE read policies
-        let mut policy_changes: HashMap<_, Vec<_>> = HashMap::new();
-        for ((compute_instance, id), hold) in read_holds.compute_holds.iter_mut() {
-            let read_needs = self.ensure_compute_capability(compute_instance, id, None);
-            read_needs.holds.update_iter(hold.updates().cloned());
-            policy_changes
-                .entry(*compute_instance)
-                .or_default()
-                .push((*id, read_needs.policy()));
+        // Dropping the storage holds releases them, and the compute holds have
+        // not been installed yet.
+        if !unavailable.is_empty() {
+            return Err(unavailable);
         }
 
-        for (compute_instance, policy_changes) in policy_changes {
-            self.controller
-                .compute
-                .set_read_policy(compute_instance, policy_changes)
-                .unwrap_or_terminate("cannot fail to set read policy");
+        Ok(self.install_read_holds(read_holds))
+    }
+
+    /// Acquires read holds on the indicated collections for a read at
+    /// `requested`, without holding them at times they aren't readable at yet.
+    ///
+    /// Unlike [`Coordinator::acquire_read_holds`], which
+    /// holds the sinces of the collections regardless of when they are read,
+    /// this holds each collection at the least of `requested` and the largest
+    /// time not in advance of the write frontier of the collections, advanced
+    /// to their sinces. Returns the holds and the time they were installed at, which is
+    /// `requested` only if the collections are readable at it.
+    ///
+    /// # Panics
+    ///
+    /// Will panic if any of the referenced collections in `id_bundle` don't
+    /// exist.
+    pub(crate) fn acquire_read_holds_for(
+        &mut self,
+        requested: Timestamp,
+        id_bundle: &CollectionIdBundle,
+    ) -> (ReadHolds<Timestamp>, Timestamp) {
+        // Keep the sinces from advancing past the chosen time while the holds
+        // at that time are acquired.
+        let mut since_holds = self.acquire_read_holds(id_bundle);
+        let upper = self.least_valid_write(id_bundle);
+        let readable = Coordinator::largest_not_in_advance_of_upper(&upper);
+        let time = since_holds.best_effort_read_time(std::cmp::min(requested, readable));
+        let read_holds = self
+            .acquire_read_holds_at(&Antichain::from_elem(time), id_bundle, true)
+            .unwrap_or_else(|_| panic!("collections are held at or before {time}"));
+        // Dropping the now empty `ReadHolds` is a no-op.
+        let inner = std::mem::take(&mut since_holds.inner);
+        self.release_read_holds(vec![inner]);
+        tracing::debug!(%requested, %time, ?id_bundle, "acquired read holds");
+        (read_holds, time)
+    }
+
+    /// Installs the COMPUTE holds in `read_holds` in the read capabilities of
+    /// their collections and wraps them in [`ReadHolds`].
+    ///
+    /// The STORAGE holds in `read_holds` are expected to already be held by
+    /// the STORAGE controller.
+    fn install_read_holds(
+        &mut self,
+        mut read_holds: ReadHoldsInner<Timestamp>,
+    ) -> ReadHolds<Timestamp> {
+        // Update COMPUTE read policies
+        for (compute_instance, id) in read_holds.compute_holds.keys() {
+            self.ensure_compute_capability(compute_instance, id, None);
         }
+        install_compute_holds(
+            &mut self.compute_read_capabilities,
+            &mut self.pending_read_policy_updates,
+            &mut read_holds,
+        );
 
         let read_holds = ReadHolds::new(read_holds, self.dropped_read_holds_tx.clone());
-        tracing::debug!(?read_holds, "acquire_read_holds");
+        tracing::debug!(?read_holds, "install_read_holds");
         read_holds
     }
 
@@ -861,6 +1469,520 @@ impl crate::coord::Coordinator {
         }
     }
 
+    /// Chains the transaction read holds on replaced collections to their
+    /// replacements.
+    ///
+    /// When the catalog replaces objects with new collections, as `ALTER ...
+    /// SWAP` does, open transactions still hold the old `GlobalId`s and could
+    /// not read the replacements. For each transaction that holds one of the
+    /// keys of `replacements`, this acquires read holds on the corresponding
+    /// replacement IDs and stores them with the transaction's read holds. The
+    /// holds on the old IDs are kept until the transaction ends, so its
+    /// snapshot stays valid for both.
+    ///
+    /// Replacements that don't exist in the controllers are skipped.
+    pub(crate) fn chain_txn_read_holds(&mut self, replacements: &BTreeMap<GlobalId, GlobalId>) {
+        if replacements.is_empty() {
+            return;
+        }
+
+        let catalog = self.catalog();
+        let compute_instance = |id| {
+            catalog
+                .try_get_entry(&id)
+                .and_then(|entry| entry.index())
+                .map(|index| index.cluster_id)
+        };
+        let mut chained = Vec::new();
+        for (conn_id, read_holds) in &self.txn_read_holds {
+            let mut id_bundle = read_holds.replacement_id_bundle(replacements, compute_instance);
+            id_bundle
+                .storage_ids
+                .retain(|id| self.controller.storage.check_exists(*id).is_ok());
+            for (instance_id, ids) in id_bundle.compute_ids.iter_mut() {
+                ids.retain(|id| {
+                    self.controller
+                        .compute
+                        .collection(*instance_id, *id)
+                        .is_ok()
+                });
+            }
+            id_bundle.compute_ids.retain(|_, ids| !ids.is_empty());
+            if !id_bundle.is_empty() {
+                chained.push((conn_id.clone(), read_holds.least_valid_read(), id_bundle));
+            }
+        }
+
+        for (conn_id, frontier, id_bundle) in chained {
+            // Hold the replacements where the transaction holds the replaced
+            // collections, if possible.
+            let new_read_holds = self
+                .acquire_read_holds_at(&frontier, &id_bundle, false)
+                .expect("can always acquire non-precise read holds");
+            tracing::debug!(%conn_id, ?new_read_holds, "chaining txn read holds");
+            self.txn_read_holds
+                .get_mut(&conn_id)
+                .expect("txn read holds exist")
+                .merge(new_read_holds);
+        }
+    }
+
+    /// Returns the read holds that affect the collections in `id_bundle`.
+    ///
+    /// These are the holds in the [TimelineReadHolds] of the timelines of the
+    /// collections and, if the connection `conn_id` has an open transaction,
+    /// the transaction's read holds. Collections without any such holds are
+    /// omitted.
+    pub(crate) fn read_holds_affecting(
+        &self,
+        conn_id: &ConnectionId,
+        id_bundle: &CollectionIdBundle,
+    ) -> AffectingReadHolds<Timestamp> {
+        let mut holds = AffectingReadHolds::default();
+        let mut add_storage = |id: &GlobalId, kind, frontier: &Antichain<Timestamp>| {
+            if id_bundle.storage_ids.contains(id) {
+                holds.storage.entry(*id).or_default().push(ReadHoldInfo {
+                    kind,
+                    frontier: frontier.clone(),
+                });
+            }
+        };
+        for TimelineState { read_holds, .. } in self.global_timelines.values() {
+            for (time, id) in read_holds.storage_ids() {
+                add_storage(id, ReadHoldKind::Timeline, time);
+            }
+        }
+        let txn_read_holds = self.txn_read_holds.get(conn_id);
+        if let Some(txn_read_holds) = txn_read_holds {
+            for (id, hold) in &txn_read_holds.storage_holds {
+                add_storage(id, ReadHoldKind::Transaction, hold.since());
+            }
+        }
+
+        let mut add_compute =
+            |compute_instance: &ComputeInstanceId, id: &GlobalId, kind, frontier| {
+                let wanted = id_bundle
+                    .compute_ids
+                    .get(compute_instance)
+                    .map_or(false, |ids| ids.contains(id));
+                if wanted {
+                    holds
+                        .compute
+                        .entry((*compute_instance, *id))
+                        .or_default()
+                        .push(ReadHoldInfo { kind, frontier });
+                }
+            };
+        for TimelineState { read_holds, .. } in self.global_timelines.values() {
+            for (compute_instance, ids) in read_holds.compute_ids() {
+                for (time, id) in ids {
+                    add_compute(compute_instance, id, ReadHoldKind::Timeline, time.clone());
+                }
+            }
+        }
+        if let Some(txn_read_holds) = txn_read_holds {
+            for ((compute_instance, id), hold) in &txn_read_holds.compute_holds {
+                let frontier = hold.frontier().to_owned();
+                add_compute(compute_instance, id, ReadHoldKind::Transaction, frontier);
+            }
+        }
+
+        holds
+    }
+
+    /// Releases the transaction read holds of the connection with the given
+    /// ID, if any.
+    ///
+    /// Unlike dropping the [`ReadHolds`], which defers the release to a later
+    /// [`Message::DropReadHolds`], this releases the holds immediately.
+    ///
+    /// [`Message::DropReadHolds`]: crate::coord::Message::DropReadHolds
+    pub(crate) fn release_txn_read_holds(&mut self, conn_id: &ConnectionId) {
+        if let Some(mut txn_reads) = self.txn_read_holds.remove(conn_id) {
+            tracing::debug!(?txn_reads, "releasing txn read holds");
+            // Dropping the now empty `ReadHolds` is a no-op.
+            let inner = std::mem::take(&mut txn_reads.inner);
+            self.release_read_holds(vec![inner]);
+        }
+    }
+
+    /// Releases the transaction read holds of all connections that are no
+    /// longer active.
+    ///
+    /// Transaction read holds are released when their connection terminates.
+    /// This periodically sweeps up any holds that escaped that cleanup, as they
+    /// would otherwise stall compaction of the held collections forever.
+    pub(crate) fn sweep_txn_read_holds(&mut self) {
+        let dead_conn_ids: Vec<_> = self
+            .txn_read_holds
+            .keys()
+            .filter(|conn_id| !self.active_conns.contains_key(*conn_id))
+            .cloned()
+            .collect();
+        for conn_id in dead_conn_ids {
+            tracing::warn!(%conn_id, "releasing txn read holds of terminated connection");
+            self.release_txn_read_holds(&conn_id);
+        }
+    }
+
+    /// Reconciles the read holds that pause compaction on the clusters named
+    /// by the `compaction_paused_clusters` config.
+    ///
+    /// Each compute collection on a paused cluster is held at its read
+    /// frontier as of when it was first held, which keeps the collection from
+    /// compacting without affecting the collections of other clusters. This
+    /// runs whenever the config changes. Collections created on a paused
+    /// cluster afterwards are held when their read policies are initialized,
+    /// and the holds of dropped collections are released when they are
+    /// dropped, see [`Coordinator::pause_compaction_of_new_collections`] and
+    /// [`Coordinator::release_compaction_pause_holds`]. Removing a cluster from
+    /// the config, or dropping it, releases its holds. Names that don't
+    /// resolve to a cluster are ignored, with a warning the first time they
+    /// are seen.
+    pub(crate) fn update_compaction_pause_holds(&mut self) {
+        let paused = self.compaction_paused_clusters();
+
+        let resumed: Vec<_> = self
+            .compaction_pause_holds
+            .keys()
+            .filter(|cluster_id| !paused.contains(*cluster_id))
+            .copied()
+            .collect();
+        for cluster_id in resumed {
+            tracing::info!(%cluster_id, "resuming compaction");
+            let mut read_holds = self
+                .compaction_pause_holds
+                .remove(&cluster_id)
+                .expect("known to exist");
+            // Dropping the now empty `ReadHolds` is a no-op.
+            let inner = std::mem::take(&mut read_holds.inner);
+            self.release_read_holds(vec![inner]);
+        }
+
+        for cluster_id in paused {
+            let Ok(instance) = self.controller.compute.instance_ref(cluster_id) else {
+                continue;
+            };
+            let collections: BTreeSet<_> = instance
+                .collections()
+                .map(|(id, _)| *id)
+                .filter(|id| !id.is_transient())
+                .collect();
+            self.hold_paused_collections(cluster_id, collections);
+        }
+    }
+
+    /// Holds the given new compute collections if their cluster's compaction
+    /// is paused, so they don't compact before the next time the config
+    /// changes.
+    ///
+    /// Called once the read policies of the collections are initialized.
+    pub(crate) fn pause_compaction_of_new_collections(
+        &mut self,
+        compute_ids: BTreeMap<ComputeInstanceId, BTreeSet<GlobalId>>,
+    ) {
+        if compute_ids.is_empty() {
+            return;
+        }
+        let paused = self.compaction_paused_clusters();
+        for (cluster_id, ids) in compute_ids {
+            if paused.contains(&cluster_id) {
+                let ids = ids.into_iter().filter(|id| !id.is_transient()).collect();
+                self.hold_paused_collections(cluster_id, ids);
+            }
+        }
+    }
+
+    /// Releases the compaction pause holds of the given compute collections,
+    /// which are being dropped, and of all collections of the given clusters,
+    /// which are being dropped too.
+    pub(crate) fn release_compaction_pause_holds(
+        &mut self,
+        collections: impl IntoIterator<Item = (ComputeInstanceId, GlobalId)>,
+        clusters: impl IntoIterator<Item = ComputeInstanceId>,
+    ) {
+        let mut released = ReadHoldsInner::new();
+        for key in collections {
+            if let Some(held) = self.compaction_pause_holds.get_mut(&key.0) {
+                if let Some(hold) = held.inner.compute_holds.remove(&key) {
+                    released.compute_holds.insert(key, hold);
+                }
+            }
+        }
+        for cluster_id in clusters {
+            if let Some(mut held) = self.compaction_pause_holds.remove(&cluster_id) {
+                // Dropping the now empty `ReadHolds` is a no-op.
+                released.merge(std::mem::take(&mut held.inner));
+            }
+        }
+        if !released.compute_holds.is_empty() {
+            self.release_read_holds(vec![released]);
+        }
+    }
+
+    /// Returns the clusters named by the `compaction_paused_clusters` config.
+    fn compaction_paused_clusters(&mut self) -> BTreeSet<ComputeInstanceId> {
+        let names = COMPACTION_PAUSED_CLUSTERS.get(self.catalog().system_config().dyncfgs());
+        let mut paused = BTreeSet::new();
+        let mut unknown = BTreeSet::new();
+        for name in names
+            .split(',')
+            .map(str::trim)
+            .filter(|name| !name.is_empty())
+        {
+            match self.catalog().resolve_cluster(name) {
+                Ok(cluster) => {
+                    paused.insert(cluster.id);
+                }
+                Err(_) => {
+                    // Only warn the first time a name fails to resolve, not
+                    // every time the config is read.
+                    if !self.unknown_compaction_paused_clusters.contains(name) {
+                        tracing::warn!(
+                            cluster = name,
+                            "cannot pause compaction of unknown cluster"
+                        );
+                    }
+                    unknown.insert(name.to_string());
+                }
+            }
+        }
+        self.unknown_compaction_paused_clusters = unknown;
+        paused
+    }
+
+    /// Holds those of the given collections of the paused cluster `cluster_id`
+    /// at their read frontier that aren't held yet.
+    fn hold_paused_collections(
+        &mut self,
+        cluster_id: ComputeInstanceId,
+        collections: BTreeSet<GlobalId>,
+    ) {
+        let held = self.compaction_pause_holds.get(&cluster_id);
+        let unheld: BTreeSet<_> = collections
+            .into_iter()
+            .filter(|id| {
+                held.map_or(true, |held| {
+                    !held.compute_holds.contains_key(&(cluster_id, *id))
+                })
+            })
+            .collect();
+        if unheld.is_empty() {
+            return;
+        }
+        let id_bundle = CollectionIdBundle {
+            storage_ids: BTreeSet::new(),
+            compute_ids: BTreeMap::from([(cluster_id, unheld)]),
+        };
+        let read_holds = self.acquire_read_holds(&id_bundle);
+        match self.compaction_pause_holds.entry(cluster_id) {
+            btree_map::Entry::Occupied(mut entry) => entry.get_mut().merge(read_holds),
+            btree_map::Entry::Vacant(entry) => {
+                tracing::info!(%cluster_id, "pausing compaction");
+                entry.insert(read_holds);
+            }
+        }
+    }
+
+    /// Returns the read holds of open transactions, of pending peeks, and of
+    /// compaction pauses.
+    ///
+    /// Holds of timelines are not included: every timeline holds all of its
+    /// collections at its read timestamp, so their holds would make up most of
+    /// the result without explaining any stalls.
+    pub(crate) fn read_hold_records(&self) -> BTreeSet<ReadHoldRecord> {
+        let mut records = BTreeSet::new();
+        let mut add = |holder, read_holds: &ReadHolds<Timestamp>| {
+            for (id, hold) in &read_holds.storage_holds {
+                records.insert(ReadHoldRecord {
+                    object_id: *id,
+                    cluster_id: None,
+                    holder,
+                    read_frontier: hold.since().iter().min().copied(),
+                });
+            }
+            for ((compute_instance, id), hold) in &read_holds.compute_holds {
+                records.insert(ReadHoldRecord {
+                    object_id: *id,
+                    cluster_id: Some(*compute_instance),
+                    holder,
+                    read_frontier: hold.frontier().iter().min().copied(),
+                });
+            }
+        };
+        for (conn_id, read_holds) in &self.txn_read_holds {
+            add(ReadHolder::Transaction(conn_id.unhandled()), read_holds);
+        }
+        for read_holds in self.compaction_pause_holds.values() {
+            add(ReadHolder::CompactionPause, read_holds);
+        }
+
+        // The holds of pending peeks are held by the COMPUTE controller.
+        let peek_clusters: BTreeSet<_> = self
+            .pending_peeks
+            .values()
+            .map(|peek| peek.cluster_id)
+            .collect();
+        for cluster_id in peek_clusters {
+            let Ok(instance) = self.controller.compute.instance_ref(cluster_id) else {
+                continue;
+            };
+            for (uuid, hold, storage_read_hold) in instance.peek_read_holds() {
+                let Some(peek) = self.pending_peeks.get(uuid) else {
+                    continue;
+                };
+                records.insert(ReadHoldRecord {
+                    object_id: hold.id(),
+                    cluster_id: (!storage_read_hold).then_some(cluster_id),
+                    holder: ReadHolder::Peek(peek.conn_id.unhandled()),
+                    read_frontier: hold.since().iter().min().copied(),
+                });
+            }
+        }
+        records
+    }
+
+    /// Returns what pending peeks and the cleanup of dropped collections wait
+    /// on, given the read holds returned by
+    /// [Coordinator::read_hold_records].
+    ///
+    /// A pending peek waits for its cluster to answer it at the time it holds
+    /// the peeked collection at. A dropped collection is only cleaned up once
+    /// all read holds on it are released, so it waits on each of its holders.
+    fn read_hold_waits(&self, records: &BTreeSet<ReadHoldRecord>) -> BTreeSet<ReadHoldWait> {
+        let mut waits = BTreeSet::new();
+        for record in records {
+            if let ReadHolder::Peek(conn_id) = record.holder {
+                waits.insert(ReadHoldWait {
+                    waiter: ReadHoldWaiter::Peek(conn_id),
+                    object_id: record.object_id,
+                    cluster_id: record.cluster_id,
+                    holder: None,
+                    timestamp: record.read_frontier,
+                });
+            }
+            let dropped = match record.cluster_id {
+                Some(_) => !self
+                    .compute_read_capabilities
+                    .contains_key(&record.object_id),
+                None => !self
+                    .storage_read_capabilities
+                    .contains_key(&record.object_id),
+            };
+            if dropped {
+                waits.insert(ReadHoldWait {
+                    waiter: ReadHoldWaiter::Drop,
+                    object_id: record.object_id,
+                    cluster_id: record.cluster_id,
+                    holder: Some(record.holder),
+                    timestamp: record.read_frontier,
+                });
+            }
+        }
+        waits
+    }
+
+    /// Updates `mz_internal.mz_read_holds` and `mz_internal.mz_read_hold_waits`
+    /// to reflect the current read holds of open transactions, pending peeks,
+    /// and compaction pauses, and what waits on them, if they haven't been
+    /// updated within [READ_HOLDS_INTROSPECTION_INTERVAL]. Also logs the holds
+    /// that dropped collections wait on.
+    ///
+    /// A dropped collection is only cleaned up once all read holds on it are
+    /// released. The log lines record which holder the drop waits on, and when
+    /// the holder released the collection, so that stalls between long-running
+    /// reads and DDL can be diagnosed after the fact.
+    pub(crate) fn update_read_holds_introspection(&mut self) {
+        if self.read_holds_introspection.updated_at.elapsed() < READ_HOLDS_INTROSPECTION_INTERVAL {
+            return;
+        }
+
+        let records = self.read_hold_records();
+        let waits = self.read_hold_waits(&records);
+        let dropped: BTreeSet<_> = waits
+            .iter()
+            .filter(|wait| wait.waiter == ReadHoldWaiter::Drop)
+            .copied()
+            .collect();
+        let introspection = &self.read_holds_introspection;
+        for wait in dropped.difference(&introspection.dropped) {
+            let holder = wait.holder.expect("drops wait on a holder");
+            tracing::warn!(
+                object_id = %wait.object_id,
+                cluster_id = ?wait.cluster_id,
+                holder = holder.name(),
+                connection_id = ?holder.connection_id(),
+                read_frontier = ?wait.timestamp,
+                "dropped collection is held back by read hold",
+            );
+        }
+        for wait in introspection.dropped.difference(&dropped) {
+            let holder = wait.holder.expect("drops wait on a holder");
+            tracing::info!(
+                object_id = %wait.object_id,
+                cluster_id = ?wait.cluster_id,
+                holder = holder.name(),
+                connection_id = ?holder.connection_id(),
+                "dropped collection released by read hold",
+            );
+        }
+
+        // In read-only mode, the builtin tables can't be written to. The
+        // records and waits reported stay empty, so that all of them are
+        // reported once the coordinator can write.
+        let mut updates = Vec::new();
+        if !self.controller.read_only() {
+            let state = self.catalog().state();
+            let introspection = &self.read_holds_introspection;
+            let pack_record = |record: &ReadHoldRecord, diff| {
+                state.pack_read_hold_update(
+                    record.object_id,
+                    record.cluster_id,
+                    record.holder.name(),
+                    record.holder.connection_id(),
+                    record.read_frontier,
+                    diff,
+                )
+            };
+            for record in introspection.records.difference(&records) {
+                updates.push(pack_record(record, -1));
+            }
+            for record in records.difference(&introspection.records) {
+                updates.push(pack_record(record, 1));
+            }
+            let pack_wait = |wait: &ReadHoldWait, diff| {
+                state.pack_read_hold_wait_update(
+                    wait.waiter.name(),
+                    wait.waiter.connection_id(),
+                    wait.object_id,
+                    wait.cluster_id,
+                    wait.holder.map(|holder| holder.name()),
+                    wait.holder.and_then(|holder| holder.connection_id()),
+                    wait.timestamp,
+                    diff,
+                )
+            };
+            for wait in introspection.waits.difference(&waits) {
+                updates.push(pack_wait(wait, -1));
+            }
+            for wait in waits.difference(&introspection.waits) {
+                updates.push(pack_wait(wait, 1));
+            }
+            self.read_holds_introspection.records = records;
+            self.read_holds_introspection.waits = waits;
+        }
+
+        self.read_holds_introspection.dropped = dropped;
+        self.read_holds_introspection.updated_at = Instant::now();
+        if !updates.is_empty() {
+            let updates = self
+                .catalog()
+                .state()
+                .resolve_builtin_table_updates(updates);
+            self.builtin_table_update().background(updates);
+        }
+    }
+
     /// Release the given read holds.
     ///
     /// This method relies on a previous call to
@@ -872,28 +1994,320 @@ impl crate::coord::Coordinator {
         // STORAGE read holds are released implicitly by dropping the STORAGE
         // ReadHolds.
 
-        // Update COMPUTE read policies
-        let mut policy_changes_per_instance = BTreeMap::new();
+        // Update COMPUTE read policies. It's possible that a concurrent DDL
+        // statement has already dropped some of the held collections.
         for read_holds in read_holdses.iter_mut() {
-            for ((compute_instance, id), hold) in read_holds.compute_holds.iter_mut() {
-                let policy_changes = policy_changes_per_instance
-                    .entry(compute_instance)
-                    .or_insert_with(Vec::new);
-                // It's possible that a concurrent DDL statement has already dropped this GlobalId
-                if let Some(read_needs) = self.compute_read_capabilities.get_mut(id) {
-                    let inverted_hold = hold.updates().map(|(t, diff)| (*t, -diff));
-                    read_needs.holds.update_iter(inverted_hold);
-                    policy_changes.push((*id, read_needs.policy()));
-                }
-            }
+            release_compute_holds(
+                &mut self.compute_read_capabilities,
+                &mut self.pending_read_policy_updates,
+                read_holds,
+            );
         }
-        for (compute_instance, policy_changes) in policy_changes_per_instance {
+    }
+
+    /// Sends the read policies of the collections whose read capabilities
+    /// changed since the last call to the controllers.
+    ///
+    /// The coordinator calls this before it handles each message, see
+    /// [`PendingReadPolicyUpdates`]. Collections of dropped COMPUTE instances
+    /// are skipped.
+    pub(crate) fn flush_read_policy_updates(&mut self) {
+        let (storage_policies, compute_policies) = self.pending_read_policy_updates.drain(
+            &self.storage_read_capabilities,
+            &self.compute_read_capabilities,
+        );
+        if !storage_policies.is_empty() {
+            self.controller.storage.set_read_policy(storage_policies);
+        }
+        for (compute_instance, policies) in compute_policies {
             let compute = &mut self.controller.compute;
-            if compute.instance_exists(*compute_instance) {
+            if compute.instance_exists(compute_instance) {
                 compute
-                    .set_read_policy(*compute_instance, policy_changes)
+                    .set_read_policy(compute_instance, policies)
                     .unwrap_or_terminate("cannot fail to set read policy");
             }
         }
     }
 }
+
+/// Returns the frontier at which a read hold at `frontier` holds a collection
+/// with the given `since`, which is `frontier` advanced to `since`.
+///
+/// If `precise` is `true`, returns `None` instead if the collection can't be
+/// held at exactly `frontier`.
+fn hold_frontier<T: TimelyTimestamp + Lattice>(
+    since: &Antichain<T>,
+    frontier: &Antichain<T>,
+    precise: bool,
+) -> Option<Antichain<T>> {
+    if PartialOrder::less_equal(since, frontier) {
+        Some(frontier.clone())
+    } else if precise {
+        None
+    } else {
+        Some(frontier.join(since))
+    }
+}
+
+/// Returns the replacements of the IDs in `a` and `b` when the objects that
+/// contain them are swapped, as `ALTER ... SWAP` does: each ID is replaced by
+/// the ID that takes over its key.
+///
+/// Keys that appear on only one side have no replacement.
+pub(crate) fn swapped_ids<K: Ord>(
+    a: &BTreeMap<K, GlobalId>,
+    b: &BTreeMap<K, GlobalId>,
+) -> BTreeMap<GlobalId, GlobalId> {
+    let mut replacements = BTreeMap::new();
+    for (key, id_a) in a {
+        if let Some(id_b) = b.get(key) {
+            replacements.insert(*id_a, *id_b);
+            replacements.insert(*id_b, *id_a);
+        }
+    }
+    replacements
+}
+
+#[cfg(test)]
+mod tests {
+    use mz_ore::metrics::MetricsRegistry;
+
+    use super::*;
+
+    #[mz_ore::test]
+    fn pending_read_policy_updates() {
+        let valid_from = |t: u64| ReadPolicy::ValidFrom(Antichain::from_elem(Timestamp::from(t)));
+        let mut storage_caps: BTreeMap<_, ReadCapability<Timestamp>> = BTreeMap::new();
+        let mut compute_caps: BTreeMap<_, ReadCapability<Timestamp>> = BTreeMap::new();
+        let (s1, s2) = (GlobalId::User(1), GlobalId::User(2));
+        let (c1, c2) = (GlobalId::User(3), GlobalId::User(4));
+        let instance = ComputeInstanceId::User(1);
+        storage_caps.insert(s1, valid_from(1).into());
+        storage_caps.insert(s2, valid_from(1).into());
+        compute_caps.insert(c1, valid_from(1).into());
+        compute_caps.insert(c2, valid_from(1).into());
+
+        // Change the capabilities several times, recording the policy that
+        // would have been sent for each change without batching.
+        let mut pending = PendingReadPolicyUpdates::default();
+        let mut unbatched = BTreeMap::new();
+        let mut change = |caps: &mut BTreeMap<GlobalId, ReadCapability<Timestamp>>,
+                          id: GlobalId,
+                          update: (u64, i64)| {
+            let cap = caps.get_mut(&id).expect("known to exist");
+            cap.holds
+                .update_iter([(Timestamp::from(update.0), update.1)]);
+            unbatched.insert(id, format!("{:?}", cap.policy()));
+        };
+        change(&mut storage_caps, s1, (5, 1));
+        pending.record_storage(s1);
+        change(&mut storage_caps, s1, (7, 1));
+        pending.record_storage(s1);
+        change(&mut storage_caps, s1, (5, -1));
+        pending.record_storage(s1);
+        change(&mut compute_caps, c1, (3, 1));
+        pending.record_compute(instance, c1);
+        change(&mut compute_caps, c1, (3, -1));
+        pending.record_compute(instance, c1);
+        change(&mut compute_caps, c2, (4, 1));
+        pending.record_compute(instance, c2);
+
+        // Each changed collection is sent once, with the policy of its last
+        // change, and unchanged collections are not sent.
+        let (storage, compute) = pending.drain(&storage_caps, &compute_caps);
+        let batched: BTreeMap<_, _> = storage
+            .iter()
+            .chain(compute[&instance].iter())
+            .map(|(id, policy)| (*id, format!("{policy:?}")))
+            .collect();
+        assert_eq!(batched, unbatched);
+        assert_eq!(storage.len(), 1);
+        assert_eq!(compute.len(), 1);
+
+        // Draining clears the recorded collections.
+        let (storage, compute) = pending.drain(&storage_caps, &compute_caps);
+        assert!(storage.is_empty());
+        assert!(compute.is_empty());
+
+        // Dropped collections are skipped.
+        pending.record_storage(s2);
+        pending.record_compute(instance, c2);
+        storage_caps.remove(&s2);
+        compute_caps.remove(&c2);
+        let (storage, compute) = pending.drain(&storage_caps, &compute_caps);
+        assert!(storage.is_empty());
+        assert!(compute.is_empty());
+    }
+
+    #[mz_ore::test]
+    fn compute_holds_acquire_release_flush() {
+        let instance = ComputeInstanceId::User(1);
+        let (c1, c2) = (GlobalId::User(1), GlobalId::User(2));
+        let base = Antichain::from_elem(Timestamp::from(8));
+        let mut compute_caps: BTreeMap<_, ReadCapability<Timestamp>> = BTreeMap::new();
+        compute_caps.insert(c1, ReadPolicy::ValidFrom(base.clone()).into());
+        compute_caps.insert(c2, ReadPolicy::ValidFrom(base.clone()).into());
+        let mut pending = PendingReadPolicyUpdates::default();
+        let upper = Antichain::from_elem(Timestamp::from(10));
+        let hold_at = |t: u64, ids: &[GlobalId]| {
+            let mut read_holds = ReadHoldsInner::new();
+            for id in ids {
+                let mut hold = MutableAntichain::new();
+                hold.update_iter([(Timestamp::from(t), 1)]);
+                read_holds.compute_holds.insert((instance, *id), hold);
+            }
+            read_holds
+        };
+        // Flushes `pending` and returns the sinces the sent policies allow.
+        let flush =
+            |pending: &mut PendingReadPolicyUpdates,
+             compute_caps: &BTreeMap<GlobalId, ReadCapability<Timestamp>>| {
+                let (storage, compute) = pending.drain(&BTreeMap::new(), compute_caps);
+                assert!(storage.is_empty());
+                compute
+                    .into_iter()
+                    .flat_map(|(_, policies)| policies)
+                    .map(|(id, policy)| (id, policy.frontier(upper.borrow())))
+                    .collect::<BTreeMap<_, _>>()
+            };
+
+        // Acquiring a hold keeps the collection from compacting past it.
+        let mut read_holds = hold_at(5, &[c1]);
+        install_compute_holds(&mut compute_caps, &mut pending, &mut read_holds);
+        assert_eq!(
+            flush(&mut pending, &compute_caps),
+            BTreeMap::from([(c1, Antichain::from_elem(Timestamp::from(5)))])
+        );
+
+        // Releasing it allows compaction up to the base policy again.
+        release_compute_holds(&mut compute_caps, &mut pending, &mut read_holds);
+        assert_eq!(
+            flush(&mut pending, &compute_caps),
+            BTreeMap::from([(c1, base.clone())])
+        );
+
+        // A hold acquired and released before a flush sends the policy
+        // without it.
+        let mut read_holds = hold_at(3, &[c1, c2]);
+        install_compute_holds(&mut compute_caps, &mut pending, &mut read_holds);
+        release_compute_holds(&mut compute_caps, &mut pending, &mut read_holds);
+        assert_eq!(
+            flush(&mut pending, &compute_caps),
+            BTreeMap::from([(c1, base.clone()), (c2, base.clone())])
+        );
+
+        // Releasing the holds of dropped collections sends nothing for them.
+        let mut read_holds = hold_at(3, &[c1, c2]);
+        install_compute_holds(&mut compute_caps, &mut pending, &mut read_holds);
+        compute_caps.remove(&c2);
+        release_compute_holds(&mut compute_caps, &mut pending, &mut read_holds);
+        assert_eq!(
+            flush(&mut pending, &compute_caps),
+            BTreeMap::from([(c1, base)])
+        );
+    }
+
+    #[mz_ore::test]
+    fn test_swapped_ids() {
+        let a = BTreeMap::from([("t", GlobalId::User(1)), ("only_a", GlobalId::User(2))]);
+        let b = BTreeMap::from([("t", GlobalId::User(3)), ("only_b", GlobalId::User(4))]);
+        let replacements = swapped_ids(&a, &b);
+        assert_eq!(
+            replacements,
+            BTreeMap::from([
+                (GlobalId::User(1), GlobalId::User(3)),
+                (GlobalId::User(3), GlobalId::User(1)),
+            ])
+        );
+        assert!(swapped_ids(&a, &BTreeMap::new()).is_empty());
+    }
+
+    #[mz_ore::test]
+    fn test_hold_frontier() {
+        use timely::order::Product;
+
+        let antichain = |times: &[(u64, u64)]| {
+            Antichain::from_iter(times.iter().map(|(a, b)| Product::new(*a, *b)))
+        };
+        let frontier = antichain(&[(1, 3), (3, 1)]);
+
+        // A since at or before the frontier is held at exactly the frontier.
+        for precise in [false, true] {
+            let since = antichain(&[(0, 2), (2, 0)]);
+            assert_eq!(
+                hold_frontier(&since, &frontier, precise),
+                Some(frontier.clone())
+            );
+            let since = antichain(&[(1, 1)]);
+            assert_eq!(
+                hold_frontier(&since, &frontier, precise),
+                Some(frontier.clone())
+            );
+        }
+
+        // A since beyond some element of the frontier can only be held at the
+        // frontier advanced to it.
+        let since = antichain(&[(2, 2)]);
+        assert_eq!(hold_frontier(&since, &frontier, true), None);
+        assert_eq!(
+            hold_frontier(&since, &frontier, false),
+            Some(antichain(&[(2, 3), (3, 2)]))
+        );
+        let since = antichain(&[(0, 2), (2, 2)]);
+        assert_eq!(hold_frontier(&since, &frontier, true), None);
+        assert_eq!(
+            hold_frontier(&since, &frontier, false),
+            Some(antichain(&[(1, 3), (3, 2)]))
+        );
+    }
+
+    #[mz_ore::test]
+    fn dropped_read_holds_queue() {
+        let metrics = Metrics::register_into(&MetricsRegistry::new());
+        let clock = Arc::new(Mutex::new(0));
+        let now = {
+            let clock = Arc::clone(&clock);
+            NowFn::from(move || *clock.lock().expect("lock poisoned"))
+        };
+        let advance = |ms: EpochMillis| *clock.lock().expect("lock poisoned") += ms;
+        let (tx, mut rx) = dropped_read_holds_channel::<Timestamp>(&metrics, now);
+        let depth = || metrics.dropped_read_holds_queue_depth.get();
+        let oldest_age = || metrics.dropped_read_holds_oldest_age_seconds.get();
+
+        for _ in 0..DROPPED_READ_HOLDS_CAPACITY {
+            tx.send(ReadHoldsInner::new());
+        }
+        assert!(!rx.over_capacity());
+        advance(1_000);
+
+        // Holds dropped once the queue is full are merged into one entry.
+        for _ in 0..3 {
+            tx.send(ReadHoldsInner::new());
+        }
+        assert!(rx.over_capacity());
+        assert_eq!(
+            depth(),
+            i64::try_from(DROPPED_READ_HOLDS_CAPACITY + 3).unwrap()
+        );
+
+        // The age of the oldest entry is computed when the metrics are read,
+        // so it grows while the queue is not drained.
+        assert_eq!(oldest_age(), 1.0);
+        advance(500);
+        assert_eq!(oldest_age(), 1.5);
+
+        // Receiving dequeues the send times along with the holds.
+        let batch = rx.try_recv_all();
+        assert_eq!(batch.len(), DROPPED_READ_HOLDS_CAPACITY + 1);
+        assert!(!rx.over_capacity());
+        assert_eq!(depth(), 0);
+        assert_eq!(oldest_age(), 0.0);
+        assert!(rx.try_recv_all().is_empty());
+
+        // A failed send doesn't leave its send time behind.
+        drop(rx);
+        tx.send(ReadHoldsInner::new());
+        assert_eq!(depth(), 0);
+        assert_eq!(oldest_age(), 0.0);
+    }
+}
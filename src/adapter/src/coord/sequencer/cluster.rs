@@ -9,7 +9,7 @@
 
 //! Coordinator functionality to sequence cluster-related plans
 
-use std::collections::BTreeSet;
+use std::collections::{BTreeMap, BTreeSet};
 use std::time::Duration;
 
 use mz_adapter_types::compaction::CompactionWindow;
@@ -33,6 +33,7 @@ use mz_sql::session::metadata::SessionMetadata;
 use mz_sql::session::vars::{SystemVars, Var, MAX_REPLICAS_PER_CLUSTER};
 
 use crate::catalog::{Op, ReplicaCreateDropReason};
+use crate::coord::read_policy::swapped_ids;
 use crate::coord::Coordinator;
 use crate::session::Session;
 use crate::{catalog, AdapterError, ExecuteResponse};
@@ -993,11 +994,29 @@ impl Coordinator {
             check_reserved_names: false,
         };
 
+        // Indexes that take over the name of their cluster from an equivalent
+        // index in the other cluster replace it for open transactions.
+        let indexes = |cluster_id| -> BTreeMap<_, _> {
+            self.catalog()
+                .get_cluster(cluster_id)
+                .bound_objects()
+                .iter()
+                .filter_map(|id| {
+                    let index = self.catalog().get_entry(id).index()?;
+                    Some(((index.on, index.keys.clone()), *id))
+                })
+                .collect()
+        };
+        let replacements = swapped_ids(&indexes(id_a), &indexes(id_b));
+
         match self
             .catalog_transact_with_ddl_transaction(session, vec![op_a, op_b, op_temp])
             .await
         {
-            Ok(()) => Ok(ExecuteResponse::AlteredObject(ObjectType::Cluster)),
+            Ok(()) => {
+                self.chain_txn_read_holds(&replacements);
+                Ok(ExecuteResponse::AlteredObject(ObjectType::Cluster))
+            }
             Err(err) => Err(err),
         }
     }
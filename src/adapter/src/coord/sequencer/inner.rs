@@ -94,6 +94,7 @@ use tracing::{warn, Instrument, Span};
 use crate::catalog::{self, Catalog, ConnCatalog, DropObjectInfo, UpdatePrivilegeVariant};
 use crate::command::{ExecuteResponse, Response};
 use crate::coord::appends::{Deferred, DeferredPlan, PendingWriteTxn};
+use crate::coord::read_policy::swapped_ids;
 use crate::coord::{
     AlterConnectionValidationReady, AlterSinkReadyContext, Coordinator,
     CreateConnectionValidationReady, DeferredPlanStatement, ExecuteContext, ExplainContext,
@@ -2985,11 +2986,24 @@ impl Coordinator {
             check_reserved_names: false,
         };
 
+        // Items that take over the name of an item in the other schema
+        // replace it for open transactions.
+        let replacements = {
+            let catalog = self.catalog();
+            let conn_id = session.conn_id();
+            let schema_a = catalog.get_schema(&schema_a_db, &schema_a, conn_id);
+            let schema_b = catalog.get_schema(&schema_b_db, &schema_b, conn_id);
+            swapped_ids(&schema_a.items, &schema_b.items)
+        };
+
         match self
             .catalog_transact_with_ddl_transaction(session, vec![op_a, op_b, op_c])
             .await
         {
-            Ok(()) => Ok(ExecuteResponse::AlteredObject(ObjectType::Schema)),
+            Ok(()) => {
+                self.chain_txn_read_holds(&replacements);
+                Ok(ExecuteResponse::AlteredObject(ObjectType::Schema))
+            }
             Err(err) => Err(err),
         }
     }
@@ -15,10 +15,15 @@ use mz_repr::explain::ExplainFormat;
 use mz_repr::{Datum, Row};
 use mz_sql::plan::{self};
 use mz_sql::session::metadata::SessionMetadata;
+use timely::progress::Antichain;
+use timely::PartialOrder;
 use tracing::{Instrument, Span};
 
+use crate::coord::read_policy::ReadHoldInfo;
 use crate::coord::sequencer::inner::return_if_err;
-use crate::coord::timestamp_selection::{TimestampDetermination, TimestampSource};
+use crate::coord::timestamp_selection::{
+    TimestampDetermination, TimestampReadHold, TimestampSource,
+};
 use crate::coord::{
     Coordinator, ExplainTimestampFinish, ExplainTimestampOptimize, ExplainTimestampRealTimeRecency,
     ExplainTimestampStage, Message, PlanValidity, StageResult, Staged, TargetCluster,
@@ -214,6 +219,7 @@ impl Coordinator {
         id_bundle: &CollectionIdBundle,
         determination: TimestampDetermination<mz_repr::Timestamp>,
     ) -> TimestampExplanation<mz_repr::Timestamp> {
+        let mut read_holds = self.read_holds_affecting(session.conn_id(), id_bundle);
         let mut sources = Vec::new();
         {
             let storage_ids = id_bundle.storage_ids.iter().cloned().collect_vec();
@@ -234,8 +240,10 @@ impl Coordinator {
                             .to_string()
                     })
                     .unwrap_or_else(|| id.to_string());
+                let holds = read_holds.storage.remove(&id).unwrap_or_default();
                 sources.push(TimestampSource {
                     name: format!("{name} ({id}, storage)"),
+                    read_holds: timestamp_read_holds(holds, &since),
                     read_frontier: since.elements().to_vec(),
                     write_frontier: upper.elements().to_vec(),
                 });
@@ -259,8 +267,13 @@ impl Coordinator {
                                 .to_string()
                         })
                         .unwrap_or_else(|| id.to_string());
+                    let holds = read_holds
+                        .compute
+                        .remove(&(cluster_id, *id))
+                        .unwrap_or_default();
                     sources.push(TimestampSource {
                         name: format!("{name} ({id}, compute)"),
+                        read_holds: timestamp_read_holds(holds, state.read_capability()),
                         read_frontier: state.read_capability().elements().to_vec(),
                         write_frontier: state.write_frontier().to_vec(),
                     });
@@ -323,6 +336,7 @@ impl Coordinator {
             &source_ids,
             real_time_recency_ts,
             RequireLinearization::NotRequired,
+            false,
         )?;
         let explanation = self.explain_timestamp(session, cluster_id, &id_bundle, determination);
 
@@ -335,3 +349,28 @@ impl Coordinator {
         Ok(StageResult::Response(Self::send_immediate_rows(rows)))
     }
 }
+
+/// Converts the read holds on a source with read frontier `since` for a
+/// [TimestampSource].
+///
+/// A hold constrains the read frontier if it is at the read frontier and no
+/// other hold is below it.
+fn timestamp_read_holds(
+    holds: Vec<ReadHoldInfo<mz_repr::Timestamp>>,
+    since: &Antichain<mz_repr::Timestamp>,
+) -> Vec<TimestampReadHold<mz_repr::Timestamp>> {
+    let constrains_read_frontier = |hold: &ReadHoldInfo<_>| {
+        &hold.frontier == since
+            && !holds
+                .iter()
+                .any(|other| PartialOrder::less_than(&other.frontier, &hold.frontier))
+    };
+    holds
+        .iter()
+        .map(|hold| TimestampReadHold {
+            kind: hold.kind,
+            constrains_read_frontier: constrains_read_frontier(hold),
+            frontier: hold.frontier.elements().to_vec(),
+        })
+        .collect()
+}
@@ -482,6 +482,9 @@ impl Coordinator {
         // sufficient collections for safety.
         validity.extend_dependencies(id_bundle.iter());
 
+        // Only ad-hoc SELECTs observe the session's `default_as_of_lag`, not
+        // `COPY ... TO`.
+        let apply_as_of_lag = optimizer.is_left() && plan.copy_to.is_none();
         let determination = self.sequence_peek_timestamp(
             session,
             &plan.when,
@@ -492,6 +495,7 @@ impl Coordinator {
             &source_ids,
             real_time_recency_ts,
             (&explain_ctx).into(),
+            apply_as_of_lag,
         )?;
 
         let stage = PeekStage::Optimize(PeekStageOptimize {
@@ -1074,6 +1078,8 @@ impl Coordinator {
 
     /// Determines the query timestamp and acquires read holds on dependent sources
     /// if necessary.
+    ///
+    /// The session's `default_as_of_lag` is applied only if `apply_as_of_lag` is set.
     #[instrument]
     pub(super) fn sequence_peek_timestamp(
         &mut self,
@@ -1086,6 +1092,7 @@ impl Coordinator {
         source_ids: &BTreeSet<GlobalId>,
         real_time_recency_ts: Option<Timestamp>,
         requires_linearization: RequireLinearization,
+        apply_as_of_lag: bool,
     ) -> Result<TimestampDetermination<Timestamp>, AdapterError> {
         let in_immediate_multi_stmt_txn = session.transaction().in_immediate_multi_stmt_txn(when);
         let timedomain_bundle;
@@ -1124,6 +1131,7 @@ impl Coordinator {
                     &timeline_context,
                     oracle_read_ts,
                     real_time_recency_ts,
+                    apply_as_of_lag,
                 )?;
                 // We only need read holds if the read depends on a timestamp.
                 let read_holds = match determination.timestamp_context.timestamp() {
@@ -259,6 +259,7 @@ impl Coordinator {
             &timeline,
             oracle_read_ts,
             None,
+            false,
         )?;
 
         let as_of = determination.timestamp_context.timestamp_or_default();
@@ -275,7 +276,17 @@ impl Coordinator {
             }
         }
 
-        self.store_transaction_read_holds(ctx.session(), read_holds);
+        // Hold the inputs at the as of rather than at their sinces. An as of in
+        // the future is not readable yet, so the inputs are held at the latest
+        // time they are readable at instead.
+        let (read_holds_at_as_of, held_at) = self.acquire_read_holds_for(as_of, bundle);
+        drop(read_holds);
+        tracing::debug!(%as_of, %held_at, "holding subscribe inputs");
+        if held_at != as_of {
+            ctx.session()
+                .add_notice(AdapterNotice::SubscribeAsOfNotReadable { as_of, held_at });
+        }
+        self.store_transaction_read_holds(ctx.session(), read_holds_at_as_of);
 
         let global_mir_plan = global_mir_plan.resolve(Antichain::from_elem(as_of));
 
@@ -210,13 +210,7 @@ impl Coordinator {
         self.retire_cluster_reconfigurations_for_conn(conn_id).await;
 
         // Release this transaction's compaction hold on collections.
-        if let Some(txn_reads) = self.txn_read_holds.remove(conn_id) {
-            tracing::debug!(?txn_reads, "releasing txn read holds");
-
-            // Make it explicit that we're dropping these read holds. Dropping
-            // them will release them at the Coordinator.
-            drop(txn_reads);
-        }
+        self.release_txn_read_holds(conn_id);
 
         if let Some(_guard) = self
             .active_conns
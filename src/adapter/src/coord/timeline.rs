@@ -12,7 +12,7 @@
 use std::collections::{BTreeMap, BTreeSet};
 use std::fmt;
 use std::sync::Arc;
-use std::time::Duration;
+use std::time::{Duration, Instant};
 
 use chrono::{DateTime, Utc};
 use futures::Future;
@@ -24,6 +24,7 @@ use mz_expr::CollectionPlan;
 use mz_ore::collections::CollectionExt;
 use mz_ore::instrument;
 use mz_ore::now::{to_datetime, EpochMillis, NowFn};
+use mz_ore::task;
 use mz_ore::vec::VecExt;
 use mz_repr::{GlobalId, Timestamp};
 use mz_sql::names::{ResolvedDatabaseSpecifier, SchemaSpecifier};
@@ -35,12 +36,12 @@ use mz_timestamp_oracle::postgres_oracle::{
 use mz_timestamp_oracle::{self, TimestampOracle, WriteTimestamp};
 use once_cell::sync::Lazy;
 use timely::progress::Timestamp as TimelyTimestamp;
-use tracing::{debug, error, info, Instrument};
+use tracing::{debug, error, info, warn, Instrument};
 
 use crate::coord::id_bundle::CollectionIdBundle;
 use crate::coord::read_policy::TimelineReadHolds;
 use crate::coord::timestamp_selection::TimestampProvider;
-use crate::coord::Coordinator;
+use crate::coord::{Coordinator, Message};
 use crate::AdapterError;
 
 /// An enum describing whether or not a query belongs to a timeline and whether the query can be
@@ -603,6 +604,13 @@ impl Coordinator {
 
     #[instrument(level = "debug")]
     pub(crate) async fn advance_timelines(&mut self) {
+        // The read timestamps move on every call, so only refresh the
+        // introspection periodically.
+        let update_introspection = !self.controller.read_only()
+            && self.timeline_timestamps_updated_at.elapsed()
+                >= TIMELINE_TIMESTAMPS_INTROSPECTION_INTERVAL;
+        let mut timeline_timestamps = Vec::new();
+
         let global_timelines = std::mem::take(&mut self.global_timelines);
         for (
             timeline,
@@ -640,10 +648,101 @@ impl Coordinator {
             if read_holds.times().any(|time| time.less_than(&read_ts)) {
                 self.update_timeline_read_holds(&mut read_holds, read_ts);
             }
+            if update_introspection {
+                let read_hold = read_holds.least_time().copied();
+                timeline_timestamps.push((
+                    timeline.clone(),
+                    Arc::clone(&oracle),
+                    read_ts,
+                    read_hold,
+                ));
+            }
             self.global_timelines
                 .insert(timeline, TimelineState { oracle, read_holds });
         }
+
+        if update_introspection {
+            self.timeline_timestamps_updated_at = Instant::now();
+            // Peeking the write timestamp can require a round trip to the
+            // oracle, so do it off the coordinator loop and report the
+            // timestamps in a `Message::TimelineTimestamps`. Asking the oracle
+            // rather than tracking our own writes also covers the writes of
+            // previous incarnations of the coordinator.
+            let internal_cmd_tx = self.internal_cmd_tx.clone();
+            task::spawn(|| "timeline_timestamps_fetch", async move {
+                let mut timestamps = BTreeMap::new();
+                for (timeline, oracle, read_ts, read_hold) in timeline_timestamps {
+                    let write_ts = oracle.peek_write_ts().await;
+                    timestamps.insert(
+                        timeline,
+                        TimelineTimestamps {
+                            read_ts,
+                            write_ts,
+                            read_hold,
+                        },
+                    );
+                }
+                // It is not an error for the timestamps to become ready after
+                // `internal_cmd_rx` is dropped.
+                if let Err(e) = internal_cmd_tx.send(Message::TimelineTimestamps(timestamps)) {
+                    warn!("internal_cmd_rx dropped before we could send: {:?}", e);
+                }
+            });
+        }
     }
+
+    /// Updates `mz_internal.mz_timeline_timestamps` to reflect the given
+    /// timestamps of each timeline.
+    pub(crate) fn update_timeline_timestamps_introspection(
+        &mut self,
+        timeline_timestamps: BTreeMap<Timeline, TimelineTimestamps>,
+    ) {
+        let pack = |timeline, timestamps: &TimelineTimestamps, diff| {
+            self.catalog().state().pack_timeline_timestamps_update(
+                timeline,
+                timestamps.read_ts,
+                timestamps.write_ts,
+                timestamps.read_hold,
+                diff,
+            )
+        };
+        let mut updates = Vec::new();
+        for (timeline, old) in &self.timeline_timestamps {
+            if timeline_timestamps.get(timeline) != Some(old) {
+                updates.push(pack(timeline, old, -1));
+            }
+        }
+        for (timeline, new) in &timeline_timestamps {
+            if self.timeline_timestamps.get(timeline) != Some(new) {
+                updates.push(pack(timeline, new, 1));
+            }
+        }
+
+        self.timeline_timestamps = timeline_timestamps;
+        if !updates.is_empty() {
+            let updates = self
+                .catalog()
+                .state()
+                .resolve_builtin_table_updates(updates);
+            self.builtin_table_update().background(updates);
+        }
+    }
+}
+
+/// How often to refresh `mz_internal.mz_timeline_timestamps`.
+const TIMELINE_TIMESTAMPS_INTROSPECTION_INTERVAL: Duration = Duration::from_secs(10);
+
+/// The timestamps of a timeline, as reported in
+/// `mz_internal.mz_timeline_timestamps`.
+#[derive(Debug, Clone, Copy, PartialEq, Eq)]
+pub struct TimelineTimestamps {
+    /// The read timestamp of the timeline's oracle.
+    pub(crate) read_ts: Timestamp,
+    /// The write timestamp of the timeline's oracle.
+    pub(crate) write_ts: Timestamp,
+    /// The least time of the timeline's [`TimelineReadHolds`], if it holds
+    /// back any collections.
+    pub(crate) read_hold: Option<Timestamp>,
 }
 
 /// Convenience function for calculating the current upper bound that we want to
@@ -22,7 +22,7 @@ use mz_repr::explain::ExprHumanizer;
 use mz_repr::{GlobalId, RowArena, ScalarType, Timestamp, TimestampManipulation};
 use mz_sql::plan::QueryWhen;
 use mz_sql::session::metadata::SessionMetadata;
-use mz_sql::session::vars::IsolationLevel;
+use mz_sql::session::vars::{AsOfOutsideRetention, IsolationLevel};
 use mz_storage_types::sources::Timeline;
 use serde::{Deserialize, Serialize};
 use timely::progress::frontier::AntichainRef;
@@ -31,9 +31,10 @@ use tracing::{event, Level};
 
 use crate::catalog::CatalogState;
 use crate::coord::id_bundle::CollectionIdBundle;
-use crate::coord::read_policy::ReadHolds;
+use crate::coord::read_policy::{ReadHoldKind, ReadHolds};
 use crate::coord::timeline::TimelineContext;
 use crate::coord::Coordinator;
+use crate::notice::AdapterNotice;
 use crate::optimize::dataflows::{prep_scalar_expr, ExprPrepStyle};
 use crate::session::Session;
 use crate::AdapterError;
@@ -260,6 +261,9 @@ pub trait TimestampProvider {
     /// after `since` and sure to be available not after `upper`.
     ///
     /// The timeline that `id_bundle` belongs to is also returned, if one exists.
+    ///
+    /// The session's `default_as_of_lag` is only applied if `apply_as_of_lag`
+    /// is set, which callers do for ad-hoc SELECTs only.
     fn determine_timestamp_for(
         &mut self,
         session: &Session,
@@ -270,6 +274,7 @@ pub trait TimestampProvider {
         oracle_read_ts: Option<Timestamp>,
         real_time_recency_ts: Option<mz_repr::Timestamp>,
         isolation_level: &IsolationLevel,
+        apply_as_of_lag: bool,
     ) -> Result<
         (
             TimestampDetermination<mz_repr::Timestamp>,
@@ -349,10 +354,25 @@ pub trait TimestampProvider {
         //   reading source data that is being written to in the future.
         // - The isolation level is Strict Serializable but there is no timelines and the `when`
         //   allows us to advance to upper.
+        //
+        // When the session requests a default AS OF lag, serializable ad-hoc SELECTs in the
+        // `EpochMilliseconds` timeline instead advance to the upper minus the lag, or the
+        // nearest time after that at which the inputs are readable.
         if when.can_advance_to_upper()
             && (isolation_level == &IsolationLevel::Serializable || timeline.is_none())
         {
-            candidate.join_assign(&largest_not_in_advance_of_upper);
+            let lag = session.vars().default_as_of_lag();
+            let advance_to = match Timestamp::try_from(*lag) {
+                Ok(lag)
+                    if apply_as_of_lag
+                        && lag > Timestamp::minimum()
+                        && timeline == Some(Timeline::EpochMilliseconds) =>
+                {
+                    read_holds.lagged_read_time(largest_not_in_advance_of_upper, lag)
+                }
+                _ => largest_not_in_advance_of_upper,
+            };
+            candidate.join_assign(&advance_to);
         }
 
         if let Some(real_time_recency_ts) = real_time_recency_ts {
@@ -400,6 +420,10 @@ pub trait TimestampProvider {
         // the timestamp oracle. For Strict Serializable queries, the Coord will
         // linearize the query by holding back the result until the timestamp
         // oracle catches up.
+        //
+        // Queries whose AS OF is before `since` are rejected, unless the session
+        // asks to answer them at the nearest readable time instead.
+        let mut adjusted_from = None;
         let timestamp = if since.less_equal(&candidate) {
             event!(
                 Level::DEBUG,
@@ -409,6 +433,22 @@ pub trait TimestampProvider {
                 timestamp = format!("{candidate}")
             );
             candidate
+        } else if when.advance_to_timestamp().is_some()
+            && session.vars().as_of_outside_retention() == AsOfOutsideRetention::Nearest
+            && !since.is_empty()
+        {
+            // The read holds acquired above keep the inputs readable at `since`.
+            let mut adjusted = candidate;
+            adjusted.advance_by(since.borrow());
+            event!(
+                Level::DEBUG,
+                conn_id = format!("{}", session.conn_id()),
+                since = format!("{since:?}"),
+                requested = format!("{candidate}"),
+                timestamp = format!("{adjusted}")
+            );
+            adjusted_from = Some(candidate);
+            adjusted
         } else {
             coord_bail!(generate_timestamp_not_valid_error_msg(
                 id_bundle,
@@ -433,6 +473,7 @@ pub trait TimestampProvider {
             oracle_read_ts,
             session_oracle_read_ts,
             real_time_recency_ts,
+            adjusted_from,
         };
 
         Ok((determination, read_holds))
@@ -546,6 +587,9 @@ impl Coordinator {
     ///
     /// The caller is responsible for eventually dropping those read holds using
     /// [Coordinator::release_read_holds]!
+    ///
+    /// The session's `default_as_of_lag` is applied only if `apply_as_of_lag` is
+    /// set, which must only be the case for ad-hoc SELECTs.
     #[mz_ore::instrument(level = "debug")]
     pub(crate) fn determine_timestamp(
         &mut self,
@@ -556,6 +600,7 @@ impl Coordinator {
         timeline_context: &TimelineContext,
         oracle_read_ts: Option<Timestamp>,
         real_time_recency_ts: Option<mz_repr::Timestamp>,
+        apply_as_of_lag: bool,
     ) -> Result<
         (
             TimestampDetermination<mz_repr::Timestamp>,
@@ -573,6 +618,7 @@ impl Coordinator {
             oracle_read_ts,
             real_time_recency_ts,
             isolation_level,
+            apply_as_of_lag,
         )?;
         self.metrics
             .determine_timestamp
@@ -585,6 +631,14 @@ impl Coordinator {
                 &compute_instance.to_string(),
             ])
             .inc();
+        if let (Some(requested), Some(adjusted)) =
+            (det.adjusted_from, det.timestamp_context.timestamp())
+        {
+            session.add_notice(AdapterNotice::AsOfAdjusted {
+                requested,
+                adjusted: *adjusted,
+            });
+        }
         if !det.respond_immediately()
             && isolation_level == &IsolationLevel::StrictSerializable
             && real_time_recency_ts.is_none()
@@ -599,6 +653,7 @@ impl Coordinator {
                     oracle_read_ts,
                     real_time_recency_ts,
                     &IsolationLevel::Serializable,
+                    apply_as_of_lag,
                 )?;
 
                 if let Some(serializable) = serializable_det.timestamp_context.timestamp() {
@@ -693,6 +748,9 @@ pub struct TimestampDetermination<T> {
     pub session_oracle_read_ts: Option<T>,
     /// The value of the real time recency timestamp, if used.
     pub real_time_recency_ts: Option<T>,
+    /// The timestamp the query asked for, if it was before `since` and the
+    /// query is answered at the nearest readable time instead.
+    pub adjusted_from: Option<T>,
 }
 
 impl<T: TimestampManipulation> TimestampDetermination<T> {
@@ -724,6 +782,19 @@ pub struct TimestampSource<T> {
     pub name: String,
     pub read_frontier: Vec<T>,
     pub write_frontier: Vec<T>,
+    /// The read holds on the source.
+    pub read_holds: Vec<TimestampReadHold<T>>,
+}
+
+/// A read hold on a [TimestampSource].
+#[derive(Clone, Debug, Serialize, Deserialize)]
+pub struct TimestampReadHold<T> {
+    pub kind: ReadHoldKind,
+    pub frontier: Vec<T>,
+    /// Whether this hold determines the read frontier of the source, i.e.,
+    /// whether it constrains the earliest timestamp that can be chosen for
+    /// the source.
+    pub constrains_read_frontier: bool,
 }
 
 pub trait DisplayableInTimeline {
@@ -803,6 +874,13 @@ impl<T: fmt::Display + fmt::Debug + DisplayableInTimeline + TimestampManipulatio
                 real_time_recency_ts.display(timeline)
             )?;
         }
+        if let Some(adjusted_from) = &self.determination.adjusted_from {
+            writeln!(
+                f,
+                "            requested timestamp: {}",
+                adjusted_from.display(timeline)
+            )?;
+        }
         writeln!(
             f,
             "largest not in advance of upper: {}",
@@ -862,6 +940,25 @@ impl<T: fmt::Display + fmt::Debug + DisplayableInTimeline + TimestampManipulatio
                     .map(|t| t.display(timeline))
                     .collect::<Vec<_>>()
             )?;
+            for hold in &source.read_holds {
+                let kind = match hold.kind {
+                    ReadHoldKind::Timeline => "timeline read hold",
+                    ReadHoldKind::Transaction => "transaction read hold",
+                };
+                let constraining = if hold.constrains_read_frontier {
+                    " (constraining)"
+                } else {
+                    ""
+                };
+                writeln!(
+                    f,
+                    "{kind:>31}:{:?}{constraining}",
+                    hold.frontier
+                        .iter()
+                        .map(|t| t.display(timeline))
+                        .collect::<Vec<_>>()
+                )?;
+            }
         }
         Ok(())
     }
@@ -63,6 +63,9 @@ pub use crate::coord::id_bundle::CollectionIdBundle;
 pub use crate::coord::peek::PeekResponseUnary;
 pub use crate::coord::read_policy::ReadHolds;
 pub use crate::coord::read_policy::ReadHoldsInner;
+pub use crate::coord::read_policy::{
+    dropped_read_holds_channel, DroppedReadHoldsRx, DroppedReadHoldsTx,
+};
 pub use crate::coord::timeline::TimelineContext;
 pub use crate::coord::timestamp_selection::{
     TimestampContext, TimestampExplanation, TimestampProvider,
@@ -7,13 +7,17 @@
 // the Business Source License, use of this software will be governed
 // by the Apache License, Version 2.0.
 
+use std::sync::{Arc, Mutex};
+use std::time::Duration;
+
 use mz_ore::metric;
-use mz_ore::metrics::MetricsRegistry;
+use mz_ore::metrics::{ComputedGauge, MetricsRegistry};
+use mz_ore::now::{EpochMillis, NowFn};
 use mz_ore::stats::{histogram_milliseconds_buckets, histogram_seconds_buckets};
 use mz_sql::ast::{AstInfo, Statement, StatementKind, SubscribeOutput};
 use mz_sql::session::user::User;
 use mz_sql_parser::ast::statement_kind_label_value;
-use prometheus::{Histogram, HistogramVec, IntCounter, IntCounterVec, IntGaugeVec};
+use prometheus::{Histogram, HistogramVec, IntCounter, IntCounterVec, IntGauge, IntGaugeVec};
 
 #[derive(Debug, Clone)]
 pub struct Metrics {
@@ -40,10 +44,17 @@ pub struct Metrics {
     pub check_scheduling_policies_seconds: HistogramVec,
     pub handle_scheduling_decisions_seconds: HistogramVec,
     pub row_set_finishing_seconds: HistogramVec,
+    pub dropped_read_holds_queue_depth: IntGauge,
+    /// When the oldest dropped read hold that waits to be released was dropped,
+    /// along with the clock of the queue it waits in.
+    pub dropped_read_holds_oldest_enqueued_at: Arc<Mutex<Option<(EpochMillis, NowFn)>>>,
+    pub dropped_read_holds_oldest_age_seconds: ComputedGauge,
 }
 
 impl Metrics {
-    pub(crate) fn register_into(registry: &MetricsRegistry) -> Self {
+    pub fn register_into(registry: &MetricsRegistry) -> Self {
+        let dropped_read_holds_oldest_enqueued_at =
+            Arc::new(Mutex::new(None::<(EpochMillis, NowFn)>));
         Self {
             query_total: registry.register(metric!(
                 name: "mz_query_total",
@@ -162,6 +173,26 @@ impl Metrics {
                 help: "The time it takes to run RowSetFinishing::finish.",
                 buckets: histogram_seconds_buckets(0.000_128, 16.0),
             )),
+            dropped_read_holds_queue_depth: registry.register(metric!(
+                name: "mz_dropped_read_holds_queue_depth",
+                help: "The number of dropped read holds waiting to be released by the coordinator.",
+            )),
+            dropped_read_holds_oldest_age_seconds: registry.register_computed_gauge(
+                metric!(
+                    name: "mz_dropped_read_holds_oldest_age_seconds",
+                    help: "How long the oldest dropped read hold has waited to be released, in seconds.",
+                ),
+                {
+                    let oldest = Arc::clone(&dropped_read_holds_oldest_enqueued_at);
+                    move || {
+                        let oldest = oldest.lock().expect("lock poisoned");
+                        oldest.as_ref().map_or(0.0, |(at, now)| {
+                            Duration::from_millis(now().saturating_sub(*at)).as_secs_f64()
+                        })
+                    }
+                },
+            ),
+            dropped_read_holds_oldest_enqueued_at,
         }
     }
 
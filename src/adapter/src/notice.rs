@@ -84,6 +84,14 @@ pub enum AdapterNotice {
     EqualSubscribeBounds {
         bound: mz_repr::Timestamp,
     },
+    AsOfAdjusted {
+        requested: mz_repr::Timestamp,
+        adjusted: mz_repr::Timestamp,
+    },
+    SubscribeAsOfNotReadable {
+        as_of: mz_repr::Timestamp,
+        held_at: mz_repr::Timestamp,
+    },
     QueryTrace {
         trace_id: opentelemetry::trace::TraceId,
     },
@@ -176,6 +184,8 @@ impl AdapterNotice {
             AdapterNotice::DroppedActiveCluster { .. } => Severity::Notice,
             AdapterNotice::QueryTimestamp { .. } => Severity::Notice,
             AdapterNotice::EqualSubscribeBounds { .. } => Severity::Notice,
+            AdapterNotice::AsOfAdjusted { .. } => Severity::Notice,
+            AdapterNotice::SubscribeAsOfNotReadable { .. } => Severity::Notice,
             AdapterNotice::QueryTrace { .. } => Severity::Notice,
             AdapterNotice::UnimplementedIsolationLevel { .. } => Severity::Notice,
             AdapterNotice::StrongSessionSerializable => Severity::Notice,
@@ -216,6 +226,9 @@ impl AdapterNotice {
                     .map(|obj_info| format!("drop cascades to {}", obj_info))
                     .join("\n"),
             ),
+            AdapterNotice::SubscribeAsOfNotReadable { held_at, .. } => {
+                Some(format!("The inputs are held at {held_at}."))
+            }
             _ => None,
         }
     }
@@ -233,6 +246,7 @@ impl AdapterNotice {
                 match status {
                     ServiceStatus::Offline(None) => Some("The cluster replica may be restarting or going offline.".into()),
                     ServiceStatus::Offline(Some(OfflineReason::OomKilled)) => Some("The cluster replica may have run out of memory and been killed.".into()),
+                    ServiceStatus::Offline(Some(OfflineReason::CrashLooping)) => Some("The cluster replica restarted too often and is no longer being restarted.".into()),
                     ServiceStatus::Online => None,
                 }
             },
@@ -276,6 +290,8 @@ impl AdapterNotice {
             AdapterNotice::DroppedActiveCluster { .. } => SqlState::from_code("MZ003"),
             AdapterNotice::QueryTimestamp { .. } => SqlState::SUCCESSFUL_COMPLETION,
             AdapterNotice::EqualSubscribeBounds { .. } => SqlState::SUCCESSFUL_COMPLETION,
+            AdapterNotice::AsOfAdjusted { .. } => SqlState::SUCCESSFUL_COMPLETION,
+            AdapterNotice::SubscribeAsOfNotReadable { .. } => SqlState::SUCCESSFUL_COMPLETION,
             AdapterNotice::QueryTrace { .. } => SqlState::SUCCESSFUL_COMPLETION,
             AdapterNotice::UnimplementedIsolationLevel { .. } => SqlState::SUCCESSFUL_COMPLETION,
             AdapterNotice::StrongSessionSerializable => SqlState::SUCCESSFUL_COMPLETION,
@@ -378,6 +394,17 @@ impl fmt::Display for AdapterNotice {
             AdapterNotice::EqualSubscribeBounds { bound } => {
                 write!(f, "subscribe as of {bound} (inclusive) up to the same bound {bound} (exclusive) is guaranteed to be empty")
             }
+            AdapterNotice::AsOfAdjusted {
+                requested,
+                adjusted,
+            } => write!(
+                f,
+                "AS OF {requested} is before the earliest readable time of the inputs; reading at {adjusted} instead"
+            ),
+            AdapterNotice::SubscribeAsOfNotReadable { as_of, .. } => write!(
+                f,
+                "SUBSCRIBE AS OF {as_of} is not readable yet; its inputs are held at their latest readable time and the subscribe waits for the AS OF to become readable"
+            ),
             AdapterNotice::QueryTrace { trace_id } => {
                 write!(f, "trace id: {}", trace_id)
             }
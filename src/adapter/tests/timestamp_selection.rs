@@ -13,12 +13,16 @@ use std::collections::{BTreeMap, BTreeSet};
 
 use async_trait::async_trait;
 use mz_adapter::catalog::CatalogState;
+use mz_adapter::dropped_read_holds_channel;
+use mz_adapter::metrics::Metrics;
 use mz_adapter::session::Session;
 use mz_adapter::ReadHolds;
 use mz_adapter::ReadHoldsInner;
 use mz_adapter::{CollectionIdBundle, TimelineContext, TimestampProvider};
 use mz_compute_types::ComputeInstanceId;
 use mz_expr::MirScalarExpr;
+use mz_ore::metrics::MetricsRegistry;
+use mz_ore::now::NOW_ZERO;
 use mz_repr::{Datum, GlobalId, ScalarType, Timestamp};
 use mz_sql::plan::QueryWhen;
 use mz_sql::session::vars::IsolationLevel;
@@ -144,7 +148,8 @@ impl TimestampProvider for Frontiers {
             read_holds.storage_holds.insert(*id, mock_storage_hold);
         }
 
-        let (dummy_tx, _dummy_rx) = tokio::sync::mpsc::unbounded_channel();
+        let metrics = Metrics::register_into(&MetricsRegistry::new());
+        let (dummy_tx, _dummy_rx) = dropped_read_holds_channel(&metrics, NOW_ZERO.clone());
         ReadHolds::new(read_holds, dummy_tx)
     }
 
@@ -3249,6 +3249,49 @@ pub static MZ_SESSIONS: Lazy<BuiltinTable> = Lazy::new(|| BuiltinTable {
     access: vec![PUBLIC_SELECT],
 });
 
+pub static MZ_TIMELINE_TIMESTAMPS: Lazy<BuiltinTable> = Lazy::new(|| BuiltinTable {
+    name: "mz_timeline_timestamps",
+    schema: MZ_INTERNAL_SCHEMA,
+    oid: oid::TABLE_MZ_TIMELINE_TIMESTAMPS_OID,
+    desc: RelationDesc::empty()
+        .with_column("timeline", ScalarType::String.nullable(false))
+        .with_column("read_ts", ScalarType::MzTimestamp.nullable(false))
+        .with_column("write_ts", ScalarType::MzTimestamp.nullable(false))
+        .with_column("read_hold", ScalarType::MzTimestamp.nullable(true)),
+    is_retained_metrics_object: false,
+    access: vec![PUBLIC_SELECT],
+});
+
+pub static MZ_READ_HOLDS: Lazy<BuiltinTable> = Lazy::new(|| BuiltinTable {
+    name: "mz_read_holds",
+    schema: MZ_INTERNAL_SCHEMA,
+    oid: oid::TABLE_MZ_READ_HOLDS_OID,
+    desc: RelationDesc::empty()
+        .with_column("object_id", ScalarType::String.nullable(false))
+        .with_column("cluster_id", ScalarType::String.nullable(true))
+        .with_column("holder", ScalarType::String.nullable(false))
+        .with_column("connection_id", ScalarType::UInt32.nullable(true))
+        .with_column("read_frontier", ScalarType::MzTimestamp.nullable(true)),
+    is_retained_metrics_object: false,
+    access: vec![PUBLIC_SELECT],
+});
+
+pub static MZ_READ_HOLD_WAITS: Lazy<BuiltinTable> = Lazy::new(|| BuiltinTable {
+    name: "mz_read_hold_waits",
+    schema: MZ_INTERNAL_SCHEMA,
+    oid: oid::TABLE_MZ_READ_HOLD_WAITS_OID,
+    desc: RelationDesc::empty()
+        .with_column("waiter", ScalarType::String.nullable(false))
+        .with_column("waiter_connection_id", ScalarType::UInt32.nullable(true))
+        .with_column("object_id", ScalarType::String.nullable(false))
+        .with_column("cluster_id", ScalarType::String.nullable(true))
+        .with_column("holder", ScalarType::String.nullable(true))
+        .with_column("holder_connection_id", ScalarType::UInt32.nullable(true))
+        .with_column("timestamp", ScalarType::MzTimestamp.nullable(true)),
+    is_retained_metrics_object: false,
+    access: vec![PUBLIC_SELECT],
+});
+
 pub static MZ_DEFAULT_PRIVILEGES: Lazy<BuiltinTable> = Lazy::new(|| BuiltinTable {
     name: "mz_default_privileges",
     schema: MZ_CATALOG_SCHEMA,
@@ -7586,6 +7629,9 @@ pub static BUILTINS_STATIC: Lazy<Vec<Builtin<NameReference>>> = Lazy::new(|| {
         Builtin::Table(&MZ_AWS_CONNECTIONS),
         Builtin::Table(&MZ_SUBSCRIPTIONS),
         Builtin::Table(&MZ_SESSIONS),
+        Builtin::Table(&MZ_TIMELINE_TIMESTAMPS),
+        Builtin::Table(&MZ_READ_HOLDS),
+        Builtin::Table(&MZ_READ_HOLD_WAITS),
         Builtin::Table(&MZ_DEFAULT_PRIVILEGES),
         Builtin::Table(&MZ_SYSTEM_PRIVILEGES),
         Builtin::Table(&MZ_COMMENTS),
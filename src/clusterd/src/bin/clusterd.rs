@@ -178,8 +178,13 @@ async fn run(args: Args) -> Result<(), anyhow::Error> {
         .await
         .context("loading secrets reader")?;
 
+    // Shared with the persist config below, which receives the config updates
+    // of the controllers.
+    let dyncfgs = mz_dyncfgs::all_dyncfgs();
+
     mz_ore::task::spawn(|| "clusterd_internal_http_server", {
         let metrics_registry = metrics_registry.clone();
+        let dyncfgs = dyncfgs.clone();
         tracing::info!(
             "serving internal HTTP server on {}",
             args.internal_http_listen_addr
@@ -197,6 +202,12 @@ async fn run(args: Args) -> Result<(), anyhow::Error> {
                 }),
             )
             .route("/api/tracing", routing::get(mz_http_util::handle_tracing))
+            .route(
+                "/api/dyncfg/snapshot",
+                routing::get(move || async move {
+                    mz_http_util::handle_dyncfg_snapshot(&dyncfgs).await
+                }),
+            )
             .route(
                 "/api/opentelemetry/config",
                 routing::put({
@@ -260,8 +271,7 @@ async fn run(args: Args) -> Result<(), anyhow::Error> {
         .ok()
         .or_else(|| args.tracing.log_prefix.clone())
         .unwrap_or_default();
-    let mut persist_cfg =
-        PersistConfig::new(&BUILD_INFO, SYSTEM_TIME.clone(), mz_dyncfgs::all_dyncfgs());
+    let mut persist_cfg = PersistConfig::new(&BUILD_INFO, SYSTEM_TIME.clone(), dyncfgs);
     persist_cfg.is_cc_active = args.is_cluster_size_v2;
     persist_cfg.announce_memory_limit = args.announce_memory_limit;
     let persist_clients = Arc::new(PersistClientCache::new(
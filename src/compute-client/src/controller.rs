@@ -581,7 +581,12 @@ where
     }
 
     /// Update compute configuration.
-    pub fn update_configuration(&mut self, config_params: ComputeParameters) {
+    pub fn update_configuration(&mut self, mut config_params: ComputeParameters) {
+        // The adapter sends the values of all configs on every change. Our config set has applied
+        // every dyncfg update forwarded to the clusters so far, so its digest describes the
+        // values the replicas already have, and only the updates that change them are forwarded.
+        config_params.dyncfg_updates = config_params.dyncfg_updates.delta(&self.dyncfg.digest());
+
         // Apply dyncfg updates.
         config_params.dyncfg_updates.apply(&self.dyncfg);
 
@@ -929,6 +934,13 @@ impl<T: ComputeControllerTimestamp> ComputeInstanceRef<'_, T> {
     pub fn collections(&self) -> impl Iterator<Item = (&GlobalId, &CollectionState<T>)> {
         self.instance.collections_iter()
     }
+
+    /// Return an iterator over the read holds of the outstanding peeks, by
+    /// peek, along with whether each is a hold on a storage collection rather
+    /// than on a collection of this instance.
+    pub fn peek_read_holds(&self) -> impl Iterator<Item = (&Uuid, &ReadHold<T>, bool)> {
+        self.instance.peek_read_holds_iter()
+    }
 }
 
 /// State maintained about individual compute collections.
@@ -34,7 +34,7 @@ use mz_repr::refresh_schedule::RefreshSchedule;
 use mz_repr::{Datum, Diff, GlobalId, Row};
 use mz_storage_client::controller::IntrospectionType;
 use mz_storage_client::storage_collections::StorageCollections;
-use mz_storage_types::read_holds::{ReadHold, ReadHoldError};
+use mz_storage_types::read_holds::{apply_read_capability_changes, ReadHold, ReadHoldError};
 use mz_storage_types::read_policy::ReadPolicy;
 use serde::Serialize;
 use thiserror::Error;
@@ -276,6 +276,14 @@ impl<T: ComputeControllerTimestamp> Instance<T> {
         self.collections.iter()
     }
 
+    /// Returns the read holds installed to serve the outstanding peeks, by
+    /// peek, along with whether each is a hold on a storage collection.
+    pub fn peek_read_holds_iter(&self) -> impl Iterator<Item = (&Uuid, &ReadHold<T>, bool)> {
+        self.peeks
+            .iter()
+            .map(|(uuid, peek)| (uuid, &peek.read_hold, peek.storage_read_hold))
+    }
+
     /// Add a collection to the instance state.
     ///
     /// # Panics
@@ -1371,6 +1379,7 @@ where
         peek_target: PeekTarget,
     ) -> Result<(), PeekError> {
         // Install a compaction hold on `id` at `timestamp`.
+        let storage_read_hold = matches!(peek_target, PeekTarget::Persist { .. });
         let read_hold = match &peek_target {
             PeekTarget::Index { .. } => {
                 self.acquire_read_hold_at(id, Antichain::from_elem(timestamp.clone()))?
@@ -1394,7 +1403,8 @@ where
                 // TODO(guswynn): can we just hold the `tracing::Span` here instead?
                 otel_ctx: otel_ctx.clone(),
                 requested_at: Instant::now(),
-                _read_hold: read_hold,
+                read_hold,
+                storage_read_hold,
             },
         );
 
@@ -1561,29 +1571,16 @@ where
                     continue;
                 };
 
-                // Sanity check to prevent corrupted `read_capabilities`, which can cause hard-to-debug
-                // issues (usually stuck read frontiers).
-                let read_frontier = collection.read_capabilities.frontier();
-                for (time, diff) in update.iter() {
-                    let count = collection.read_capabilities.count_for(time) + diff;
-                    assert!(
-                        count >= 0,
-                        "invalid read capabilities update: negative capability \
-                     (id={id:?}, read_capabilities={:?}, update={update:?})",
-                        collection.read_capabilities,
-                    );
-                    assert!(
-                        count == 0 || read_frontier.less_equal(time),
-                        "invalid read capabilities update: frontier regression \
-                     (id={id:?}, read_capabilities={:?}, update={update:?})",
-                        collection.read_capabilities,
-                    );
-                }
-
                 // Apply read capability updates and learn about resulting changes to the read
-                // frontier.
-                let changes = collection.read_capabilities.update_iter(update.drain());
-                if changes.count() == 0 {
+                // frontier. This also sanity checks the updates, to prevent corrupted
+                // `read_capabilities`, which can cause hard-to-debug issues (usually stuck read
+                // frontiers).
+                let mut changes = apply_read_capability_changes(
+                    id,
+                    &mut collection.read_capabilities,
+                    &mut update,
+                );
+                if changes.is_empty() {
                     continue; // read frontier did not change
                 }
 
@@ -1950,7 +1947,10 @@ struct PendingPeek<T: Timestamp> {
     /// Used to track peek durations.
     requested_at: Instant,
     /// The read hold installed to serve this peek.
-    _read_hold: ReadHold<T>,
+    read_hold: ReadHold<T>,
+    /// Whether `read_hold` is a hold on a storage collection, i.e., the peek
+    /// reads from persist rather than from an index.
+    storage_read_hold: bool,
 }
 
 #[derive(Debug, Clone)]
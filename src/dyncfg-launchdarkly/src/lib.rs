@@ -9,8 +9,9 @@
 
 //! A dyncfg::ConfigSet backed by LaunchDarkly.
 
-use std::time::Duration;
+use std::time::{Duration, SystemTime};
 
+use bytesize::ByteSize;
 use launchdarkly_server_sdk as ld;
 use mz_build_info::BuildInfo;
 use mz_dyncfg::{ConfigSet, ConfigUpdates, ConfigVal};
@@ -144,12 +145,15 @@ impl<F: Fn(&ConfigUpdates, &ConfigSet) + Send> SyncedConfigSet<F> {
             if let Err(err) = self.sync() {
                 tracing::info!("SyncedConfigSet: {err}");
             }
+            for updates in self.set.apply_scheduled(SystemTime::now()) {
+                (self.on_update)(&updates, &self.set);
+            }
         }
     }
 
     /// Reads current values from LaunchDarkly and updates the ConfigSet.
     fn sync(&self) -> Result<ConfigUpdates, anyhow::Error> {
-        let mut updates = ConfigUpdates::default();
+        let mut updates = ConfigUpdates::default().with_source("launchdarkly");
         let Some(ld_client) = &self.ld_client else {
             (self.on_update)(&updates, &self.set);
             return Ok(updates);
@@ -159,9 +163,15 @@ impl<F: Fn(&ConfigUpdates, &ConfigSet) + Send> SyncedConfigSet<F> {
             let flag_var = ld_client.variation(&self.ld_ctx, entry.name(), val);
             let update = match (entry.val(), flag_var) {
                 (ConfigVal::Bool(_), ld::FlagValue::Bool(flag)) => ConfigVal::Bool(flag),
+                (ConfigVal::U8(_), ld::FlagValue::Number(flag)) => {
+                    ConfigVal::U8(u8::cast_lossy(flag))
+                }
                 (ConfigVal::U32(_), ld::FlagValue::Number(flag)) => {
                     ConfigVal::U32(u32::cast_lossy(flag))
                 }
+                (ConfigVal::I64(_), ld::FlagValue::Number(flag)) => {
+                    ConfigVal::I64(i64::cast_lossy(flag))
+                }
                 (ConfigVal::Usize(_), ld::FlagValue::Number(flag)) => {
                     ConfigVal::Usize(usize::cast_lossy(flag))
                 }
@@ -170,15 +180,24 @@ impl<F: Fn(&ConfigUpdates, &ConfigSet) + Send> SyncedConfigSet<F> {
                 (ConfigVal::Duration(_), ld::FlagValue::Str(flag)) => {
                     ConfigVal::Duration(humantime::parse_duration(&flag)?)
                 }
+                (ConfigVal::Bytes(_), ld::FlagValue::Number(flag)) => {
+                    ConfigVal::Bytes(ByteSize(u64::cast_lossy(flag)))
+                }
+                (ConfigVal::Bytes(_), ld::FlagValue::Str(flag)) => {
+                    ConfigVal::Bytes(flag.parse().map_err(anyhow::Error::msg)?)
+                }
                 (ConfigVal::Json(_), ld::FlagValue::Json(flag)) => ConfigVal::Json(flag),
 
                 // Hardcode all others so that if ConfigVal gets new types this match block will
                 // compile error.
                 (ConfigVal::Bool(_), _)
+                | (ConfigVal::U8(_), _)
                 | (ConfigVal::U32(_), _)
+                | (ConfigVal::I64(_), _)
                 | (ConfigVal::Usize(_), _)
                 | (ConfigVal::F64(_), _)
                 | (ConfigVal::Duration(_), _)
+                | (ConfigVal::Bytes(_), _)
                 | (ConfigVal::Json(_), _)
                 | (ConfigVal::OptUsize(_), _)
                 | (ConfigVal::String(_), _) => anyhow::bail!(
@@ -187,6 +206,19 @@ impl<F: Fn(&ConfigUpdates, &ConfigSet) + Send> SyncedConfigSet<F> {
                 ),
             };
             updates.add_dynamic(entry.name(), update);
+            // A change to a dangerous config is only applied if its
+            // `<name>.confirm` flag is also set, otherwise it is staged.
+            if entry.dangerous() {
+                let confirmation = mz_dyncfg::confirmation_name(entry.name());
+                let confirmed = ld_client.variation(
+                    &self.ld_ctx,
+                    &confirmation,
+                    ld::FlagValue::Bool(false),
+                );
+                if matches!(confirmed, ld::FlagValue::Bool(true)) {
+                    updates.confirm_dynamic(entry.name());
+                }
+            }
         }
         updates.apply(&self.set);
         (self.on_update)(&updates, &self.set);
@@ -202,12 +234,15 @@ fn dyn_into_flag(val: ConfigVal) -> Result<ld::FlagValue, anyhow::Error> {
     // error always for an Opt value because it might be None.
     Ok(match val {
         ConfigVal::Bool(v) => ld::FlagValue::Bool(v),
+        ConfigVal::U8(v) => ld::FlagValue::Number(v.into()),
         ConfigVal::U32(v) => ld::FlagValue::Number(v.into()),
+        ConfigVal::I64(v) => ld::FlagValue::Number(f64::cast_lossy(v)),
         ConfigVal::Usize(v) => ld::FlagValue::Number(f64::cast_lossy(v)),
         ConfigVal::OptUsize(_) => anyhow::bail!("OptUsize None cannot be converted to a FlagValue"),
         ConfigVal::F64(v) => ld::FlagValue::Number(v),
         ConfigVal::String(v) => ld::FlagValue::Str(v),
         ConfigVal::Duration(v) => ld::FlagValue::Str(humantime::format_duration(v).to_string()),
+        ConfigVal::Bytes(v) => ld::FlagValue::Number(f64::cast_lossy(v.as_u64())),
         ConfigVal::Json(v) => ld::FlagValue::Json(v),
     })
 }
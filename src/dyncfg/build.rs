@@ -7,10 +7,16 @@
 // the Business Source License, use of this software will be governed
 // by the Apache License, Version 2.0.
 
-use std::env;
-
 fn main() {
-    env::set_var("PROTOC", mz_build_tools::protoc());
+    // Config updates, and with them the protobuf messages, are only built
+    // with the `proto` feature.
+    #[cfg(feature = "proto")]
+    compile_protos();
+}
+
+#[cfg(feature = "proto")]
+fn compile_protos() {
+    std::env::set_var("PROTOC", mz_build_tools::protoc());
 
     prost_build::Config::new()
         .btree_map(["."])
@@ -54,18 +54,63 @@
 //!   to run the read-write `persistcli admin` tooling with the defaults
 //!   compiled into code, but `persistcli` doesn't have access to the vars stuff
 //!   and doesn't want to instantiate a catalog impl.
-
-use std::collections::BTreeMap;
+//!
+//!   Such tools may opt in to a process-wide `ConfigSet` with
+//!   [`ConfigSet::install_global`] and read it with [`ConfigSet::global`],
+//!   loading values with [`ConfigUpdates::load_file`] or
+//!   [`ConfigUpdates::from_env`]. Nothing else installs the global set, so
+//!   tests and the rest of the codebase are unaffected by it.
+//! - Dev and CI environments can flip configs without any flag infrastructure
+//!   by opting a set in to [`ConfigSet::with_env_overrides`], which freezes
+//!   configs at the values of `MZ_DYNCFG_<NAME>` environment variables.
+//! - External flag-management tooling can keep its flag definitions in sync
+//!   with the code by consuming [`ConfigSet::schema`].
+//! - Every change to a config value is logged with the `dyncfg` tracing
+//!   target, along with the source of the change set by
+//!   [`ConfigUpdates::with_source`], so the changes show up in our logs.
+//!   [`ConfigSet::register_change_listener`] can record them elsewhere too.
+//! - Processes that sync the full set of values from another process on every
+//!   change can have the sender leave out the values they already have: the
+//!   receiver reports a [`ConfigDigest`] of its set and the sender sends only
+//!   the [`ConfigUpdates::delta`] against it. A sender that mirrors every
+//!   update it sent in a set of its own, like the compute controller does for
+//!   its replicas, can take the digest of that set instead.
+//! - A config update that fails to propagate to some process shows up as a
+//!   config whose value differs between processes. Collect a
+//!   [`ConfigUpdates::snapshot`] from each process, e.g. with the
+//!   `handle_dyncfg_snapshot` HTTP handler of `mz-http-util`, and compare them
+//!   with [`check_consistency`].
+//! - Configs outlive the rollouts they were added for. In debug builds, a set
+//!   tracks which of its configs were ever read, so tests can flag the dead
+//!   ones with [`ConfigSet::unread_configs`].
+//! - Tools that only need the typed config definitions and defaults of the
+//!   server shouldn't have to build its protobuf code. Without the default
+//!   `proto` feature, this crate offers only [`Config`], [`ConfigSet`], and
+//!   their values and schema, but not [`ConfigUpdates`], which are encoded as
+//!   protobuf messages, and doesn't depend on `prost` or `mz-proto`. It then
+//!   also builds for `wasm32-unknown-unknown`, which CI checks, so it must not
+//!   read the clock outside of the `proto` feature: there is none on that
+//!   target.
+
+use std::cell::RefCell;
+use std::collections::{btree_map, BTreeMap, BTreeSet, VecDeque};
 use std::marker::PhantomData;
-use std::sync::atomic::Ordering::SeqCst;
-use std::sync::atomic::{AtomicBool, AtomicU32, AtomicU64, AtomicUsize};
-use std::sync::{Arc, RwLock};
-use std::time::Duration;
-
-use tracing::error;
-
+use std::num::{ParseFloatError, ParseIntError};
+#[cfg(feature = "proto")]
+use std::path::Path;
+use std::sync::atomic::Ordering::{Relaxed, SeqCst};
+use std::sync::atomic::{AtomicBool, AtomicI64, AtomicU32, AtomicU64, AtomicU8, AtomicUsize};
+use std::sync::{Arc, Mutex, OnceLock, RwLock};
+use std::time::{Duration, Instant, SystemTime};
+
+use bytesize::ByteSize;
+#[cfg(feature = "proto")]
+use tracing::{error, info, warn};
+
+#[cfg(feature = "proto")]
 use mz_proto::{ProtoType, RustType};
 
+#[cfg(feature = "proto")]
 include!(concat!(env!("OUT_DIR"), "/mz_dyncfg.rs"));
 
 /// A handle to a dynamically updatable configuration value.
@@ -74,13 +119,19 @@ include!(concat!(env!("OUT_DIR"), "/mz_dyncfg.rs"));
 /// registered to a set of such configs with [ConfigSet::add] and then later
 /// used to retrieve the latest value at any time with [Self::get].
 ///
-/// The supported types are [bool], [usize], [Duration], and [String], as well as [Option]
-/// variants of these as necessary.
+/// The supported types are [bool], [u8], [u32], [i64], [usize], [f64],
+/// [Duration], [ByteSize], [String], and JSON values, as well as [Option]
+/// variants of these as necessary. [Duration] configs may be declared in the
+/// unit they're documented in and restricted to bounds, see [Config::new_secs]
+/// and [Config::with_bounds]. Small enums may be stored as a [u8], see
+/// [impl_config_enum].
 #[derive(Clone, Debug)]
 pub struct Config<D: ConfigDefault> {
     name: &'static str,
     desc: &'static str,
     default: D,
+    dangerous: bool,
+    duration_bounds: Option<DurationBounds>,
 }
 
 impl<D: ConfigDefault> Config<D> {
@@ -103,6 +154,24 @@ impl<D: ConfigDefault> Config<D> {
             name,
             default,
             desc,
+            dangerous: false,
+            duration_bounds: None,
+        }
+    }
+
+    /// Like [Config::new], but marks the config as dangerous.
+    ///
+    /// Updates to a dangerous config are only applied if they are confirmed,
+    /// see [ConfigUpdates::confirm]. This guards configs where a single
+    /// mistaken flag push would do outsized damage, e.g., by disabling safety
+    /// checks everywhere at once.
+    pub const fn new_dangerous(name: &'static str, default: D, desc: &'static str) -> Self {
+        Config {
+            name,
+            default,
+            desc,
+            dangerous: true,
+            duration_bounds: None,
         }
     }
 
@@ -121,6 +190,11 @@ impl<D: ConfigDefault> Config<D> {
         &self.default
     }
 
+    /// Whether this config is dangerous. See [Config::new_dangerous].
+    pub fn dangerous(&self) -> bool {
+        self.dangerous
+    }
+
     /// Returns the latest value of this config within the given set.
     ///
     /// Panics if this config was not previously registered to the set.
@@ -144,12 +218,58 @@ impl<D: ConfigDefault> Config<D> {
         }
     }
 
+    /// Returns a handle to the value of this config in the given set that
+    /// caches the value for up to `ttl`.
+    ///
+    /// See [CachedConfigValHandle].
+    pub fn cached(&self, set: &ConfigSet, ttl: Duration) -> CachedConfigValHandle<D::ConfigType> {
+        CachedConfigValHandle {
+            handle: self.handle(set),
+            ttl,
+            cached: RefCell::new(None),
+        }
+    }
+
+    /// Overrides the value of this config in the given set until the returned
+    /// guard is dropped, which restores the previous value.
+    ///
+    /// This is meant for tests, so that an override can't leak into later
+    /// assertions, even if the test panics. The override runs the update
+    /// callbacks of the set but bypasses the checks of [ConfigUpdates::apply]:
+    /// dangerous configs need no confirmation, frozen configs are overridden
+    /// too, and duration bounds are not enforced.
+    ///
+    /// Panics if this config was not previously registered to the set.
+    #[cfg(feature = "proto")]
+    pub fn override_for_test<U>(&self, set: &ConfigSet, val: U) -> ConfigScopeGuard
+    where
+        U: ConfigDefault<ConfigType = D::ConfigType>,
+    {
+        let entry = set
+            .entry(self.name)
+            .unwrap_or_else(|| panic!("config {} should be registered to set", self.name));
+        let prev = entry.val();
+        let val = val.into_config_type().into();
+        ConfigUpdates::apply_valid(set, vec![(entry, val)], TEST_OVERRIDE_SOURCE);
+        ConfigScopeGuard {
+            set: set.clone(),
+            name: self.name,
+            prev,
+        }
+    }
+
     /// Returns the shared value of this config in the given set.
     fn shared<'a>(&self, set: &'a ConfigSet) -> &'a ConfigValAtomic {
-        &set.configs
+        let entry = set
+            .configs
             .get(self.name)
-            .unwrap_or_else(|| panic!("config {} should be registered to set", self.name))
-            .val
+            .unwrap_or_else(|| panic!("config {} should be registered to set", self.name));
+        // Check first, to keep hot reads from contending on the flag.
+        #[cfg(debug_assertions)]
+        if !entry.read.load(SeqCst) {
+            entry.read.store(true, SeqCst);
+        }
+        &entry.val
     }
 
     /// Parse a string value for this config.
@@ -160,6 +280,66 @@ impl<D: ConfigDefault> Config<D> {
     }
 }
 
+impl Config<Duration> {
+    /// Like [Config::new], but with a default of `secs` seconds.
+    ///
+    /// This allows declaring the default in the unit it's documented in, e.g.,
+    /// `Config::new_secs("timeout", 30, "timeout in seconds")`.
+    pub const fn new_secs(name: &'static str, secs: u64, desc: &'static str) -> Self {
+        Config::new(name, Duration::from_secs(secs), desc)
+    }
+
+    /// Like [Config::new], but with a default of `millis` milliseconds.
+    pub const fn new_millis(name: &'static str, millis: u64, desc: &'static str) -> Self {
+        Config::new(name, Duration::from_millis(millis), desc)
+    }
+
+    /// Restricts the values of this config to the inclusive range from `min`
+    /// to `max`.
+    ///
+    /// Updates to values outside of the range are rejected like updates of
+    /// the wrong type, see [InvalidConfigUpdate::OutOfBounds]. This guards
+    /// against pushing, e.g., a zero interval that would turn a background
+    /// task into a busy loop.
+    ///
+    /// Panics, at compile time when used in a `const`, if `min` is greater
+    /// than `max` or the default is outside of the range.
+    pub const fn with_bounds(self, min: Duration, max: Duration) -> Self {
+        let bounds = DurationBounds { min, max };
+        assert!(
+            min.as_nanos() <= max.as_nanos(),
+            "duration bounds must not be empty"
+        );
+        assert!(
+            bounds.contains(self.default),
+            "default must be within the duration bounds"
+        );
+        Config {
+            duration_bounds: Some(bounds),
+            ..self
+        }
+    }
+}
+
+/// Inclusive bounds on the values of a [Duration] config.
+///
+/// See [Config::with_bounds].
+#[derive(Clone, Copy, Debug, PartialEq, Eq)]
+pub struct DurationBounds {
+    /// The least allowed value.
+    pub min: Duration,
+    /// The greatest allowed value.
+    pub max: Duration,
+}
+
+impl DurationBounds {
+    /// Whether `val` is within these bounds.
+    pub const fn contains(&self, val: Duration) -> bool {
+        let val = val.as_nanos();
+        self.min.as_nanos() <= val && val <= self.max.as_nanos()
+    }
+}
+
 /// A type usable as a [Config].
 pub trait ConfigType: Into<ConfigVal> + Clone + Sized {
     /// Converts a type-erased enum value to this type.
@@ -200,6 +380,40 @@ impl<T: ConfigType> ConfigDefault for fn() -> T {
 #[derive(Clone, Default)]
 pub struct ConfigSet {
     configs: BTreeMap<String, ConfigEntry>,
+    callbacks: Arc<Mutex<Vec<(String, UpdateCallback)>>>,
+    change_listeners: Arc<Mutex<Vec<ChangeListener>>>,
+    #[cfg(feature = "proto")]
+    scheduled: Arc<Mutex<Vec<ScheduledConfigUpdates>>>,
+    staged: Arc<Mutex<BTreeMap<&'static str, ConfigVal>>>,
+    frozen: Arc<Mutex<BTreeSet<&'static str>>>,
+}
+
+/// The prefix of the environment variables read by
+/// [ConfigSet::with_env_overrides] in production binaries.
+pub const ENV_OVERRIDE_PREFIX: &str = "MZ_DYNCFG_";
+
+/// The process-wide set installed by [ConfigSet::install_global].
+static GLOBAL: OnceLock<ConfigSet> = OnceLock::new();
+
+/// A callback registered with [ConfigSet::register_update_callback].
+type UpdateCallback = Arc<dyn Fn(&ConfigSet, &BTreeSet<&'static str>) + Send + Sync>;
+
+/// A listener registered with [ConfigSet::register_change_listener].
+type ChangeListener = Arc<dyn Fn(&ConfigChange) + Send + Sync>;
+
+/// A change to the value of a config in a [ConfigSet].
+///
+/// See [ConfigSet::register_change_listener].
+#[derive(Clone, Debug, PartialEq)]
+pub struct ConfigChange {
+    /// The name of the changed config.
+    pub name: &'static str,
+    /// The value of the config before the change.
+    pub old: ConfigVal,
+    /// The value of the config after the change.
+    pub new: ConfigVal,
+    /// Where the change originated. See [ConfigUpdates::with_source].
+    pub source: String,
 }
 
 impl ConfigSet {
@@ -219,7 +433,12 @@ impl ConfigSet {
             name: config.name,
             desc: config.desc,
             default: default.clone(),
+            dangerous: config.dangerous,
+            duration_bounds: config.duration_bounds,
             val: ConfigValAtomic::from(default),
+            history: Arc::new(Mutex::new(VecDeque::new())),
+            #[cfg(debug_assertions)]
+            read: Arc::new(AtomicBool::new(false)),
         };
         if let Some(prev) = self.configs.insert(config.name.to_owned(), config) {
             panic!("{} registered twice", prev.name);
@@ -227,6 +446,159 @@ impl ConfigSet {
         self
     }
 
+    /// Merges the registrations of `other` into this set.
+    ///
+    /// This allows a binary to build its set from the config bundles of the
+    /// subsystems it's composed of. A config registered to both sets must have
+    /// the same type and default in each, in which case the entry (and thus
+    /// the current value) of this set is kept. Otherwise, returns an error
+    /// listing every conflicting config, without merging anything.
+    ///
+    /// The update callbacks and change listeners registered to `other` are
+    /// carried over, as is whether the configs taken from it are frozen; its
+    /// scheduled and staged updates are not.
+    pub fn merge(mut self, other: ConfigSet) -> Result<Self, ConfigSetMergeError> {
+        let mut conflicts = BTreeMap::new();
+        for (name, entry) in &other.configs {
+            let Some(prev) = self.configs.get(name) else {
+                continue;
+            };
+            let (left, right) = (&prev.default, &entry.default);
+            if left.type_name() != right.type_name() {
+                let conflict = ConfigConflict::TypeMismatch {
+                    left: left.type_name(),
+                    right: right.type_name(),
+                };
+                conflicts.insert(name.clone(), conflict);
+            } else if left != right {
+                let conflict = ConfigConflict::DefaultMismatch {
+                    left: left.clone(),
+                    right: right.clone(),
+                };
+                conflicts.insert(name.clone(), conflict);
+            }
+        }
+        if !conflicts.is_empty() {
+            return Err(ConfigSetMergeError { conflicts });
+        }
+
+        if !Arc::ptr_eq(&self.callbacks, &other.callbacks) {
+            let other_callbacks = other.callbacks.lock().expect("lock poisoned");
+            let mut callbacks = self.callbacks.lock().expect("lock poisoned");
+            callbacks.extend(other_callbacks.iter().cloned());
+        }
+        if !Arc::ptr_eq(&self.change_listeners, &other.change_listeners) {
+            let other_listeners = other.change_listeners.lock().expect("lock poisoned");
+            let mut listeners = self.change_listeners.lock().expect("lock poisoned");
+            listeners.extend(other_listeners.iter().cloned());
+        }
+        let other_frozen = other.frozen();
+        for (name, entry) in other.configs {
+            if let btree_map::Entry::Vacant(vacant) = self.configs.entry(name) {
+                if other_frozen.contains(entry.name) {
+                    let mut frozen = self.frozen.lock().expect("lock poisoned");
+                    frozen.insert(entry.name);
+                }
+                vacant.insert(entry);
+            }
+        }
+        Ok(self)
+    }
+
+    /// Applies overrides for the configs of this set from environment
+    /// variables and freezes them at the overridden values.
+    ///
+    /// The variables are read with [ConfigUpdates::from_env], so with a
+    /// `prefix` of [ENV_OVERRIDE_PREFIX] the `persist_blob_target_size` config
+    /// is overridden by `MZ_DYNCFG_PERSIST_BLOB_TARGET_SIZE`. Overrides of
+    /// dangerous configs need no confirmation, as setting the variable is
+    /// confirmation enough. If any variable can't be parsed or holds a value
+    /// the config doesn't allow, returns an error describing every such
+    /// variable, without applying any of them.
+    ///
+    /// Freezing the overridden configs, see [ConfigSet::freeze], keeps the
+    /// overrides from being undone by the next sync from a flag service. This
+    /// is intended as a zero-infrastructure way to flip configs in dev and CI.
+    #[cfg(feature = "proto")]
+    pub fn with_env_overrides(self, prefix: &str) -> Result<Self, ConfigUpdatesError> {
+        let mut updates = ConfigUpdates::from_env(&self, prefix)?;
+        let overridden: Vec<_> = self
+            .entries()
+            .filter(|config| updates.updates.contains_key(config.name))
+            .collect();
+        for config in &overridden {
+            if config.dangerous {
+                updates.confirm_dynamic(config.name);
+            }
+        }
+        if let Err(mut err) = updates.apply_strict(&self) {
+            // Report the offending variables, like `from_env` does.
+            err.invalid = std::mem::take(&mut err.invalid)
+                .into_iter()
+                .map(|(name, invalid)| (env_var_name(prefix, &name), invalid))
+                .collect();
+            return Err(err);
+        }
+        let overridden: Vec<_> = overridden.into_iter().map(|config| config.name).collect();
+        for name in overridden {
+            self.freeze(name);
+        }
+        Ok(self)
+    }
+
+    /// Freezes the config with `name` at its current value.
+    ///
+    /// Later [ConfigUpdates] to a frozen config are skipped, and reported as
+    /// skipped if they would have changed its value, until it is unfrozen with
+    /// [ConfigSet::unfreeze]. This allows an operator to manually override a
+    /// config, e.g. during an incident, without the next sync from a flag
+    /// service immediately reverting the override. Whether a config is frozen
+    /// is shared by all clones of this set and is not durable.
+    ///
+    /// Returns false if no config with `name` is registered to this set.
+    pub fn freeze(&self, name: &str) -> bool {
+        let Some(config) = self.configs.get(name) else {
+            return false;
+        };
+        let mut frozen = self.frozen.lock().expect("lock poisoned");
+        frozen.insert(config.name);
+        true
+    }
+
+    /// Unfreezes the config with `name`, returning whether it was frozen.
+    ///
+    /// The config keeps its current value until the next update to it.
+    pub fn unfreeze(&self, name: &str) -> bool {
+        let mut frozen = self.frozen.lock().expect("lock poisoned");
+        frozen.remove(name)
+    }
+
+    /// Returns the names of the frozen configs of this set.
+    ///
+    /// See [ConfigSet::freeze].
+    pub fn frozen(&self) -> BTreeSet<&'static str> {
+        let frozen = self.frozen.lock().expect("lock poisoned");
+        frozen.clone()
+    }
+
+    /// Returns the names of the configs of this set whose values were never
+    /// read.
+    ///
+    /// A config counts as read once its value is retrieved through its
+    /// [Config], with [Config::get] or by creating a handle with
+    /// [Config::handle] or [Config::cached]. Type-erased reads through
+    /// [ConfigEntry::val], which tooling uses to list every config, don't
+    /// count. This allows tests to flag dead configs that linger after a
+    /// rollout is complete. Reads are shared by all clones of this set and
+    /// are only tracked in debug builds.
+    #[cfg(debug_assertions)]
+    pub fn unread_configs(&self) -> BTreeSet<&'static str> {
+        self.entries()
+            .filter(|entry| !entry.read.load(SeqCst))
+            .map(|entry| entry.name)
+            .collect()
+    }
+
     /// Returns the configs currently registered to this set.
     pub fn entries(&self) -> impl Iterator<Item = &ConfigEntry> {
         self.configs.values()
@@ -236,15 +608,215 @@ impl ConfigSet {
     pub fn entry(&self, name: &str) -> Option<&ConfigEntry> {
         self.configs.get(name)
     }
+
+    /// Returns a machine-readable description of the configs registered to
+    /// this set, for external tooling that keeps flag definitions in sync with
+    /// the code.
+    ///
+    /// The schema is a JSON object with a `version` (see
+    /// [CONFIG_SCHEMA_VERSION]) and a `configs` array, sorted by name. Each
+    /// config has a `name`, `type`, `default`, `description`, `dangerous`, and
+    /// `bounds`, which is either null or an object with the `min` and `max` of
+    /// a [Duration] config. Values are rendered as by [ConfigVal::to_json].
+    /// Only compiled-in metadata is included, never the current values.
+    pub fn schema(&self) -> serde_json::Value {
+        let configs: Vec<_> = self
+            .entries()
+            .map(|entry| {
+                let bounds = entry.duration_bounds().map(|bounds| {
+                    serde_json::json!({
+                        "min": ConfigVal::Duration(bounds.min).to_json(),
+                        "max": ConfigVal::Duration(bounds.max).to_json(),
+                    })
+                });
+                serde_json::json!({
+                    "name": entry.name(),
+                    "type": entry.value_type().name(),
+                    "default": entry.default().to_json(),
+                    "description": entry.desc(),
+                    "dangerous": entry.dangerous(),
+                    "bounds": bounds,
+                })
+            })
+            .collect();
+        serde_json::json!({
+            "version": CONFIG_SCHEMA_VERSION,
+            "configs": configs,
+        })
+    }
+
+    /// Registers a callback to be invoked whenever configs whose names start
+    /// with `prefix` change.
+    ///
+    /// The callback is invoked at most once per [ConfigUpdates::apply], after
+    /// all of the updates have been applied, with the set and the names of the
+    /// changed configs under `prefix`. This allows subsystems to rebuild state
+    /// derived from several configs once per batch of updates, instead of on
+    /// every get.
+    ///
+    /// Callbacks are shared by all clones of this set. They must not register
+    /// further callbacks or apply updates to the set.
+    pub fn register_update_callback<F>(&self, prefix: &str, callback: F)
+    where
+        F: Fn(&ConfigSet, &BTreeSet<&'static str>) + Send + Sync + 'static,
+    {
+        let mut callbacks = self.callbacks.lock().expect("lock poisoned");
+        callbacks.push((prefix.to_owned(), Arc::new(callback)));
+    }
+
+    /// Registers a listener to be invoked with every change to the value of a
+    /// config in this set.
+    ///
+    /// Every change is also logged as an event with the `dyncfg` target. The
+    /// listener is for recording changes elsewhere, e.g., in an audit log. It
+    /// is invoked once per changed config, before the update callbacks, and
+    /// is subject to the same restrictions as the update callbacks (see
+    /// [ConfigSet::register_update_callback]).
+    pub fn register_change_listener<F>(&self, listener: F)
+    where
+        F: Fn(&ConfigChange) + Send + Sync + 'static,
+    {
+        let mut listeners = self.change_listeners.lock().expect("lock poisoned");
+        listeners.push(Arc::new(listener));
+    }
+
+    /// Returns the updates scheduled with [ConfigUpdates::schedule] that have
+    /// not yet been applied, in the order they will be applied.
+    #[cfg(feature = "proto")]
+    pub fn scheduled(&self) -> Vec<ScheduledConfigUpdates> {
+        let scheduled = self.scheduled.lock().expect("lock poisoned");
+        scheduled.clone()
+    }
+
+    /// Applies the scheduled updates whose activation time is at or before
+    /// `now`, returning them in the order they were applied.
+    ///
+    /// Nothing applies scheduled updates on its own; this is expected to be
+    /// called periodically, e.g. from the same tick that syncs the set's
+    /// values from a flag service.
+    #[cfg(feature = "proto")]
+    pub fn apply_scheduled(&self, now: SystemTime) -> Vec<ConfigUpdates> {
+        let due: Vec<_> = {
+            let mut scheduled = self.scheduled.lock().expect("lock poisoned");
+            let (due, pending) = std::mem::take(&mut *scheduled)
+                .into_iter()
+                .partition(|s| s.activate_at <= now);
+            *scheduled = pending;
+            due
+        };
+        due.into_iter()
+            .map(|ScheduledConfigUpdates { updates, .. }| {
+                updates.apply(self);
+                updates
+            })
+            .collect()
+    }
+
+    /// Returns the updates to dangerous configs that await confirmation,
+    /// keyed by config name.
+    ///
+    /// See [ConfigUpdates::confirm].
+    pub fn staged(&self) -> BTreeMap<&'static str, ConfigVal> {
+        let staged = self.staged.lock().expect("lock poisoned");
+        staged.clone()
+    }
+
+    /// Returns a digest of the current value of every config in this set.
+    ///
+    /// See [ConfigUpdates::delta].
+    #[cfg(feature = "proto")]
+    pub fn digest(&self) -> ConfigDigest {
+        let digests = self
+            .configs
+            .values()
+            .map(|config| {
+                let val = ProtoConfigVal {
+                    val: config.val().into_proto(),
+                };
+                (config.name.to_owned(), val_digest(&val))
+            })
+            .collect();
+        ConfigDigest { digests }
+    }
+
+    /// Discards all scheduled updates that have not yet been applied.
+    #[cfg(feature = "proto")]
+    pub fn clear_scheduled(&self) {
+        let mut scheduled = self.scheduled.lock().expect("lock poisoned");
+        scheduled.clear();
+    }
+
+    /// Installs this set as the process-wide global set and returns it.
+    ///
+    /// This is an explicit opt-in for small binaries, like `persistcli`, that
+    /// don't want to plumb a set everywhere. Libraries should continue to
+    /// accept a set from their caller, as the global set doesn't play well
+    /// with tests.
+    ///
+    /// Panics if a global set has already been installed.
+    pub fn install_global(self) -> &'static ConfigSet {
+        if GLOBAL.set(self).is_err() {
+            panic!("global ConfigSet installed twice");
+        }
+        Self::global()
+    }
+
+    /// Returns the global set installed by [ConfigSet::install_global].
+    ///
+    /// Panics if no global set has been installed.
+    pub fn global() -> &'static ConfigSet {
+        Self::try_global().expect("global ConfigSet should be installed")
+    }
+
+    /// Returns the global set installed by [ConfigSet::install_global], if
+    /// any.
+    pub fn try_global() -> Option<&'static ConfigSet> {
+        GLOBAL.get()
+    }
+}
+
+/// A batch of [ConfigUpdates] scheduled to be applied to a [ConfigSet] at a
+/// later time.
+#[cfg(feature = "proto")]
+#[derive(Clone, Debug, PartialEq)]
+pub struct ScheduledConfigUpdates {
+    /// The (wall clock) time at or after which to apply the updates.
+    pub activate_at: SystemTime,
+    /// The updates to apply.
+    pub updates: ConfigUpdates,
 }
 
+/// The version of the format returned by [ConfigSet::schema].
+///
+/// Bumped whenever a field is removed or changes meaning.
+pub const CONFIG_SCHEMA_VERSION: u64 = 1;
+
 /// An entry for a config in a [ConfigSet].
 #[derive(Clone, Debug)]
 pub struct ConfigEntry {
     name: &'static str,
     desc: &'static str,
     default: ConfigVal,
+    dangerous: bool,
+    duration_bounds: Option<DurationBounds>,
     val: ConfigValAtomic,
+    history: Arc<Mutex<VecDeque<ConfigHistoryEntry>>>,
+    /// Whether the value was ever read through the [Config]. See
+    /// [ConfigSet::unread_configs].
+    #[cfg(debug_assertions)]
+    read: Arc<AtomicBool>,
+}
+
+/// The number of applied values retained in the history of a [ConfigEntry].
+pub const CONFIG_HISTORY_LEN: usize = 16;
+
+/// A value that was applied to a [ConfigEntry].
+#[derive(Clone, Debug, PartialEq)]
+pub struct ConfigHistoryEntry {
+    /// The (wall clock) time at which the value was applied.
+    pub applied_at: SystemTime,
+    /// The value that was applied.
+    pub val: ConfigVal,
 }
 
 impl ConfigEntry {
@@ -265,10 +837,68 @@ impl ConfigEntry {
         &self.default
     }
 
+    /// Whether this config is dangerous. See [Config::new_dangerous].
+    pub fn dangerous(&self) -> bool {
+        self.dangerous
+    }
+
+    /// The type of the values of this config.
+    pub fn value_type(&self) -> ConfigTypeKind {
+        self.default.kind()
+    }
+
+    /// The bounds on the values of this config, if it's a [Duration] config
+    /// with bounds. See [Config::with_bounds].
+    pub fn duration_bounds(&self) -> Option<DurationBounds> {
+        self.duration_bounds
+    }
+
     /// The value of this config in the set.
     pub fn val(&self) -> ConfigVal {
         self.val.load()
     }
+
+    /// Parse a string value for this config.
+    ///
+    /// This is the type-erased version of [Config::parse_val].
+    pub fn parse_val(&self, val: &str) -> Result<ConfigVal, String> {
+        self.value_type().parse(val)
+    }
+
+    /// The most recently applied values of this config in the set, oldest
+    /// first.
+    ///
+    /// Only updates that changed the value are recorded, and at most
+    /// [CONFIG_HISTORY_LEN] of them are retained. If the history is empty,
+    /// the config still has its default value.
+    pub fn history(&self) -> Vec<ConfigHistoryEntry> {
+        let history = self.history.lock().expect("lock poisoned");
+        history.iter().cloned().collect()
+    }
+
+    /// Stores `val` as the new value of this config, recording it in the
+    /// history if it differs from the current value.
+    ///
+    /// Returns the previous value if the value changed.
+    #[cfg(feature = "proto")]
+    fn apply(&self, val: ConfigVal) -> Option<ConfigVal> {
+        // Hold the lock across the store so concurrent updates are recorded
+        // in the order they were applied.
+        let mut history = self.history.lock().expect("lock poisoned");
+        let prev = self.val.load();
+        if prev == val {
+            return None;
+        }
+        self.val.store(val.clone());
+        if history.len() == CONFIG_HISTORY_LEN {
+            history.pop_front();
+        }
+        history.push_back(ConfigHistoryEntry {
+            applied_at: SystemTime::now(),
+            val,
+        });
+        Some(prev)
+    }
 }
 
 /// A handle to a configuration value in a [`ConfigSet`].
@@ -290,14 +920,223 @@ impl<T: ConfigType> ConfigValHandle<T> {
     }
 }
 
+/// Restores the value of a config overridden with [Config::override_for_test]
+/// when dropped.
+#[cfg(feature = "proto")]
+#[must_use = "the override is reverted when the guard is dropped"]
+#[derive(Debug)]
+pub struct ConfigScopeGuard {
+    set: ConfigSet,
+    name: &'static str,
+    prev: ConfigVal,
+}
+
+/// The source of the changes made by [Config::override_for_test].
+#[cfg(feature = "proto")]
+const TEST_OVERRIDE_SOURCE: &str = "test_override";
+
+#[cfg(feature = "proto")]
+impl Drop for ConfigScopeGuard {
+    fn drop(&mut self) {
+        let entry = self.set.entry(self.name).expect("config is registered");
+        let valid = vec![(entry, self.prev.clone())];
+        ConfigUpdates::apply_valid(&self.set, valid, TEST_OVERRIDE_SOURCE);
+    }
+}
+
+/// A handle to a configuration value in a [`ConfigSet`] that caches the value
+/// for a bounded amount of time.
+///
+/// Reading a [ConfigValHandle] of an integer, float, bool, or byte size config
+/// is a single relaxed atomic load, which is as cheap as reading a plain
+/// value, so there's nothing to cache. String, duration, optional usize, and
+/// JSON configs are instead shared behind a lock, which every
+/// [ConfigValHandle::get] acquires, and the value is cloned out from under it.
+/// A cached handle only takes the lock once the cached value is older than
+/// `ttl`, so it keeps call sites that read such a config in a loop from
+/// contending on the lock with each other and with config updates.
+///
+/// The caller passes the current time to [CachedConfigValHandle::get], which
+/// lets loops that already track a coarse `now` avoid reading the clock on
+/// every call, and keeps the handle usable on targets without a clock. Each
+/// handle has its own cache and is not `Sync`, so the intended use is one
+/// handle per loop or per thread.
+#[derive(Debug)]
+pub struct CachedConfigValHandle<T> {
+    handle: ConfigValHandle<T>,
+    ttl: Duration,
+    cached: RefCell<Option<(Instant, T)>>,
+}
+
+impl<T: ConfigType> CachedConfigValHandle<T> {
+    /// Returns the value of this config within the set associated with the
+    /// handle as of at most `ttl` before `now`.
+    pub fn get(&self, now: Instant) -> T {
+        let mut cached = self.cached.borrow_mut();
+        match &*cached {
+            Some((loaded_at, val)) if now.saturating_duration_since(*loaded_at) < self.ttl => {
+                val.clone()
+            }
+            _ => {
+                let val = self.handle.get();
+                *cached = Some((now, val.clone()));
+                val
+            }
+        }
+    }
+
+    /// Drops the cached value, so the next [CachedConfigValHandle::get]
+    /// returns the latest value.
+    pub fn invalidate(&self) {
+        *self.cached.borrow_mut() = None;
+    }
+}
+
+/// Declares a struct that holds a snapshot of the values of a group of related
+/// configs.
+///
+/// Each field names its type and the [Config] it is loaded from. The struct
+/// gets a `load(&ConfigSet) -> Self` method that reads every config in the
+/// group once, so a hot code path can take a coherent snapshot once per
+/// invocation instead of looking up each config by name wherever it is used,
+/// and an `add_configs(ConfigSet) -> ConfigSet` method that registers all of
+/// the group's configs to a set.
+///
+/// ```
+/// # use std::time::Duration;
+/// # use mz_dyncfg::{config_snapshot, Config, ConfigSet};
+/// const TARGET_BATCH_BYTES: Config<usize> =
+///     Config::new("target_batch_bytes", 1024, "target size of a batch");
+/// const COMPACTION_INTERVAL: Config<Duration> =
+///     Config::new("compaction_interval", Duration::from_secs(1), "time between compactions");
+///
+/// config_snapshot! {
+///     /// Knobs for storage.
+///     pub struct StorageKnobs {
+///         pub target_batch_bytes: usize = TARGET_BATCH_BYTES,
+///         pub compaction_interval: Duration = COMPACTION_INTERVAL,
+///     }
+/// }
+///
+/// let cfg = StorageKnobs::add_configs(ConfigSet::default());
+/// let knobs = StorageKnobs::load(&cfg);
+/// assert_eq!(knobs.target_batch_bytes, 1024);
+/// ```
+#[macro_export]
+macro_rules! config_snapshot {
+    (
+        $(#[$meta:meta])*
+        $vis:vis struct $name:ident {
+            $(
+                $(#[$field_meta:meta])*
+                $field_vis:vis $field:ident: $ty:ty = $config:path
+            ),* $(,)?
+        }
+    ) => {
+        $(#[$meta])*
+        #[derive(Clone, Debug)]
+        $vis struct $name {
+            $(
+                $(#[$field_meta])*
+                $field_vis $field: $ty,
+            )*
+        }
+
+        impl $name {
+            /// Loads the current values of the configs in this group from
+            /// `set`.
+            ///
+            /// Panics if any of the configs was not registered to the set.
+            $vis fn load(set: &$crate::ConfigSet) -> Self {
+                $name {
+                    $($field: $crate::Config::get(&$config, set),)*
+                }
+            }
+
+            /// Adds the configs in this group to `set`.
+            $vis fn add_configs(set: $crate::ConfigSet) -> $crate::ConfigSet {
+                set$(.add(&$config))*
+            }
+        }
+    };
+}
+
+/// Makes a small enum usable as the type of a [Config].
+///
+/// The enum is stored as a [u8], so its values are read without taking a
+/// lock, unlike [String] configs. This allows mode switches in inner loops to
+/// be dynamically tunable. The enum must be `Clone` and convert from and into
+/// a `u8`. Values are parsed as numbers and only checked to fit into a `u8`,
+/// so the conversion from `u8` must be total, e.g., by mapping unknown values
+/// to the default variant.
+///
+/// ```
+/// # use mz_dyncfg::{impl_config_enum, Config, ConfigSet};
+/// #[derive(Clone, Copy, Debug, PartialEq)]
+/// enum JoinStrategy {
+///     Linear,
+///     Delta,
+/// }
+///
+/// impl From<u8> for JoinStrategy {
+///     fn from(val: u8) -> Self {
+///         match val {
+///             1 => JoinStrategy::Delta,
+///             _ => JoinStrategy::Linear,
+///         }
+///     }
+/// }
+///
+/// impl From<JoinStrategy> for u8 {
+///     fn from(val: JoinStrategy) -> u8 {
+///         match val {
+///             JoinStrategy::Linear => 0,
+///             JoinStrategy::Delta => 1,
+///         }
+///     }
+/// }
+///
+/// impl_config_enum!(JoinStrategy);
+///
+/// const JOIN_STRATEGY: Config<JoinStrategy> =
+///     Config::new("join_strategy", JoinStrategy::Linear, "the join strategy");
+///
+/// let cfg = ConfigSet::default().add(&JOIN_STRATEGY);
+/// assert_eq!(JOIN_STRATEGY.get(&cfg), JoinStrategy::Linear);
+/// ```
+#[macro_export]
+macro_rules! impl_config_enum {
+    ($ty:ty) => {
+        impl ::std::convert::From<$ty> for $crate::ConfigVal {
+            fn from(val: $ty) -> $crate::ConfigVal {
+                $crate::ConfigVal::U8(::std::convert::Into::<u8>::into(val))
+            }
+        }
+
+        impl $crate::ConfigType for $ty {
+            fn from_val(val: $crate::ConfigVal) -> Self {
+                <$ty>::from(<u8 as $crate::ConfigType>::from_val(val))
+            }
+
+            fn parse(s: &str) -> ::std::result::Result<Self, ::std::string::String> {
+                <u8 as $crate::ConfigType>::parse(s).map(<$ty>::from)
+            }
+        }
+    };
+}
+
 /// A type-erased configuration value for when set of different types are stored
 /// in a collection.
 #[derive(Clone, Debug, PartialEq)]
 pub enum ConfigVal {
     /// A `bool` value.
     Bool(bool),
+    /// A `u8` value, e.g., of a small enum. See [impl_config_enum].
+    U8(u8),
     /// A `u32` value.
     U32(u32),
+    /// An `i64` value.
+    I64(i64),
     /// A `usize` value.
     Usize(usize),
     /// An `Option<usize>` value.
@@ -308,10 +1147,133 @@ pub enum ConfigVal {
     String(String),
     /// A `Duration` value.
     Duration(Duration),
+    /// A `ByteSize` value.
+    Bytes(ByteSize),
     /// A JSON value.
     Json(serde_json::Value),
 }
 
+impl ConfigVal {
+    /// The type of this value.
+    pub fn kind(&self) -> ConfigTypeKind {
+        match self {
+            ConfigVal::Bool(_) => ConfigTypeKind::Bool,
+            ConfigVal::U8(_) => ConfigTypeKind::U8,
+            ConfigVal::U32(_) => ConfigTypeKind::U32,
+            ConfigVal::I64(_) => ConfigTypeKind::I64,
+            ConfigVal::Usize(_) => ConfigTypeKind::Usize,
+            ConfigVal::OptUsize(_) => ConfigTypeKind::OptUsize,
+            ConfigVal::F64(_) => ConfigTypeKind::F64,
+            ConfigVal::String(_) => ConfigTypeKind::String,
+            ConfigVal::Duration(_) => ConfigTypeKind::Duration,
+            ConfigVal::Bytes(_) => ConfigTypeKind::Bytes,
+            ConfigVal::Json(_) => ConfigTypeKind::Json,
+        }
+    }
+
+    /// A human-readable name for the type of this value.
+    fn type_name(&self) -> &'static str {
+        self.kind().name()
+    }
+
+    /// A JSON representation of this value.
+    ///
+    /// Numbers and strings are rendered natively, an empty `Option<usize>` as
+    /// null, [Duration]s in the humantime format accepted by
+    /// [ConfigEntry::parse_val], and [ByteSize]s as a number of bytes.
+    pub fn to_json(&self) -> serde_json::Value {
+        match self {
+            ConfigVal::Bool(x) => serde_json::Value::from(*x),
+            ConfigVal::U8(x) => serde_json::Value::from(*x),
+            ConfigVal::U32(x) => serde_json::Value::from(*x),
+            ConfigVal::I64(x) => serde_json::Value::from(*x),
+            ConfigVal::Usize(x) => serde_json::Value::from(*x),
+            ConfigVal::OptUsize(x) => serde_json::Value::from(*x),
+            ConfigVal::F64(x) => serde_json::Value::from(*x),
+            ConfigVal::String(x) => serde_json::Value::from(x.as_str()),
+            ConfigVal::Duration(x) => {
+                serde_json::Value::from(humantime::format_duration(*x).to_string())
+            }
+            ConfigVal::Bytes(x) => serde_json::Value::from(x.as_u64()),
+            ConfigVal::Json(x) => x.clone(),
+        }
+    }
+}
+
+/// The type of a [ConfigVal], for tooling that renders and parses the values
+/// of arbitrary configs. See [ConfigEntry::value_type].
+#[derive(Clone, Copy, Debug, PartialEq, Eq, PartialOrd, Ord, Hash)]
+pub enum ConfigTypeKind {
+    /// The type of [ConfigVal::Bool] values.
+    Bool,
+    /// The type of [ConfigVal::U8] values.
+    U8,
+    /// The type of [ConfigVal::U32] values.
+    U32,
+    /// The type of [ConfigVal::I64] values.
+    I64,
+    /// The type of [ConfigVal::Usize] values.
+    Usize,
+    /// The type of [ConfigVal::OptUsize] values.
+    OptUsize,
+    /// The type of [ConfigVal::F64] values.
+    F64,
+    /// The type of [ConfigVal::String] values.
+    String,
+    /// The type of [ConfigVal::Duration] values.
+    Duration,
+    /// The type of [ConfigVal::Bytes] values.
+    Bytes,
+    /// The type of [ConfigVal::Json] values.
+    Json,
+}
+
+impl ConfigTypeKind {
+    /// A human-readable name for this type, as used in [ConfigSet::schema].
+    pub fn name(&self) -> &'static str {
+        match self {
+            ConfigTypeKind::Bool => "bool",
+            ConfigTypeKind::U8 => "u8",
+            ConfigTypeKind::U32 => "u32",
+            ConfigTypeKind::I64 => "i64",
+            ConfigTypeKind::Usize => "usize",
+            ConfigTypeKind::OptUsize => "Option<usize>",
+            ConfigTypeKind::F64 => "f64",
+            ConfigTypeKind::String => "String",
+            ConfigTypeKind::Duration => "Duration",
+            ConfigTypeKind::Bytes => "ByteSize",
+            ConfigTypeKind::Json => "JSON",
+        }
+    }
+
+    /// Parses a string value of this type.
+    ///
+    /// Accepts the same strings as [Config::parse_val] for configs of this
+    /// type.
+    pub fn parse(&self, val: &str) -> Result<ConfigVal, String> {
+        let val = match self {
+            ConfigTypeKind::Bool => <bool as ConfigType>::parse(val)?.into(),
+            ConfigTypeKind::U8 => <u8 as ConfigType>::parse(val)?.into(),
+            ConfigTypeKind::U32 => <u32 as ConfigType>::parse(val)?.into(),
+            ConfigTypeKind::I64 => <i64 as ConfigType>::parse(val)?.into(),
+            ConfigTypeKind::Usize => <usize as ConfigType>::parse(val)?.into(),
+            ConfigTypeKind::OptUsize => <Option<usize> as ConfigType>::parse(val)?.into(),
+            ConfigTypeKind::F64 => <f64 as ConfigType>::parse(val)?.into(),
+            ConfigTypeKind::String => <String as ConfigType>::parse(val)?.into(),
+            ConfigTypeKind::Duration => <Duration as ConfigType>::parse(val)?.into(),
+            ConfigTypeKind::Bytes => <ByteSize as ConfigType>::parse(val)?.into(),
+            ConfigTypeKind::Json => <serde_json::Value as ConfigType>::parse(val)?.into(),
+        };
+        Ok(val)
+    }
+}
+
+impl std::fmt::Display for ConfigTypeKind {
+    fn fmt(&self, f: &mut std::fmt::Formatter<'_>) -> std::fmt::Result {
+        f.write_str(self.name())
+    }
+}
+
 /// An atomic version of [`ConfigVal`] to allow configuration values to be
 /// shared between configuration writers and readers.
 ///
@@ -322,13 +1284,17 @@ pub enum ConfigVal {
 #[derive(Clone, Debug)]
 enum ConfigValAtomic {
     Bool(Arc<AtomicBool>),
+    U8(Arc<AtomicU8>),
     U32(Arc<AtomicU32>),
+    I64(Arc<AtomicI64>),
     Usize(Arc<AtomicUsize>),
     OptUsize(Arc<RwLock<Option<usize>>>),
     // Shared via to_bits/from_bits so we can use the atomic instead of Mutex.
     F64(Arc<AtomicU64>),
     String(Arc<RwLock<String>>),
     Duration(Arc<RwLock<Duration>>),
+    // Shared via the number of bytes so we can use the atomic instead of Mutex.
+    Bytes(Arc<AtomicU64>),
     Json(Arc<RwLock<serde_json::Value>>),
 }
 
@@ -336,58 +1302,72 @@ impl From<ConfigVal> for ConfigValAtomic {
     fn from(val: ConfigVal) -> ConfigValAtomic {
         match val {
             ConfigVal::Bool(x) => ConfigValAtomic::Bool(Arc::new(AtomicBool::new(x))),
+            ConfigVal::U8(x) => ConfigValAtomic::U8(Arc::new(AtomicU8::new(x))),
             ConfigVal::U32(x) => ConfigValAtomic::U32(Arc::new(AtomicU32::new(x))),
+            ConfigVal::I64(x) => ConfigValAtomic::I64(Arc::new(AtomicI64::new(x))),
             ConfigVal::Usize(x) => ConfigValAtomic::Usize(Arc::new(AtomicUsize::new(x))),
             ConfigVal::OptUsize(x) => ConfigValAtomic::OptUsize(Arc::new(RwLock::new(x))),
             ConfigVal::F64(x) => ConfigValAtomic::F64(Arc::new(AtomicU64::new(x.to_bits()))),
             ConfigVal::String(x) => ConfigValAtomic::String(Arc::new(RwLock::new(x))),
             ConfigVal::Duration(x) => ConfigValAtomic::Duration(Arc::new(RwLock::new(x))),
+            ConfigVal::Bytes(x) => ConfigValAtomic::Bytes(Arc::new(AtomicU64::new(x.as_u64()))),
             ConfigVal::Json(x) => ConfigValAtomic::Json(Arc::new(RwLock::new(x))),
         }
     }
 }
 
 impl ConfigValAtomic {
+    // Each config is independent of all other memory, so the atomics only
+    // need to be atomic, not ordered with respect to anything else.
     fn load(&self) -> ConfigVal {
         match self {
-            ConfigValAtomic::Bool(x) => ConfigVal::Bool(x.load(SeqCst)),
-            ConfigValAtomic::U32(x) => ConfigVal::U32(x.load(SeqCst)),
-            ConfigValAtomic::Usize(x) => ConfigVal::Usize(x.load(SeqCst)),
+            ConfigValAtomic::Bool(x) => ConfigVal::Bool(x.load(Relaxed)),
+            ConfigValAtomic::U8(x) => ConfigVal::U8(x.load(Relaxed)),
+            ConfigValAtomic::U32(x) => ConfigVal::U32(x.load(Relaxed)),
+            ConfigValAtomic::I64(x) => ConfigVal::I64(x.load(Relaxed)),
+            ConfigValAtomic::Usize(x) => ConfigVal::Usize(x.load(Relaxed)),
             ConfigValAtomic::OptUsize(x) => ConfigVal::OptUsize(*x.read().expect("lock poisoned")),
-            ConfigValAtomic::F64(x) => ConfigVal::F64(f64::from_bits(x.load(SeqCst))),
+            ConfigValAtomic::F64(x) => ConfigVal::F64(f64::from_bits(x.load(Relaxed))),
             ConfigValAtomic::String(x) => {
                 ConfigVal::String(x.read().expect("lock poisoned").clone())
             }
             ConfigValAtomic::Duration(x) => ConfigVal::Duration(*x.read().expect("lock poisoned")),
+            ConfigValAtomic::Bytes(x) => ConfigVal::Bytes(ByteSize(x.load(Relaxed))),
             ConfigValAtomic::Json(x) => ConfigVal::Json(x.read().expect("lock poisoned").clone()),
         }
     }
 
     fn store(&self, val: ConfigVal) {
         match (self, val) {
-            (ConfigValAtomic::Bool(x), ConfigVal::Bool(val)) => x.store(val, SeqCst),
-            (ConfigValAtomic::U32(x), ConfigVal::U32(val)) => x.store(val, SeqCst),
-            (ConfigValAtomic::Usize(x), ConfigVal::Usize(val)) => x.store(val, SeqCst),
+            (ConfigValAtomic::Bool(x), ConfigVal::Bool(val)) => x.store(val, Relaxed),
+            (ConfigValAtomic::U8(x), ConfigVal::U8(val)) => x.store(val, Relaxed),
+            (ConfigValAtomic::U32(x), ConfigVal::U32(val)) => x.store(val, Relaxed),
+            (ConfigValAtomic::I64(x), ConfigVal::I64(val)) => x.store(val, Relaxed),
+            (ConfigValAtomic::Usize(x), ConfigVal::Usize(val)) => x.store(val, Relaxed),
             (ConfigValAtomic::OptUsize(x), ConfigVal::OptUsize(val)) => {
                 *x.write().expect("lock poisoned") = val
             }
-            (ConfigValAtomic::F64(x), ConfigVal::F64(val)) => x.store(val.to_bits(), SeqCst),
+            (ConfigValAtomic::F64(x), ConfigVal::F64(val)) => x.store(val.to_bits(), Relaxed),
             (ConfigValAtomic::String(x), ConfigVal::String(val)) => {
                 *x.write().expect("lock poisoned") = val
             }
             (ConfigValAtomic::Duration(x), ConfigVal::Duration(val)) => {
                 *x.write().expect("lock poisoned") = val
             }
+            (ConfigValAtomic::Bytes(x), ConfigVal::Bytes(val)) => x.store(val.as_u64(), Relaxed),
             (ConfigValAtomic::Json(x), ConfigVal::Json(val)) => {
                 *x.write().expect("lock poisoned") = val
             }
             (ConfigValAtomic::Bool(_), val)
+            | (ConfigValAtomic::U8(_), val)
             | (ConfigValAtomic::U32(_), val)
+            | (ConfigValAtomic::I64(_), val)
             | (ConfigValAtomic::Usize(_), val)
             | (ConfigValAtomic::OptUsize(_), val)
             | (ConfigValAtomic::F64(_), val)
             | (ConfigValAtomic::String(_), val)
             | (ConfigValAtomic::Duration(_), val)
+            | (ConfigValAtomic::Bytes(_), val)
             | (ConfigValAtomic::Json(_), val) => {
                 panic!("attempted to store {val:?} value in {self:?} parameter")
             }
@@ -395,6 +1375,7 @@ impl ConfigValAtomic {
     }
 }
 
+#[cfg(feature = "proto")]
 impl ConfigUpdates {
     /// Adds an update for the given config and value.
     ///
@@ -424,49 +1405,780 @@ impl ConfigUpdates {
         );
     }
 
-    /// Adds the entries in `other` to `self`, with `other` taking precedence.
-    pub fn extend(&mut self, mut other: Self) {
-        self.updates.append(&mut other.updates)
+    /// Records where these updates originated, e.g. `launchdarkly`.
+    ///
+    /// The source is included in the log event and passed to the change
+    /// listeners (see [ConfigSet::register_change_listener]) of every config
+    /// change made by these updates.
+    pub fn with_source(mut self, source: impl Into<String>) -> Self {
+        self.source = source.into();
+        self
     }
 
-    /// Applies these config updates to the given [ConfigSet].
+    /// Adds a confirmation for the given dangerous config.
     ///
-    /// This doesn't need to be the same set that the value updates were added
-    /// from. In fact, the primary use of this is propagating config updates
-    /// across processes.
+    /// When these updates are applied, an update to the config in the same
+    /// updates is applied as usual. Otherwise, the update most recently staged
+    /// for the config in the set, if any, is applied. Updates to a dangerous
+    /// config without a confirmation are staged in the set instead of applied,
+    /// so a change to such a config needs either a single push carrying both
+    /// the update and its confirmation or a second, confirming push.
+    pub fn confirm<T: ConfigDefault>(&mut self, config: &Config<T>) {
+        self.confirm_dynamic(config.name);
+    }
+
+    /// Adds a confirmation for the dangerous config with the given name.
     ///
-    /// The value updates for any configs unknown by the given set are skipped.
-    /// Ditto for config type mismatches. However, this is unexpected usage at
-    /// present and so is logged to Sentry.
-    pub fn apply(&self, set: &ConfigSet) {
-        for (name, ProtoConfigVal { val }) in self.updates.iter() {
-            let Some(config) = set.configs.get(name) else {
-                error!("config update {} {:?} not known set: {:?}", name, val, set);
-                continue;
-            };
-            let val = match (val.clone()).into_rust() {
-                Ok(x) => x,
+    /// See [ConfigUpdates::confirm].
+    pub fn confirm_dynamic(&mut self, name: &str) {
+        self.add_dynamic(&confirmation_name(name), ConfigVal::Bool(true));
+    }
+
+    /// Parses updates for the configs of `set` from `contents`.
+    ///
+    /// Each non-empty line that doesn't start with `#` must be of the form
+    /// `name=value`, where `value` is parsed by [ConfigValueParser]. Later
+    /// lines take precedence over earlier ones for the same config. If any
+    /// line can't be parsed, the returned error describes every such line.
+    pub fn parse_lines(set: &ConfigSet, contents: &str) -> Result<Self, ConfigUpdatesError> {
+        let mut updates = ConfigUpdates::default();
+        let mut invalid = BTreeMap::new();
+        for line in contents.lines() {
+            let line = line.trim();
+            if line.is_empty() || line.starts_with('#') {
+                continue;
+            }
+            let Some((name, val)) = line.split_once('=') else {
+                let err = InvalidConfigUpdate::Decode("expected name=value".into());
+                invalid.insert(line.to_owned(), err);
+                continue;
+            };
+            let name = name.trim();
+            let Some(config) = set.entry(name) else {
+                invalid.insert(name.to_owned(), InvalidConfigUpdate::Unknown);
+                continue;
+            };
+            match ConfigValueParser.parse(config, val) {
+                Ok(val) => updates.add_dynamic(name, val),
+                Err(err) => {
+                    let err = InvalidConfigUpdate::Decode(err.to_string());
+                    invalid.insert(name.to_owned(), err);
+                }
+            }
+        }
+        if !invalid.is_empty() {
+            return Err(ConfigUpdatesError { invalid });
+        }
+        Ok(updates)
+    }
+
+    /// Loads updates for the configs of `set` from the file at `path`.
+    ///
+    /// See [ConfigUpdates::parse_lines] for the expected format.
+    pub fn load_file(set: &ConfigSet, path: &Path) -> Result<Self, String> {
+        let contents = std::fs::read_to_string(path)
+            .map_err(|err| format!("reading {}: {}", path.display(), err))?;
+        let updates = Self::parse_lines(set, &contents)
+            .map_err(|err| format!("{}: {}", path.display(), err))?;
+        Ok(updates.with_source(format!("file:{}", path.display())))
+    }
+
+    /// Reads updates for the configs of `set` from environment variables.
+    ///
+    /// The variable for a config is `prefix` followed by the config's name,
+    /// uppercased and with every character that isn't alphanumeric replaced
+    /// by `_`. For example, with a prefix of `MZ_DYNCFG_`, the value of the
+    /// `persist_blob_target_size` config is read from
+    /// `MZ_DYNCFG_PERSIST_BLOB_TARGET_SIZE`. Values are parsed by
+    /// [ConfigValueParser]. Configs without a variable are
+    /// left out of the returned updates. If any variable can't be parsed, the
+    /// returned error describes every such variable.
+    pub fn from_env(set: &ConfigSet, prefix: &str) -> Result<Self, ConfigUpdatesError> {
+        let mut updates = ConfigUpdates::default();
+        let mut invalid = BTreeMap::new();
+        for config in set.entries() {
+            let var = env_var_name(prefix, config.name);
+            let Ok(val) = std::env::var(&var) else {
+                continue;
+            };
+            match ConfigValueParser.parse(config, &val) {
+                Ok(val) => updates.add_dynamic(config.name, val),
+                Err(err) => {
+                    invalid.insert(var, InvalidConfigUpdate::Decode(err.to_string()));
+                }
+            }
+        }
+        if !invalid.is_empty() {
+            return Err(ConfigUpdatesError { invalid });
+        }
+        Ok(updates.with_source("env"))
+    }
+
+    /// Returns the updates in `self` that would change a value reported by
+    /// `digest`.
+    ///
+    /// An update is left out if `digest` reports the same value for its
+    /// config, so applying the returned updates to the set `digest` was taken
+    /// of has the same effect as applying all of `self`. A confirmation of a
+    /// dangerous config is left out along with its update, as on its own it
+    /// would confirm a staged update instead. Configs missing from `digest`
+    /// are always kept.
+    ///
+    /// Digests are hashes of the encoded values, which are only guaranteed to
+    /// be stable between processes of the same build. A digest that differs
+    /// spuriously only makes the update be sent anyway.
+    pub fn delta(&self, digest: &ConfigDigest) -> ConfigUpdates {
+        let unchanged: BTreeSet<&str> = self
+            .updates
+            .iter()
+            .filter(|(name, val)| digest.digests.get(*name) == Some(&val_digest(val)))
+            .map(|(name, _)| name.as_str())
+            .collect();
+        let updates = self
+            .updates
+            .iter()
+            .filter(|(name, _)| {
+                let config = name
+                    .strip_suffix(CONFIRMATION_SUFFIX)
+                    .unwrap_or(name.as_str());
+                !unchanged.contains(config)
+            })
+            .map(|(name, val)| (name.clone(), val.clone()))
+            .collect();
+        ConfigUpdates {
+            updates,
+            source: self.source.clone(),
+        }
+    }
+
+    /// Returns updates holding the current value of every config in `set`.
+    ///
+    /// See [check_consistency].
+    pub fn snapshot(set: &ConfigSet) -> Self {
+        let mut updates = ConfigUpdates::default();
+        for config in set.entries() {
+            updates.add_dynamic(config.name, config.val());
+        }
+        updates
+    }
+
+    /// Adds the entries in `other` to `self`, with `other` taking precedence.
+    ///
+    /// The source of `self` is kept, unless it is empty.
+    pub fn extend(&mut self, mut other: Self) {
+        self.updates.append(&mut other.updates);
+        if self.source.is_empty() {
+            self.source = other.source;
+        }
+    }
+
+    /// Applies these config updates to the given [ConfigSet].
+    ///
+    /// This doesn't need to be the same set that the value updates were added
+    /// from. In fact, the primary use of this is propagating config updates
+    /// across processes.
+    ///
+    /// The value updates for any configs unknown by the given set are skipped.
+    /// Ditto for config type mismatches. However, this is unexpected usage at
+    /// present and so is logged to Sentry. Use [ConfigUpdates::apply_strict]
+    /// to learn about skipped updates instead.
+    ///
+    /// Unconfirmed updates to dangerous configs are staged in the set, see
+    /// [ConfigUpdates::confirm]. Updates to frozen configs are skipped, see
+    /// [ConfigSet::freeze].
+    pub fn apply(&self, set: &ConfigSet) {
+        let ValidatedConfigUpdates {
+            valid,
+            unconfirmed,
+            frozen,
+            invalid,
+        } = self.validate(set);
+        Self::report_frozen(frozen);
+        for (name, invalid) in invalid {
+            match invalid {
+                InvalidConfigUpdate::Unknown => {
+                    error!("config update {} not known set: {:?}", name, set)
+                }
+                invalid => error!("config update {} skipped: {}", name, invalid),
+            }
+        }
+        if !unconfirmed.is_empty() {
+            let mut staged = set.staged.lock().expect("lock poisoned");
+            for (config, val) in unconfirmed {
+                warn!(
+                    "config update {} {:?} to dangerous config staged pending confirmation",
+                    config.name, val
+                );
+                staged.insert(config.name, val);
+            }
+        }
+        Self::apply_valid(set, valid, &self.source);
+    }
+
+    /// Schedules these config updates to be applied to the given [ConfigSet]
+    /// at `activate_at`.
+    ///
+    /// This allows a risky change to be scheduled for a low-traffic window.
+    /// The updates are held by the set, shared by all of its clones, and
+    /// applied by [ConfigSet::apply_scheduled] once `activate_at` has passed.
+    /// Updates scheduled for the same time are applied in the order they were
+    /// scheduled. Scheduled updates are not durable and do not survive a
+    /// restart.
+    pub fn schedule(self, set: &ConfigSet, activate_at: SystemTime) {
+        let mut scheduled = set.scheduled.lock().expect("lock poisoned");
+        let idx = scheduled.partition_point(|s| s.activate_at <= activate_at);
+        scheduled.insert(
+            idx,
+            ScheduledConfigUpdates {
+                activate_at,
+                updates: self,
+            },
+        );
+    }
+
+    /// Like [ConfigUpdates::apply], but rejects these updates if any of them
+    /// is invalid for the given [ConfigSet].
+    ///
+    /// An update is invalid if its config is unknown by the set, if its value
+    /// can't be decoded, if its value doesn't match the type of the config, or
+    /// if it is an unconfirmed update to a dangerous config. If any update is
+    /// invalid, none are applied or staged and the returned error describes
+    /// every invalid update, so the caller can log, alert on, or reject the
+    /// push that produced them. Updates to frozen configs are not invalid,
+    /// they are skipped like by [ConfigUpdates::apply].
+    pub fn apply_strict(&self, set: &ConfigSet) -> Result<(), ConfigUpdatesError> {
+        let ValidatedConfigUpdates {
+            valid,
+            unconfirmed,
+            frozen,
+            mut invalid,
+        } = self.validate(set);
+        for (config, _) in unconfirmed {
+            invalid.insert(config.name.to_owned(), InvalidConfigUpdate::Unconfirmed);
+        }
+        if !invalid.is_empty() {
+            return Err(ConfigUpdatesError { invalid });
+        }
+        Self::report_frozen(frozen);
+        Self::apply_valid(set, valid, &self.source);
+        Ok(())
+    }
+
+    /// Decodes these updates and matches them against the configs of `set`,
+    /// splitting them into those that can be applied and those that can't.
+    fn validate<'a>(&self, set: &'a ConfigSet) -> ValidatedConfigUpdates<'a> {
+        let mut valid = Vec::new();
+        let mut unconfirmed = Vec::new();
+        let mut invalid = BTreeMap::new();
+
+        // Collect the dangerous configs confirmed by these updates.
+        let mut confirmed = BTreeSet::new();
+        let mut frozen = Vec::new();
+        let frozen_names = set.frozen();
+        for name in self.updates.keys() {
+            let Some(confirmed_name) = name.strip_suffix(CONFIRMATION_SUFFIX) else {
+                continue;
+            };
+            match set.configs.get(confirmed_name) {
+                Some(config) if config.dangerous => {
+                    confirmed.insert(config.name);
+                }
+                _ => {
+                    invalid.insert(name.clone(), InvalidConfigUpdate::Unknown);
+                }
+            }
+        }
+
+        for (name, ProtoConfigVal { val }) in self.updates.iter() {
+            if name.ends_with(CONFIRMATION_SUFFIX) {
+                continue;
+            }
+            let Some(config) = set.configs.get(name) else {
+                invalid.insert(name.clone(), InvalidConfigUpdate::Unknown);
+                continue;
+            };
+            let val: ConfigVal = match (val.clone()).into_rust() {
+                Ok(x) => x,
                 Err(err) => {
-                    error!("config update {} decode error: {}", name, err);
+                    let err = InvalidConfigUpdate::Decode(err.to_string());
+                    invalid.insert(name.clone(), err);
                     continue;
                 }
             };
-            config.val.store(val);
+            if std::mem::discriminant(&val) != std::mem::discriminant(&config.default) {
+                let err = InvalidConfigUpdate::TypeMismatch {
+                    expected: config.default.type_name(),
+                    found: val.type_name(),
+                };
+                invalid.insert(name.clone(), err);
+                continue;
+            }
+            if let (Some(bounds), ConfigVal::Duration(val)) = (config.duration_bounds, &val) {
+                if !bounds.contains(*val) {
+                    let err = InvalidConfigUpdate::OutOfBounds {
+                        min: bounds.min,
+                        max: bounds.max,
+                    };
+                    invalid.insert(name.clone(), err);
+                    continue;
+                }
+            }
+            if frozen_names.contains(config.name) {
+                frozen.push((config, val));
+                continue;
+            }
+            // Re-sending the current value of a dangerous config needs no
+            // confirmation, as it doesn't change anything.
+            if config.dangerous && !confirmed.remove(config.name) && config.val() != val {
+                unconfirmed.push((config, val));
+                continue;
+            }
+            valid.push((config, val));
+        }
+
+        // Confirmations without an update in these updates confirm the staged
+        // update, if there is one.
+        let staged = set.staged.lock().expect("lock poisoned");
+        for name in confirmed {
+            if let Some(val) = staged.get(name) {
+                let config = &set.configs[name];
+                if frozen_names.contains(name) {
+                    frozen.push((config, val.clone()));
+                } else {
+                    valid.push((config, val.clone()));
+                }
+            }
+        }
+
+        ValidatedConfigUpdates {
+            valid,
+            unconfirmed,
+            frozen,
+            invalid,
+        }
+    }
+
+    /// Reports the updates of frozen configs returned by
+    /// [ConfigUpdates::validate] that would have changed their value.
+    fn report_frozen(frozen: Vec<(&ConfigEntry, ConfigVal)>) {
+        for (config, val) in frozen {
+            if config.val() != val {
+                warn!(
+                    "config update {} {:?} skipped: config is frozen at {:?}",
+                    config.name,
+                    val,
+                    config.val()
+                );
+            }
+        }
+    }
+
+    /// Applies updates returned by [ConfigUpdates::validate], logs the
+    /// configs that changed, and invokes the change listeners and update
+    /// callbacks of `set` for them.
+    fn apply_valid(set: &ConfigSet, valid: Vec<(&ConfigEntry, ConfigVal)>, source: &str) {
+        let mut changes = Vec::new();
+        for (config, val) in valid {
+            if config.dangerous {
+                let mut staged = set.staged.lock().expect("lock poisoned");
+                staged.remove(config.name);
+            }
+            if let Some(old) = config.apply(val.clone()) {
+                info!(
+                    target: "dyncfg",
+                    name = config.name,
+                    ?old,
+                    new = ?val,
+                    source,
+                    "applied config change"
+                );
+                changes.push(ConfigChange {
+                    name: config.name,
+                    old,
+                    new: val,
+                    source: source.to_owned(),
+                });
+            }
+        }
+        if changes.is_empty() {
+            return;
+        }
+
+        // Clone the listeners so none of them run while holding the lock.
+        let listeners = set.change_listeners.lock().expect("lock poisoned").clone();
+        for listener in listeners {
+            for change in &changes {
+                listener(change);
+            }
+        }
+
+        let changed: BTreeSet<_> = changes.iter().map(|change| change.name).collect();
+
+        // Clone the callbacks so none of them run while holding the lock.
+        let callbacks = set.callbacks.lock().expect("lock poisoned").clone();
+        for (prefix, callback) in callbacks {
+            let matching: BTreeSet<_> = changed
+                .iter()
+                .filter(|name| name.starts_with(&prefix))
+                .copied()
+                .collect();
+            if !matching.is_empty() {
+                callback(set, &matching);
+            }
+        }
+    }
+}
+
+/// Returns the digest of an encoded config value, see [ConfigSet::digest].
+#[cfg(feature = "proto")]
+fn val_digest(val: &ProtoConfigVal) -> u64 {
+    mz_ore::hash::hash(&prost::Message::encode_to_vec(val))
+}
+
+/// The suffix of the names of the updates added by [ConfigUpdates::confirm].
+#[cfg(feature = "proto")]
+const CONFIRMATION_SUFFIX: &str = ".confirm";
+
+/// Returns the name of the update that confirms the dangerous config with
+/// `name`, see [ConfigUpdates::confirm].
+#[cfg(feature = "proto")]
+pub fn confirmation_name(name: &str) -> String {
+    format!("{}{}", name, CONFIRMATION_SUFFIX)
+}
+
+/// Returns the environment variable read by [ConfigUpdates::from_env] for the
+/// config with `name`.
+#[cfg(feature = "proto")]
+fn env_var_name(prefix: &str, name: &str) -> String {
+    let name: String = name
+        .chars()
+        .map(|c| {
+            if c.is_ascii_alphanumeric() {
+                c.to_ascii_uppercase()
+            } else {
+                '_'
+            }
+        })
+        .collect();
+    format!("{}{}", prefix, name)
+}
+
+/// [ConfigUpdates] matched against the configs of a [ConfigSet].
+#[cfg(feature = "proto")]
+struct ValidatedConfigUpdates<'a> {
+    /// The updates that can be applied.
+    valid: Vec<(&'a ConfigEntry, ConfigVal)>,
+    /// The updates of dangerous configs that lack a confirmation.
+    unconfirmed: Vec<(&'a ConfigEntry, ConfigVal)>,
+    /// The updates of frozen configs, which are skipped.
+    frozen: Vec<(&'a ConfigEntry, ConfigVal)>,
+    /// The updates that can't be applied, keyed by name.
+    invalid: BTreeMap<String, InvalidConfigUpdate>,
+}
+
+/// The reason an update in [ConfigUpdates] could not be applied to a
+/// [ConfigSet].
+#[derive(Clone, Debug, PartialEq, Eq)]
+pub enum InvalidConfigUpdate {
+    /// The config is not known by the set.
+    Unknown,
+    /// The value could not be decoded.
+    Decode(String),
+    /// The type of the value doesn't match the type of the config.
+    TypeMismatch {
+        /// The type of the config.
+        expected: &'static str,
+        /// The type of the value.
+        found: &'static str,
+    },
+    /// The config is dangerous and the update was not confirmed.
+    Unconfirmed,
+    /// The value is outside of the bounds of the config.
+    OutOfBounds {
+        /// The least allowed value.
+        min: Duration,
+        /// The greatest allowed value.
+        max: Duration,
+    },
+}
+
+impl std::fmt::Display for InvalidConfigUpdate {
+    fn fmt(&self, f: &mut std::fmt::Formatter<'_>) -> std::fmt::Result {
+        match self {
+            InvalidConfigUpdate::Unknown => f.write_str("unknown config"),
+            InvalidConfigUpdate::Decode(err) => write!(f, "decode error: {}", err),
+            InvalidConfigUpdate::TypeMismatch { expected, found } => {
+                write!(f, "expected {} value got {}", expected, found)
+            }
+            InvalidConfigUpdate::Unconfirmed => f.write_str("unconfirmed dangerous config update"),
+            InvalidConfigUpdate::OutOfBounds { min, max } => {
+                write!(f, "value must be between {:?} and {:?}", min, max)
+            }
+        }
+    }
+}
+
+/// An error returned by [ConfigUpdates::apply_strict] and when parsing
+/// [ConfigUpdates] from text.
+#[derive(Clone, Debug, PartialEq, Eq)]
+pub struct ConfigUpdatesError {
+    /// The invalid updates, keyed by config name.
+    pub invalid: BTreeMap<String, InvalidConfigUpdate>,
+}
+
+impl std::fmt::Display for ConfigUpdatesError {
+    fn fmt(&self, f: &mut std::fmt::Formatter<'_>) -> std::fmt::Result {
+        f.write_str("invalid config updates: ")?;
+        for (i, (name, invalid)) in self.invalid.iter().enumerate() {
+            if i > 0 {
+                f.write_str(", ")?;
+            }
+            write!(f, "{}: {}", name, invalid)?;
+        }
+        Ok(())
+    }
+}
+
+impl std::error::Error for ConfigUpdatesError {}
+
+/// A config whose value differs between the snapshots passed to
+/// [check_consistency].
+#[cfg(feature = "proto")]
+#[derive(Clone, Debug, PartialEq)]
+pub struct ConfigInconsistency {
+    /// The name of the config.
+    pub name: String,
+    /// The value of the config in each snapshot that has it, keyed by the
+    /// name of the snapshot's process.
+    pub values: BTreeMap<String, ConfigVal>,
+}
+
+/// Compares the [ConfigUpdates::snapshot]s taken in multiple processes, keyed
+/// by process name, and returns the configs whose values disagree, ordered by
+/// name.
+///
+/// Processes need not register the same configs, so a config is compared
+/// only among the snapshots that have it. Values that can't be decoded, e.g.
+/// those of a newer version, are left out of the comparison.
+#[cfg(feature = "proto")]
+pub fn check_consistency<'a, I>(snapshots: I) -> Vec<ConfigInconsistency>
+where
+    I: IntoIterator<Item = (&'a str, &'a ConfigUpdates)>,
+{
+    let mut values: BTreeMap<&str, BTreeMap<String, ConfigVal>> = BTreeMap::new();
+    for (process, snapshot) in snapshots {
+        for (name, ProtoConfigVal { val }) in &snapshot.updates {
+            if name.ends_with(CONFIRMATION_SUFFIX) {
+                continue;
+            }
+            let Ok(val) = val.clone().into_rust() else {
+                continue;
+            };
+            values
+                .entry(name)
+                .or_default()
+                .insert(process.to_owned(), val);
+        }
+    }
+    values
+        .into_iter()
+        .filter(|(_, values)| {
+            let mut values = values.values();
+            let first = values.next();
+            values.any(|val| Some(val) != first)
+        })
+        .map(|(name, values)| ConfigInconsistency {
+            name: name.to_owned(),
+            values,
+        })
+        .collect()
+}
+
+/// A conflict between the registrations of a config in two [ConfigSet]s.
+#[derive(Clone, Debug, PartialEq)]
+pub enum ConfigConflict {
+    /// The config has a different type in each set.
+    TypeMismatch {
+        /// The type of the config in the set being merged into.
+        left: &'static str,
+        /// The type of the config in the set being merged.
+        right: &'static str,
+    },
+    /// The config has a different default in each set.
+    DefaultMismatch {
+        /// The default of the config in the set being merged into.
+        left: ConfigVal,
+        /// The default of the config in the set being merged.
+        right: ConfigVal,
+    },
+}
+
+impl std::fmt::Display for ConfigConflict {
+    fn fmt(&self, f: &mut std::fmt::Formatter<'_>) -> std::fmt::Result {
+        match self {
+            ConfigConflict::TypeMismatch { left, right } => {
+                write!(f, "registered as both {} and {}", left, right)
+            }
+            ConfigConflict::DefaultMismatch { left, right } => {
+                write!(f, "registered with defaults {:?} and {:?}", left, right)
+            }
+        }
+    }
+}
+
+/// An error returned by [ConfigSet::merge].
+#[derive(Clone, Debug, PartialEq)]
+pub struct ConfigSetMergeError {
+    /// The conflicting registrations, keyed by config name.
+    pub conflicts: BTreeMap<String, ConfigConflict>,
+}
+
+impl std::fmt::Display for ConfigSetMergeError {
+    fn fmt(&self, f: &mut std::fmt::Formatter<'_>) -> std::fmt::Result {
+        f.write_str("conflicting config registrations: ")?;
+        for (i, (name, conflict)) in self.conflicts.iter().enumerate() {
+            if i > 0 {
+                f.write_str(", ")?;
+            }
+            write!(f, "{}: {}", name, conflict)?;
+        }
+        Ok(())
+    }
+}
+
+impl std::error::Error for ConfigSetMergeError {}
+
+/// Parses config values from external string sources, like environment
+/// variables, command-line flags, and files.
+///
+/// Values are parsed according to the type of their config. Compared to
+/// [Config::parse_val], this is forgiving of human-written input:
+/// - Surrounding whitespace is ignored.
+/// - Bools may be spelled `true`/`false`, `on`/`off`, `yes`/`no`, `t`/`f`,
+///   `y`/`n`, or `1`/`0`, in any case.
+/// - Integers may contain `_` separators, like `1_000_000`.
+/// - An `Option<usize>` is unset by an empty value, `none`, or `null`.
+/// - Durations are human-friendly, like `30s` or `1h 30m`.
+/// - Byte sizes may have units, like `64MiB` or `1 GB`.
+///
+/// Parsing never panics, whatever the input, and errors name the config,
+/// its type, and the rejected input.
+#[derive(Clone, Copy, Debug, Default)]
+pub struct ConfigValueParser;
+
+impl ConfigValueParser {
+    /// Parses `input` as a value of the given config.
+    pub fn parse(
+        &self,
+        config: &ConfigEntry,
+        input: &str,
+    ) -> Result<ConfigVal, ConfigValueParseError> {
+        Self::parse_as(config.name, &config.default, input)
+    }
+
+    /// Parses `input` as a value of the given config, returning it typed.
+    pub fn parse_typed<D: ConfigDefault>(
+        &self,
+        config: &Config<D>,
+        input: &str,
+    ) -> Result<D::ConfigType, ConfigValueParseError> {
+        let default: ConfigVal = config.default.clone().into_config_type().into();
+        let val = Self::parse_as(config.name, &default, input)?;
+        Ok(D::ConfigType::from_val(val))
+    }
+
+    /// Parses `input` as a value of the same type as `like`.
+    fn parse_as(
+        name: &str,
+        like: &ConfigVal,
+        input: &str,
+    ) -> Result<ConfigVal, ConfigValueParseError> {
+        let s = input.trim();
+        let val = match like {
+            ConfigVal::Bool(_) => Self::parse_bool(s).map(ConfigVal::Bool),
+            ConfigVal::U8(_) => Self::parse_int(s).map(ConfigVal::U8),
+            ConfigVal::U32(_) => Self::parse_int(s).map(ConfigVal::U32),
+            ConfigVal::I64(_) => Self::parse_int(s).map(ConfigVal::I64),
+            ConfigVal::Usize(_) => Self::parse_int(s).map(ConfigVal::Usize),
+            ConfigVal::OptUsize(_) => match s.to_ascii_lowercase().as_str() {
+                "" | "none" | "null" => Ok(ConfigVal::OptUsize(None)),
+                _ => Self::parse_int(s).map(|x| ConfigVal::OptUsize(Some(x))),
+            },
+            ConfigVal::F64(_) => s
+                .parse()
+                .map(ConfigVal::F64)
+                .map_err(|e: ParseFloatError| e.to_string()),
+            // Whitespace may be significant in strings.
+            ConfigVal::String(_) => Ok(ConfigVal::String(input.to_owned())),
+            ConfigVal::Duration(_) => humantime::parse_duration(s)
+                .map(ConfigVal::Duration)
+                .map_err(|e| e.to_string()),
+            ConfigVal::Bytes(_) => s.parse().map(ConfigVal::Bytes),
+            ConfigVal::Json(_) => serde_json::from_str(s)
+                .map(ConfigVal::Json)
+                .map_err(|e| e.to_string()),
+        };
+        val.map_err(|reason| ConfigValueParseError {
+            name: name.to_owned(),
+            expected: like.type_name(),
+            input: input.to_owned(),
+            reason,
+        })
+    }
+
+    fn parse_bool(s: &str) -> Result<bool, String> {
+        match s.to_ascii_lowercase().as_str() {
+            "true" | "on" | "yes" | "t" | "y" | "1" => Ok(true),
+            "false" | "off" | "no" | "f" | "n" | "0" => Ok(false),
+            _ => Err("expected one of true, false, on, off, yes, no, 1, or 0".into()),
         }
     }
+
+    fn parse_int<T: std::str::FromStr<Err = ParseIntError>>(s: &str) -> Result<T, String> {
+        let digits: String = s.chars().filter(|c| *c != '_').collect();
+        digits.parse().map_err(|e: ParseIntError| e.to_string())
+    }
+}
+
+/// An error returned by [ConfigValueParser].
+#[derive(Clone, Debug, PartialEq, Eq)]
+pub struct ConfigValueParseError {
+    /// The name of the config.
+    pub name: String,
+    /// The type of the config.
+    pub expected: &'static str,
+    /// The input that could not be parsed.
+    pub input: String,
+    /// Why the input could not be parsed.
+    pub reason: String,
 }
 
+impl std::fmt::Display for ConfigValueParseError {
+    fn fmt(&self, f: &mut std::fmt::Formatter<'_>) -> std::fmt::Result {
+        write!(
+            f,
+            "invalid {} value {:?} for config {}: {}",
+            self.expected, self.input, self.name, self.reason
+        )
+    }
+}
+
+impl std::error::Error for ConfigValueParseError {}
+
 mod impls {
     use std::num::{ParseFloatError, ParseIntError};
     use std::str::ParseBoolError;
     use std::time::Duration;
 
+    use bytesize::ByteSize;
+    #[cfg(feature = "proto")]
     use mz_ore::cast::CastFrom;
+    #[cfg(feature = "proto")]
     use mz_proto::{ProtoType, RustType, TryFromProtoError};
 
-    use crate::{
-        proto_config_val, ConfigDefault, ConfigSet, ConfigType, ConfigVal, ProtoOptionU64,
-    };
+    #[cfg(feature = "proto")]
+    use crate::{proto_config_val, ProtoOptionU64};
+    use crate::{ConfigDefault, ConfigSet, ConfigType, ConfigVal};
 
     impl ConfigType for bool {
         fn from_val(val: ConfigVal) -> Self {
@@ -487,6 +2199,25 @@ mod impls {
         }
     }
 
+    impl ConfigType for u8 {
+        fn from_val(val: ConfigVal) -> Self {
+            match val {
+                ConfigVal::U8(x) => x,
+                x => panic!("expected u8 value got {:?}", x),
+            }
+        }
+
+        fn parse(s: &str) -> Result<Self, String> {
+            s.parse().map_err(|e: ParseIntError| e.to_string())
+        }
+    }
+
+    impl From<u8> for ConfigVal {
+        fn from(val: u8) -> ConfigVal {
+            ConfigVal::U8(val)
+        }
+    }
+
     impl ConfigType for u32 {
         fn from_val(val: ConfigVal) -> Self {
             match val {
@@ -506,6 +2237,25 @@ mod impls {
         }
     }
 
+    impl ConfigType for i64 {
+        fn from_val(val: ConfigVal) -> Self {
+            match val {
+                ConfigVal::I64(x) => x,
+                x => panic!("expected i64 value got {:?}", x),
+            }
+        }
+
+        fn parse(s: &str) -> Result<Self, String> {
+            s.parse().map_err(|e: ParseIntError| e.to_string())
+        }
+    }
+
+    impl From<i64> for ConfigVal {
+        fn from(val: i64) -> ConfigVal {
+            ConfigVal::I64(val)
+        }
+    }
+
     impl ConfigType for usize {
         fn from_val(val: ConfigVal) -> Self {
             match val {
@@ -614,6 +2364,25 @@ mod impls {
         }
     }
 
+    impl ConfigType for ByteSize {
+        fn from_val(val: ConfigVal) -> Self {
+            match val {
+                ConfigVal::Bytes(x) => x,
+                x => panic!("expected ByteSize value got {:?}", x),
+            }
+        }
+
+        fn parse(s: &str) -> Result<Self, String> {
+            s.parse()
+        }
+    }
+
+    impl From<ByteSize> for ConfigVal {
+        fn from(val: ByteSize) -> ConfigVal {
+            ConfigVal::Bytes(val)
+        }
+    }
+
     impl ConfigType for serde_json::Value {
         fn from_val(val: ConfigVal) -> Self {
             match val {
@@ -633,12 +2402,15 @@ mod impls {
         }
     }
 
+    #[cfg(feature = "proto")]
     impl RustType<Option<proto_config_val::Val>> for ConfigVal {
         fn into_proto(&self) -> Option<proto_config_val::Val> {
             use crate::proto_config_val::Val;
             let val = match self {
                 ConfigVal::Bool(x) => Val::Bool(*x),
+                ConfigVal::U8(x) => Val::U8(u32::from(*x)),
                 ConfigVal::U32(x) => Val::U32(*x),
+                ConfigVal::I64(x) => Val::I64(*x),
                 ConfigVal::Usize(x) => Val::Usize(u64::cast_from(*x)),
                 ConfigVal::OptUsize(x) => Val::OptUsize(ProtoOptionU64 {
                     val: x.map(u64::cast_from),
@@ -646,6 +2418,7 @@ mod impls {
                 ConfigVal::F64(x) => Val::F64(*x),
                 ConfigVal::String(x) => Val::String(x.into_proto()),
                 ConfigVal::Duration(x) => Val::Duration(x.into_proto()),
+                ConfigVal::Bytes(x) => Val::Bytes(x.as_u64()),
                 ConfigVal::Json(x) => Val::Json(x.to_string()),
             };
             Some(val)
@@ -654,7 +2427,9 @@ mod impls {
         fn from_proto(proto: Option<proto_config_val::Val>) -> Result<Self, TryFromProtoError> {
             let val = match proto {
                 Some(proto_config_val::Val::Bool(x)) => ConfigVal::Bool(x),
+                Some(proto_config_val::Val::U8(x)) => ConfigVal::U8(u8::try_from(x)?),
                 Some(proto_config_val::Val::U32(x)) => ConfigVal::U32(x),
+                Some(proto_config_val::Val::I64(x)) => ConfigVal::I64(x),
                 Some(proto_config_val::Val::Usize(x)) => ConfigVal::Usize(usize::cast_from(x)),
                 Some(proto_config_val::Val::OptUsize(ProtoOptionU64 { val })) => {
                     ConfigVal::OptUsize(val.map(usize::cast_from))
@@ -662,6 +2437,7 @@ mod impls {
                 Some(proto_config_val::Val::F64(x)) => ConfigVal::F64(x),
                 Some(proto_config_val::Val::String(x)) => ConfigVal::String(x),
                 Some(proto_config_val::Val::Duration(x)) => ConfigVal::Duration(x.into_rust()?),
+                Some(proto_config_val::Val::Bytes(x)) => ConfigVal::Bytes(ByteSize(x)),
                 Some(proto_config_val::Val::Json(x)) => ConfigVal::Json(serde_json::from_str(&x)?),
                 None => {
                     return Err(TryFromProtoError::unknown_enum_variant(
@@ -675,7 +2451,15 @@ mod impls {
 
     impl std::fmt::Debug for ConfigSet {
         fn fmt(&self, f: &mut std::fmt::Formatter<'_>) -> std::fmt::Result {
-            let ConfigSet { configs } = self;
+            let ConfigSet {
+                configs,
+                callbacks: _,
+                change_listeners: _,
+                #[cfg(feature = "proto")]
+                    scheduled: _,
+                staged: _,
+                frozen: _,
+            } = self;
             f.debug_map()
                 .entries(configs.iter().map(|(name, val)| (name, val.val())))
                 .finish()
@@ -683,19 +2467,23 @@ mod impls {
     }
 }
 
-#[cfg(test)]
+// The tests exercise config updates, which are encoded as protobuf messages.
+#[cfg(all(test, feature = "proto"))]
 mod tests {
     use super::*;
 
     use mz_ore::assert_err;
 
     const BOOL: Config<bool> = Config::new("bool", true, "");
+    const U8: Config<u8> = Config::new("u8", 2, "");
     const U32: Config<u32> = Config::new("u32", 4, "");
+    const I64: Config<i64> = Config::new("i64", -3, "");
     const USIZE: Config<usize> = Config::new("usize", 1, "");
     const OPT_USIZE: Config<Option<usize>> = Config::new("opt_usize", Some(2), "");
     const F64: Config<f64> = Config::new("f64", 5.0, "");
     const STRING: Config<&str> = Config::new("string", "a", "");
     const DURATION: Config<Duration> = Config::new("duration", Duration::from_nanos(3), "");
+    const BYTES: Config<ByteSize> = Config::new("bytes", ByteSize::kib(6), "");
     const JSON: Config<fn() -> serde_json::Value> =
         Config::new("json", || serde_json::json!({}), "");
 
@@ -703,61 +2491,407 @@ mod tests {
     fn all_types() {
         let configs = ConfigSet::default()
             .add(&BOOL)
+            .add(&U8)
             .add(&USIZE)
             .add(&U32)
+            .add(&I64)
             .add(&OPT_USIZE)
             .add(&F64)
             .add(&STRING)
             .add(&DURATION)
+            .add(&BYTES)
             .add(&JSON);
         assert_eq!(BOOL.get(&configs), true);
+        assert_eq!(U8.get(&configs), 2);
         assert_eq!(U32.get(&configs), 4);
+        assert_eq!(I64.get(&configs), -3);
         assert_eq!(USIZE.get(&configs), 1);
         assert_eq!(OPT_USIZE.get(&configs), Some(2));
         assert_eq!(F64.get(&configs), 5.0);
         assert_eq!(STRING.get(&configs), "a");
         assert_eq!(DURATION.get(&configs), Duration::from_nanos(3));
+        assert_eq!(BYTES.get(&configs), ByteSize::kib(6));
         assert_eq!(JSON.get(&configs), serde_json::json!({}));
 
         let mut updates = ConfigUpdates::default();
         updates.add(&BOOL, false);
+        updates.add(&U8, 3);
         updates.add(&U32, 7);
+        updates.add(&I64, i64::MIN);
         updates.add(&USIZE, 2);
         updates.add(&OPT_USIZE, None);
         updates.add(&F64, 8.0);
         updates.add(&STRING, "b");
         updates.add(&DURATION, Duration::from_nanos(4));
+        updates.add(&BYTES, ByteSize::mib(7));
         updates.add(&JSON, serde_json::json!({"a": 1}));
         updates.apply(&configs);
 
         assert_eq!(BOOL.get(&configs), false);
+        assert_eq!(U8.get(&configs), 3);
         assert_eq!(U32.get(&configs), 7);
+        assert_eq!(I64.get(&configs), i64::MIN);
         assert_eq!(USIZE.get(&configs), 2);
         assert_eq!(OPT_USIZE.get(&configs), None);
         assert_eq!(F64.get(&configs), 8.0);
         assert_eq!(STRING.get(&configs), "b");
         assert_eq!(DURATION.get(&configs), Duration::from_nanos(4));
+        assert_eq!(BYTES.get(&configs), ByteSize::mib(7));
         assert_eq!(JSON.get(&configs), serde_json::json!({"a": 1}));
-    }
 
-    #[mz_ore::test]
-    fn fn_default() {
-        const BOOL_FN_DEFAULT: Config<fn() -> bool> = Config::new("bool", || !true, "");
-        const STRING_FN_DEFAULT: Config<fn() -> String> =
-            Config::new("string", || "x".repeat(3), "");
-
-        let configs = ConfigSet::default()
-            .add(&BOOL_FN_DEFAULT)
-            .add(&STRING_FN_DEFAULT);
-        assert_eq!(BOOL_FN_DEFAULT.get(&configs), false);
-        assert_eq!(STRING_FN_DEFAULT.get(&configs), "xxx");
+        for val in [
+            ConfigVal::I64(i64::MIN),
+            ConfigVal::I64(-1),
+            ConfigVal::I64(i64::MAX),
+            ConfigVal::U8(u8::MAX),
+        ] {
+            let proto: Option<proto_config_val::Val> = val.into_proto();
+            assert_eq!(ConfigVal::from_proto(proto).expect("valid proto"), val);
+        }
+        assert_err!(ConfigVal::from_proto(Some(proto_config_val::Val::U8(256))));
     }
 
     #[mz_ore::test]
-    fn config_set() {
-        let c0 = ConfigSet::default().add(&USIZE);
-        assert_eq!(USIZE.get(&c0), 1);
-        let mut updates = ConfigUpdates::default();
+    fn value_type() {
+        let configs = ConfigSet::default()
+            .add(&BOOL)
+            .add(&OPT_USIZE)
+            .add(&DURATION)
+            .add(&JSON);
+        let kinds: Vec<_> = configs
+            .entries()
+            .map(|entry| (entry.name(), entry.value_type()))
+            .collect();
+        assert_eq!(
+            kinds,
+            vec![
+                (BOOL.name, ConfigTypeKind::Bool),
+                (DURATION.name, ConfigTypeKind::Duration),
+                (JSON.name, ConfigTypeKind::Json),
+                (OPT_USIZE.name, ConfigTypeKind::OptUsize),
+            ]
+        );
+        for entry in configs.entries() {
+            let kind = entry.value_type();
+            assert_eq!(entry.default().kind(), kind);
+            assert_eq!(kind.to_string(), entry.default().type_name());
+        }
+
+        assert_eq!(
+            ConfigTypeKind::Duration.parse("2s"),
+            Ok(ConfigVal::Duration(Duration::from_secs(2)))
+        );
+        assert_eq!(
+            ConfigTypeKind::OptUsize.parse(""),
+            Ok(ConfigVal::OptUsize(None))
+        );
+        assert_err!(ConfigTypeKind::Bool.parse("2"));
+    }
+
+    #[derive(Clone, Copy, Debug, PartialEq)]
+    enum Mode {
+        Off,
+        On,
+    }
+
+    impl From<u8> for Mode {
+        fn from(val: u8) -> Self {
+            match val {
+                1 => Mode::On,
+                _ => Mode::Off,
+            }
+        }
+    }
+
+    impl From<Mode> for u8 {
+        fn from(val: Mode) -> u8 {
+            match val {
+                Mode::Off => 0,
+                Mode::On => 1,
+            }
+        }
+    }
+
+    impl_config_enum!(Mode);
+
+    #[mz_ore::test]
+    fn config_enum() {
+        const MODE: Config<Mode> = Config::new("mode", Mode::Off, "");
+        let configs = ConfigSet::default().add(&MODE);
+        assert_eq!(MODE.get(&configs), Mode::Off);
+        assert_eq!(configs.entry("mode").unwrap().default(), &ConfigVal::U8(0));
+
+        let mut updates = ConfigUpdates::default();
+        updates.add(&MODE, Mode::On);
+        updates.apply(&configs);
+        assert_eq!(MODE.get(&configs), Mode::On);
+        assert_eq!(MODE.handle(&configs).get(), Mode::On);
+
+        assert_eq!(MODE.parse_val("1"), Ok(ConfigVal::U8(1)));
+        // Unknown values map to whatever the enum maps them to.
+        assert_eq!(<Mode as ConfigType>::parse("7"), Ok(Mode::Off));
+        assert_err!(MODE.parse_val("256"));
+        assert_err!(MODE.parse_val("on"));
+    }
+
+    #[mz_ore::test]
+    fn override_for_test() {
+        const DANGER: Config<bool> = Config::new_dangerous("danger", false, "");
+        let configs = ConfigSet::default().add(&USIZE).add(&DANGER);
+        {
+            let _guard = USIZE.override_for_test(&configs, 2);
+            assert_eq!(USIZE.get(&configs), 2);
+            {
+                let _guard = USIZE.override_for_test(&configs, 3);
+                assert_eq!(USIZE.get(&configs), 3);
+            }
+            assert_eq!(USIZE.get(&configs), 2);
+        }
+        assert_eq!(USIZE.get(&configs), 1);
+
+        // Overrides need no confirmation and apply to frozen configs.
+        assert!(configs.freeze(DANGER.name()));
+        let guard = DANGER.override_for_test(&configs, true);
+        assert_eq!(DANGER.get(&configs), true);
+        drop(guard);
+        assert_eq!(DANGER.get(&configs), false);
+        assert!(configs.staged().is_empty());
+    }
+
+    #[mz_ore::test]
+    fn cached() {
+        let configs = ConfigSet::default().add(&STRING);
+        let cached = STRING.cached(&configs, Duration::from_secs(60));
+        let now = Instant::now();
+        assert_eq!(cached.get(now), "a");
+
+        let mut updates = ConfigUpdates::default();
+        updates.add(&STRING, "b");
+        updates.apply(&configs);
+        assert_eq!(cached.get(now), "a");
+        assert_eq!(cached.get(now + Duration::from_secs(59)), "a");
+        // A `now` before the value was loaded doesn't reload it.
+        assert_eq!(cached.get(now - Duration::from_secs(1)), "a");
+        assert_eq!(cached.get(now + Duration::from_secs(60)), "b");
+
+        updates.add(&STRING, "c");
+        updates.apply(&configs);
+        cached.invalidate();
+        assert_eq!(cached.get(now), "c");
+    }
+
+    #[cfg(debug_assertions)]
+    #[mz_ore::test]
+    fn unread_configs() {
+        let configs = ConfigSet::default().add(&USIZE).add(&BOOL).add(&STRING);
+        assert_eq!(
+            configs.unread_configs(),
+            BTreeSet::from([BOOL.name, STRING.name, USIZE.name])
+        );
+
+        // Type-erased reads don't count.
+        for entry in configs.entries() {
+            let _ = entry.val();
+        }
+        // Neither do updates.
+        let mut updates = ConfigUpdates::default();
+        updates.add(&BOOL, false);
+        updates.apply(&configs);
+        assert_eq!(configs.unread_configs().len(), 3);
+
+        assert_eq!(USIZE.get(&configs), 1);
+        // Reads through a clone of the set count for the original.
+        let _handle = STRING.handle(&configs.clone());
+        assert_eq!(configs.unread_configs(), BTreeSet::from([BOOL.name]));
+    }
+
+    #[mz_ore::test]
+    fn change_listener() {
+        let configs = ConfigSet::default().add(&USIZE).add(&BOOL);
+        let changes = Arc::new(Mutex::new(Vec::new()));
+        let listener_changes = Arc::clone(&changes);
+        configs.register_change_listener(move |change| {
+            listener_changes.lock().unwrap().push(change.clone());
+        });
+
+        let mut updates = ConfigUpdates::default().with_source("test");
+        updates.add(&USIZE, 2);
+        // Unchanged values are not reported.
+        updates.add(&BOOL, true);
+        updates.apply(&configs);
+        let expected = ConfigChange {
+            name: USIZE.name(),
+            old: ConfigVal::Usize(1),
+            new: ConfigVal::Usize(2),
+            source: "test".into(),
+        };
+        assert_eq!(*changes.lock().unwrap(), vec![expected]);
+
+        // The source of the extended updates is kept, unless it is empty.
+        let mut updates = ConfigUpdates::default();
+        let mut other = ConfigUpdates::default().with_source("other");
+        other.add(&USIZE, 3);
+        updates.extend(other);
+        updates.apply(&configs);
+        assert_eq!(changes.lock().unwrap()[1].source, "other");
+
+        changes.lock().unwrap().clear();
+        drop(USIZE.override_for_test(&configs, 4));
+        let sources: Vec<_> = changes
+            .lock()
+            .unwrap()
+            .iter()
+            .map(|c| c.source.clone())
+            .collect();
+        assert_eq!(sources, vec![TEST_OVERRIDE_SOURCE, TEST_OVERRIDE_SOURCE]);
+    }
+
+    #[mz_ore::test]
+    fn schema() {
+        const INTERVAL: Config<Duration> = Config::new_secs("interval", 30, "how often")
+            .with_bounds(Duration::from_secs(1), Duration::from_secs(3600));
+        const DANGER: Config<bool> = Config::new_dangerous("danger", false, "careful");
+        let configs = ConfigSet::default()
+            .add(&INTERVAL)
+            .add(&DANGER)
+            .add(&OPT_USIZE)
+            .add(&BYTES);
+        // Current values are not part of the schema.
+        let mut updates = ConfigUpdates::default();
+        updates.add(&OPT_USIZE, None);
+        updates.apply(&configs);
+
+        assert_eq!(
+            configs.schema(),
+            serde_json::json!({
+                "version": CONFIG_SCHEMA_VERSION,
+                "configs": [
+                    {
+                        "name": "bytes",
+                        "type": "ByteSize",
+                        "default": 6144,
+                        "description": "",
+                        "dangerous": false,
+                        "bounds": null,
+                    },
+                    {
+                        "name": "danger",
+                        "type": "bool",
+                        "default": false,
+                        "description": "careful",
+                        "dangerous": true,
+                        "bounds": null,
+                    },
+                    {
+                        "name": "interval",
+                        "type": "Duration",
+                        "default": "30s",
+                        "description": "how often",
+                        "dangerous": false,
+                        "bounds": {"min": "1s", "max": "1h"},
+                    },
+                    {
+                        "name": "opt_usize",
+                        "type": "Option<usize>",
+                        "default": 2,
+                        "description": "",
+                        "dangerous": false,
+                        "bounds": null,
+                    },
+                ],
+            })
+        );
+    }
+
+    #[mz_ore::test]
+    fn duration_bounds() {
+        const INTERVAL: Config<Duration> = Config::new_secs("interval", 30, "")
+            .with_bounds(Duration::from_secs(1), Duration::from_secs(3600));
+        assert_eq!(INTERVAL.default(), &Duration::from_secs(30));
+        assert_eq!(
+            Config::new_millis("timeout", 1500, "").default(),
+            &Duration::from_millis(1500)
+        );
+
+        let configs = ConfigSet::default().add(&INTERVAL);
+        let mut updates = ConfigUpdates::default();
+        updates.add(&INTERVAL, Duration::from_secs(60));
+        assert_eq!(updates.apply_strict(&configs), Ok(()));
+        assert_eq!(INTERVAL.get(&configs), Duration::from_secs(60));
+
+        for val in [Duration::ZERO, Duration::from_secs(3601)] {
+            let mut updates = ConfigUpdates::default();
+            updates.add(&INTERVAL, val);
+            let err = InvalidConfigUpdate::OutOfBounds {
+                min: Duration::from_secs(1),
+                max: Duration::from_secs(3600),
+            };
+            assert_eq!(
+                updates.apply_strict(&configs),
+                Err(ConfigUpdatesError {
+                    invalid: BTreeMap::from([("interval".to_owned(), err)]),
+                })
+            );
+            updates.apply(&configs);
+            assert_eq!(INTERVAL.get(&configs), Duration::from_secs(60));
+        }
+    }
+
+    #[mz_ore::test]
+    fn merge() {
+        let left = ConfigSet::default().add(&BOOL).add(&U32);
+        let right = ConfigSet::default().add(&U32).add(&USIZE);
+        let merged = left.merge(right).expect("no conflicts");
+        let names: Vec<_> = merged.entries().map(|entry| entry.name()).collect();
+        assert_eq!(names, vec!["bool", "u32", "usize"]);
+
+        const U32_OTHER_DEFAULT: Config<u32> = Config::new("u32", 5, "");
+        const USIZE_OTHER_TYPE: Config<bool> = Config::new("usize", true, "");
+        let left = ConfigSet::default().add(&U32).add(&USIZE).add(&BOOL);
+        let right = ConfigSet::default()
+            .add(&U32_OTHER_DEFAULT)
+            .add(&USIZE_OTHER_TYPE)
+            .add(&BOOL);
+        let err = left.merge(right).unwrap_err();
+        assert_eq!(
+            err.conflicts,
+            BTreeMap::from([
+                (
+                    "u32".to_owned(),
+                    ConfigConflict::DefaultMismatch {
+                        left: ConfigVal::U32(4),
+                        right: ConfigVal::U32(5),
+                    }
+                ),
+                (
+                    "usize".to_owned(),
+                    ConfigConflict::TypeMismatch {
+                        left: "usize",
+                        right: "bool",
+                    }
+                ),
+            ])
+        );
+    }
+
+    #[mz_ore::test]
+    fn fn_default() {
+        const BOOL_FN_DEFAULT: Config<fn() -> bool> = Config::new("bool", || !true, "");
+        const STRING_FN_DEFAULT: Config<fn() -> String> =
+            Config::new("string", || "x".repeat(3), "");
+
+        let configs = ConfigSet::default()
+            .add(&BOOL_FN_DEFAULT)
+            .add(&STRING_FN_DEFAULT);
+        assert_eq!(BOOL_FN_DEFAULT.get(&configs), false);
+        assert_eq!(STRING_FN_DEFAULT.get(&configs), "xxx");
+    }
+
+    #[mz_ore::test]
+    fn config_set() {
+        let c0 = ConfigSet::default().add(&USIZE);
+        assert_eq!(USIZE.get(&c0), 1);
+        let mut updates = ConfigUpdates::default();
         updates.add(&USIZE, 2);
         updates.apply(&c0);
         assert_eq!(USIZE.get(&c0), 2);
@@ -782,6 +2916,250 @@ mod tests {
         assert_eq!(USIZE.get(&c1), 2);
     }
 
+    #[mz_ore::test]
+    fn apply_strict() {
+        let configs = ConfigSet::default().add(&USIZE).add(&BOOL);
+
+        // Valid updates are applied.
+        let mut updates = ConfigUpdates::default();
+        updates.add(&USIZE, 2);
+        assert_eq!(updates.apply_strict(&configs), Ok(()));
+        assert_eq!(USIZE.get(&configs), 2);
+
+        // An unknown config or a type mismatch rejects all of the updates.
+        let mut updates = ConfigUpdates::default();
+        updates.add(&USIZE, 3);
+        updates.add(&U32, 4);
+        updates.add_dynamic(BOOL.name(), ConfigVal::Usize(5));
+        let err = updates.apply_strict(&configs).unwrap_err();
+        assert_eq!(
+            err.invalid,
+            BTreeMap::from([
+                (
+                    BOOL.name().to_owned(),
+                    InvalidConfigUpdate::TypeMismatch {
+                        expected: "bool",
+                        found: "usize",
+                    },
+                ),
+                (U32.name().to_owned(), InvalidConfigUpdate::Unknown),
+            ])
+        );
+        assert_eq!(USIZE.get(&configs), 2);
+        assert_eq!(BOOL.get(&configs), true);
+
+        // The lenient mode skips the invalid updates instead.
+        updates.apply(&configs);
+        assert_eq!(USIZE.get(&configs), 3);
+        assert_eq!(BOOL.get(&configs), true);
+    }
+
+    #[mz_ore::test]
+    fn dangerous_configs() {
+        const DANGEROUS: Config<usize> = Config::new_dangerous("dangerous", 1, "");
+        let configs = ConfigSet::default().add(&DANGEROUS);
+
+        // An unconfirmed update is staged rather than applied.
+        let mut updates = ConfigUpdates::default();
+        updates.add(&DANGEROUS, 2);
+        updates.apply(&configs);
+        assert_eq!(DANGEROUS.get(&configs), 1);
+        assert_eq!(
+            configs.staged(),
+            BTreeMap::from([("dangerous", ConfigVal::Usize(2))])
+        );
+        assert_eq!(
+            updates.apply_strict(&configs).unwrap_err().invalid,
+            BTreeMap::from([("dangerous".to_owned(), InvalidConfigUpdate::Unconfirmed)])
+        );
+
+        // A later confirmation applies the staged update.
+        let mut updates = ConfigUpdates::default();
+        updates.confirm(&DANGEROUS);
+        updates.apply(&configs);
+        assert_eq!(DANGEROUS.get(&configs), 2);
+        assert_eq!(configs.staged(), BTreeMap::new());
+
+        // An update confirmed in the same updates is applied directly.
+        let mut updates = ConfigUpdates::default();
+        updates.add(&DANGEROUS, 3);
+        updates.confirm(&DANGEROUS);
+        assert_eq!(updates.apply_strict(&configs), Ok(()));
+        assert_eq!(DANGEROUS.get(&configs), 3);
+
+        // Re-sending the current value needs no confirmation.
+        let mut updates = ConfigUpdates::default();
+        updates.add(&DANGEROUS, 3);
+        assert_eq!(updates.apply_strict(&configs), Ok(()));
+
+        // Confirming a config that isn't dangerous is invalid.
+        let configs = configs.add(&USIZE);
+        let mut updates = ConfigUpdates::default();
+        updates.confirm(&USIZE);
+        assert_eq!(
+            updates.apply_strict(&configs).unwrap_err().invalid,
+            BTreeMap::from([("usize.confirm".to_owned(), InvalidConfigUpdate::Unknown)])
+        );
+    }
+
+    #[mz_ore::test]
+    fn frozen_configs() {
+        const DANGEROUS: Config<usize> = Config::new_dangerous("dangerous", 1, "");
+        let configs = ConfigSet::default().add(&BOOL).add(&USIZE).add(&DANGEROUS);
+        assert!(configs.freeze("usize"));
+        assert!(!configs.freeze("unknown"));
+        assert_eq!(configs.frozen(), BTreeSet::from(["usize"]));
+
+        // Updates to frozen configs are skipped, the rest are applied.
+        let mut updates = ConfigUpdates::default();
+        updates.add(&USIZE, 2);
+        updates.add(&BOOL, false);
+        updates.apply(&configs);
+        assert_eq!(USIZE.get(&configs), 1);
+        assert_eq!(BOOL.get(&configs), false);
+        updates.apply_strict(&configs).unwrap();
+        assert_eq!(USIZE.get(&configs), 1);
+
+        // Freezing is shared by clones.
+        let clone = configs.clone();
+        assert!(clone.unfreeze("usize"));
+        assert!(!clone.unfreeze("usize"));
+        updates.apply(&configs);
+        assert_eq!(USIZE.get(&configs), 2);
+
+        // Confirming an update staged before the config was frozen doesn't
+        // apply it either.
+        let mut updates = ConfigUpdates::default();
+        updates.add(&DANGEROUS, 2);
+        updates.apply(&configs);
+        assert!(configs.freeze("dangerous"));
+        let mut confirmation = ConfigUpdates::default();
+        confirmation.confirm(&DANGEROUS);
+        confirmation.apply(&configs);
+        assert_eq!(DANGEROUS.get(&configs), 1);
+    }
+
+    #[mz_ore::test]
+    fn scheduled_updates() {
+        let configs = ConfigSet::default().add(&USIZE);
+        let t0 = SystemTime::UNIX_EPOCH;
+        let t1 = t0 + Duration::from_secs(1);
+        let t2 = t0 + Duration::from_secs(2);
+
+        let schedule = |val: usize, at: SystemTime| {
+            let mut updates = ConfigUpdates::default();
+            updates.add(&USIZE, val);
+            updates.schedule(&configs, at);
+        };
+        schedule(3, t2);
+        schedule(2, t1);
+        schedule(4, t2);
+        let activations: Vec<_> = configs.scheduled().iter().map(|s| s.activate_at).collect();
+        assert_eq!(activations, vec![t1, t2, t2]);
+
+        // Nothing is due yet.
+        assert_eq!(configs.apply_scheduled(t0), vec![]);
+        assert_eq!(USIZE.get(&configs), 1);
+
+        // Due updates are applied in activation order, the rest stay pending.
+        assert_eq!(configs.apply_scheduled(t1).len(), 1);
+        assert_eq!(USIZE.get(&configs), 2);
+        assert_eq!(
+            configs.apply_scheduled(t2 + Duration::from_secs(1)).len(),
+            2
+        );
+        assert_eq!(USIZE.get(&configs), 4);
+        assert_eq!(configs.scheduled(), vec![]);
+
+        // Cleared updates are never applied.
+        schedule(5, t1);
+        configs.clear_scheduled();
+        assert_eq!(configs.apply_scheduled(t2), vec![]);
+        assert_eq!(USIZE.get(&configs), 4);
+    }
+
+    #[mz_ore::test]
+    fn config_history() {
+        let configs = ConfigSet::default().add(&USIZE);
+        let entry = configs.entry(USIZE.name()).unwrap();
+        assert_eq!(entry.history(), vec![]);
+
+        let apply = |val: usize| {
+            let mut updates = ConfigUpdates::default();
+            updates.add(&USIZE, val);
+            updates.apply(&configs);
+        };
+        let history = || {
+            entry
+                .history()
+                .into_iter()
+                .map(|x| x.val)
+                .collect::<Vec<_>>()
+        };
+
+        apply(2);
+        apply(2);
+        apply(3);
+        assert_eq!(history(), vec![ConfigVal::Usize(2), ConfigVal::Usize(3)]);
+        let times = entry.history();
+        assert!(times[0].applied_at <= times[1].applied_at);
+
+        // Only the most recent values are retained.
+        for val in 0..CONFIG_HISTORY_LEN + 1 {
+            apply(100 + val);
+        }
+        let expected = (1..CONFIG_HISTORY_LEN + 1)
+            .map(|val| ConfigVal::Usize(100 + val))
+            .collect::<Vec<_>>();
+        assert_eq!(history(), expected);
+
+        // The history is shared by clones of the set.
+        let clone = configs.clone();
+        assert_eq!(
+            clone.entry(USIZE.name()).unwrap().history().len(),
+            CONFIG_HISTORY_LEN
+        );
+    }
+
+    #[mz_ore::test]
+    fn update_callbacks() {
+        const FOO_A: Config<usize> = Config::new("foo_a", 0, "");
+        const FOO_B: Config<usize> = Config::new("foo_b", 0, "");
+        const BAR: Config<usize> = Config::new("bar", 0, "");
+
+        let configs = ConfigSet::default().add(&FOO_A).add(&FOO_B).add(&BAR);
+        let calls = Arc::new(Mutex::new(Vec::new()));
+        {
+            let calls = Arc::clone(&calls);
+            configs.register_update_callback("foo_", move |configs, changed| {
+                let sum = FOO_A.get(configs) + FOO_B.get(configs);
+                let changed = changed.iter().copied().collect::<Vec<_>>();
+                calls.lock().unwrap().push((changed, sum));
+            });
+        }
+
+        // A single call for all changed configs under the prefix.
+        let mut updates = ConfigUpdates::default();
+        updates.add(&FOO_A, 1);
+        updates.add(&FOO_B, 2);
+        updates.add(&BAR, 3);
+        updates.apply(&configs);
+        assert_eq!(*calls.lock().unwrap(), vec![(vec!["foo_a", "foo_b"], 3)]);
+
+        // No calls for unchanged configs or configs outside the prefix.
+        updates.apply(&configs);
+        let mut updates = ConfigUpdates::default();
+        updates.add(&BAR, 4);
+        updates.apply(&configs);
+        assert_eq!(calls.lock().unwrap().len(), 1);
+
+        // Callbacks are shared with clones of the set.
+        let mut updates = ConfigUpdates::default();
+        updates.add(&FOO_B, 5);
+        updates.apply(&configs.clone());
+        assert_eq!(calls.lock().unwrap().last(), Some(&(vec!["foo_b"], 6)));
+    }
+
     #[mz_ore::test]
     fn config_updates_extend() {
         // Regression test for #26196.
@@ -820,6 +3198,86 @@ mod tests {
         assert_eq!(USIZE.get(&c), 2);
     }
 
+    #[mz_ore::test]
+    fn config_updates_delta() {
+        let sender = ConfigSet::default().add(&USIZE).add(&STRING).add(&BOOL);
+        let receiver = ConfigSet::default().add(&USIZE).add(&STRING);
+        let mut updates = ConfigUpdates::default();
+        updates.add(&USIZE, 2);
+        updates.apply(&sender);
+
+        let mut full = ConfigUpdates::default();
+        for e in sender.entries() {
+            full.add_dynamic(e.name(), e.val());
+        }
+        let full = full.with_source("test");
+
+        // Only the changed value and the config unknown to the receiver are
+        // left.
+        let delta = full.delta(&receiver.digest());
+        assert_eq!(
+            delta.updates.keys().collect::<Vec<_>>(),
+            vec!["bool", "usize"]
+        );
+        assert_eq!(delta.source, "test");
+        delta.apply(&receiver);
+        assert_eq!(USIZE.get(&receiver), 2);
+        assert_eq!(STRING.get(&receiver), "a");
+
+        // Once in sync, nothing is left.
+        let delta = full.delta(&sender.digest());
+        assert!(delta.updates.is_empty());
+
+        // A confirmation is left out along with its unchanged update.
+        const DANGEROUS: Config<usize> = Config::new_dangerous("dangerous", 1, "");
+        let receiver = ConfigSet::default().add(&DANGEROUS);
+        let mut updates = ConfigUpdates::default();
+        updates.add(&DANGEROUS, 1);
+        updates.confirm(&DANGEROUS);
+        assert!(updates.delta(&receiver.digest()).updates.is_empty());
+        let mut updates = ConfigUpdates::default();
+        updates.add(&DANGEROUS, 2);
+        updates.confirm(&DANGEROUS);
+        assert_eq!(updates.delta(&receiver.digest()).updates.len(), 2);
+    }
+
+    #[mz_ore::test]
+    fn consistency() {
+        let envd = ConfigSet::default().add(&USIZE).add(&STRING);
+        let clusterd = ConfigSet::default().add(&USIZE).add(&BOOL);
+        let snapshots = |envd: &ConfigSet, clusterd: &ConfigSet| {
+            check_consistency([
+                ("envd", &ConfigUpdates::snapshot(envd)),
+                ("clusterd", &ConfigUpdates::snapshot(clusterd)),
+            ])
+        };
+
+        // Configs registered in only one process are not inconsistent.
+        assert_eq!(snapshots(&envd, &clusterd), vec![]);
+
+        let mut updates = ConfigUpdates::default();
+        updates.add(&USIZE, 2);
+        updates.apply(&envd);
+        assert_eq!(
+            snapshots(&envd, &clusterd),
+            vec![ConfigInconsistency {
+                name: "usize".into(),
+                values: BTreeMap::from([
+                    ("clusterd".into(), ConfigVal::Usize(1)),
+                    ("envd".into(), ConfigVal::Usize(2)),
+                ]),
+            }]
+        );
+
+        updates.apply(&clusterd);
+        assert_eq!(snapshots(&envd, &clusterd), vec![]);
+
+        // Snapshots survive being served as JSON.
+        let json = serde_json::to_string(&ConfigUpdates::snapshot(&envd)).unwrap();
+        let snapshot: ConfigUpdates = serde_json::from_str(&json).unwrap();
+        assert_eq!(snapshot, ConfigUpdates::snapshot(&envd));
+    }
+
     #[mz_ore::test]
     fn config_parse() {
         assert_eq!(BOOL.parse_val("true"), Ok(ConfigVal::Bool(true)));
@@ -838,6 +3296,13 @@ mod tests {
         assert_err!(U32.parse_val(""));
         assert_err!(U32.parse_val("5 s"));
 
+        assert_err!(I64.parse_val("true"));
+        assert_eq!(I64.parse_val("42"), Ok(ConfigVal::I64(42)));
+        assert_eq!(I64.parse_val("-42"), Ok(ConfigVal::I64(-42)));
+        assert_err!(I64.parse_val("66.6"));
+        assert_err!(I64.parse_val("farragut"));
+        assert_err!(I64.parse_val(""));
+
         assert_err!(USIZE.parse_val("true"));
         assert_err!(USIZE.parse_val("false"));
         assert_eq!(USIZE.parse_val("42"), Ok(ConfigVal::Usize(42)));
@@ -899,6 +3364,20 @@ mod tests {
             Ok(ConfigVal::Duration(Duration::from_secs(5)))
         );
 
+        assert_err!(BYTES.parse_val("true"));
+        assert_err!(BYTES.parse_val("false"));
+        assert_eq!(BYTES.parse_val("42"), Ok(ConfigVal::Bytes(ByteSize(42))));
+        assert_err!(BYTES.parse_val("farragut"));
+        assert_err!(BYTES.parse_val(""));
+        assert_eq!(
+            BYTES.parse_val("512MiB"),
+            Ok(ConfigVal::Bytes(ByteSize::mib(512)))
+        );
+        assert_eq!(
+            BYTES.parse_val("2 KB"),
+            Ok(ConfigVal::Bytes(ByteSize::kb(2)))
+        );
+
         assert_eq!(
             JSON.parse_val("true"),
             Ok(ConfigVal::Json(serde_json::json!(true)))
@@ -923,4 +3402,178 @@ mod tests {
             Ok(ConfigVal::Json(serde_json::json!({"joe": "developer"})))
         );
     }
+
+    #[mz_ore::test]
+    fn config_snapshot() {
+        config_snapshot! {
+            struct Knobs {
+                bool: bool = BOOL,
+                usize: usize = USIZE,
+                string: String = STRING,
+            }
+        }
+
+        let configs = Knobs::add_configs(ConfigSet::default());
+        let knobs = Knobs::load(&configs);
+        assert_eq!(knobs.bool, true);
+        assert_eq!(knobs.usize, 1);
+        assert_eq!(knobs.string, "a");
+
+        // A snapshot is unaffected by later updates.
+        let mut updates = ConfigUpdates::default();
+        updates.add(&USIZE, 2);
+        updates.apply(&configs);
+        assert_eq!(knobs.usize, 1);
+        assert_eq!(Knobs::load(&configs).usize, 2);
+    }
+
+    #[mz_ore::test]
+    fn config_value_parser() {
+        let parser = ConfigValueParser;
+        for input in ["on", "YES", " true ", "1", "t"] {
+            assert_eq!(parser.parse_typed(&BOOL, input), Ok(true));
+        }
+        for input in ["off", "No", "false", "0", "F"] {
+            assert_eq!(parser.parse_typed(&BOOL, input), Ok(false));
+        }
+        assert_eq!(parser.parse_typed(&USIZE, "1_000_000"), Ok(1_000_000));
+        assert_eq!(parser.parse_typed(&U32, " 7 "), Ok(7));
+        assert_eq!(parser.parse_typed(&I64, "-1_000"), Ok(-1_000));
+        assert_eq!(parser.parse_typed(&OPT_USIZE, "none"), Ok(None));
+        assert_eq!(parser.parse_typed(&OPT_USIZE, ""), Ok(None));
+        assert_eq!(parser.parse_typed(&OPT_USIZE, "8"), Ok(Some(8)));
+        assert_eq!(
+            parser.parse_typed(&DURATION, "1m 30s"),
+            Ok(Duration::from_secs(90))
+        );
+        assert_eq!(parser.parse_typed(&BYTES, "2 KiB"), Ok(ByteSize::kib(2)));
+        assert_eq!(parser.parse_typed(&STRING, " a b "), Ok(" a b ".to_owned()));
+        assert_eq!(
+            parser.parse_typed(&JSON, "{\"a\": 1}"),
+            Ok(serde_json::json!({"a": 1}))
+        );
+
+        let err = parser.parse_typed(&BOOL, "maybe").unwrap_err();
+        assert_eq!(err.name, "bool");
+        assert_eq!(err.expected, "bool");
+        assert_eq!(err.input, "maybe");
+        assert_err!(parser.parse_typed(&USIZE, "-1"));
+        assert_err!(parser.parse_typed(&DURATION, "30"));
+    }
+
+    proptest::proptest! {
+        #[mz_ore::test]
+        #[cfg_attr(miri, ignore)] // too slow
+        fn config_value_parser_never_panics(input in proptest::prelude::any::<String>()) {
+            let configs = ConfigSet::default()
+                .add(&BOOL)
+                .add(&U32)
+                .add(&I64)
+                .add(&USIZE)
+                .add(&OPT_USIZE)
+                .add(&F64)
+                .add(&STRING)
+                .add(&DURATION)
+                .add(&BYTES)
+                .add(&JSON);
+            for config in configs.entries() {
+                let _ = ConfigValueParser.parse(config, &input);
+            }
+        }
+    }
+
+    #[mz_ore::test]
+    fn load_updates() {
+        let configs = ConfigSet::default().add(&BOOL).add(&USIZE).add(&DURATION);
+
+        let updates = ConfigUpdates::parse_lines(
+            &configs,
+            "# a comment\n\nbool = false\nusize=7\nusize=8\nduration=1s\n",
+        )
+        .unwrap();
+        updates.apply(&configs);
+        assert_eq!(BOOL.get(&configs), false);
+        assert_eq!(USIZE.get(&configs), 8);
+        assert_eq!(DURATION.get(&configs), Duration::from_secs(1));
+
+        let err =
+            ConfigUpdates::parse_lines(&configs, "bool=true\nusize=x\nfoo=1\nbar\n").unwrap_err();
+        assert_eq!(
+            err.invalid.keys().collect::<Vec<_>>(),
+            vec!["bar", "foo", "usize"]
+        );
+        assert_eq!(err.invalid["foo"], InvalidConfigUpdate::Unknown);
+        assert_eq!(BOOL.get(&configs), false);
+
+        // Use a prefix unique to this test, as the environment is shared by
+        // all tests in the process.
+        std::env::set_var("MZ_DYNCFG_LOAD_UPDATES_TEST_USIZE", "9");
+        let updates = ConfigUpdates::from_env(&configs, "MZ_DYNCFG_LOAD_UPDATES_TEST_").unwrap();
+        updates.apply(&configs);
+        assert_eq!(USIZE.get(&configs), 9);
+        assert_eq!(BOOL.get(&configs), false);
+
+        std::env::set_var("MZ_DYNCFG_LOAD_UPDATES_TEST_BOOL", "maybe");
+        let err = ConfigUpdates::from_env(&configs, "MZ_DYNCFG_LOAD_UPDATES_TEST_").unwrap_err();
+        assert_eq!(
+            err.invalid.keys().collect::<Vec<_>>(),
+            vec!["MZ_DYNCFG_LOAD_UPDATES_TEST_BOOL"]
+        );
+
+        // Tests must stay isolated from each other, so none installs the
+        // global set.
+        assert!(ConfigSet::try_global().is_none());
+    }
+
+    #[mz_ore::test]
+    fn env_overrides() {
+        const DANGEROUS: Config<usize> = Config::new_dangerous("dangerous", 1, "");
+        const BOUNDED: Config<Duration> = Config::new_secs("bounded", 5, "")
+            .with_bounds(Duration::from_secs(1), Duration::from_secs(10));
+        let configs = || {
+            ConfigSet::default()
+                .add(&BOOL)
+                .add(&USIZE)
+                .add(&DANGEROUS)
+                .add(&BOUNDED)
+        };
+
+        // Use a prefix unique to this test, as the environment is shared by
+        // all tests in the process.
+        const PREFIX: &str = "MZ_DYNCFG_ENV_OVERRIDES_TEST_";
+        std::env::set_var("MZ_DYNCFG_ENV_OVERRIDES_TEST_USIZE", "9");
+        std::env::set_var("MZ_DYNCFG_ENV_OVERRIDES_TEST_DANGEROUS", "3");
+        let set = configs().with_env_overrides(PREFIX).unwrap();
+        assert_eq!(USIZE.get(&set), 9);
+        // Overrides of dangerous configs are applied without confirmation.
+        assert_eq!(DANGEROUS.get(&set), 3);
+        assert_eq!(BOOL.get(&set), true);
+        assert_eq!(set.frozen(), BTreeSet::from(["dangerous", "usize"]));
+
+        // Later updates to overridden configs are skipped.
+        let mut updates = ConfigUpdates::default();
+        updates.add(&USIZE, 10);
+        updates.add(&BOOL, false);
+        updates.apply_strict(&set).unwrap();
+        assert_eq!(USIZE.get(&set), 9);
+        assert_eq!(BOOL.get(&set), false);
+
+        // Overrides survive a merge.
+        let merged = ConfigSet::default().add(&BOOL).merge(set.clone()).unwrap();
+        assert_eq!(merged.frozen(), BTreeSet::from(["dangerous", "usize"]));
+
+        // Invalid overrides are reported by variable and none are applied.
+        std::env::set_var("MZ_DYNCFG_ENV_OVERRIDES_TEST_BOUNDED", "1h");
+        let err = configs().with_env_overrides(PREFIX).unwrap_err();
+        assert_eq!(
+            err.invalid,
+            BTreeMap::from([(
+                "MZ_DYNCFG_ENV_OVERRIDES_TEST_BOUNDED".to_owned(),
+                InvalidConfigUpdate::OutOfBounds {
+                    min: Duration::from_secs(1),
+                    max: Duration::from_secs(10),
+                }
+            )])
+        );
+    }
 }
@@ -12,15 +12,19 @@
 //! It listens for SQL connections on port 6875 (MTRL) and for HTTP connections
 //! on port 6876.
 
+use std::collections::BTreeMap;
 use std::ffi::CStr;
 use std::net::{IpAddr, Ipv4Addr, SocketAddr};
-use std::path::PathBuf;
+use std::num::{NonZeroU64, NonZeroUsize};
+use std::path::{Path, PathBuf};
+use std::process::ExitCode;
 use std::sync::atomic::{AtomicUsize, Ordering};
 use std::sync::Arc;
 use std::time::{Duration, Instant};
 use std::{cmp, env, iter, thread};
 
 use anyhow::{bail, Context};
+use bytesize::ByteSize;
 use clap::{ArgEnum, Parser};
 use fail::FailScenario;
 use http::header::HeaderValue;
@@ -41,7 +45,13 @@ use mz_orchestrator_kubernetes::{
     KubernetesImagePullPolicy, KubernetesOrchestrator, KubernetesOrchestratorConfig,
 };
 use mz_orchestrator_process::{
-    ProcessOrchestrator, ProcessOrchestratorConfig, ProcessOrchestratorTcpProxyConfig,
+    ClockOffset, ProcessOrchestrator, ProcessOrchestratorCleanupConfig,
+    ProcessOrchestratorClockSkewConfig, ProcessOrchestratorConfig, ProcessOrchestratorCpuAffinity,
+    ProcessOrchestratorCrashDebugConfig, ProcessOrchestratorCrashLoopConfig,
+    ProcessOrchestratorDebugger, ProcessOrchestratorDiskLimitEnforcement,
+    ProcessOrchestratorProcessGroupConfig, ProcessOrchestratorProfilingConfig,
+    ProcessOrchestratorSidecarConfig, ProcessOrchestratorSignalOrder, ProcessOrchestratorSshConfig,
+    ProcessOrchestratorTcpProxyConfig, ProcessOrchestratorTcpProxyShapingConfig,
 };
 use mz_orchestrator_tracing::{StaticTracingConfig, TracingCliArgs, TracingOrchestrator};
 use mz_ore::cli::{self, CliConfig, KeyValueArg};
@@ -62,6 +72,7 @@ use mz_service::emit_boot_diagnostics;
 use mz_service::secrets::{SecretsControllerKind, SecretsReaderCliArgs};
 use mz_sql::catalog::EnvironmentId;
 use mz_storage_types::connections::ConnectionContext;
+use nix::sys::signal::Signal;
 use once_cell::sync::Lazy;
 use opentelemetry::trace::TraceContextExt;
 use prometheus::IntGauge;
@@ -271,6 +282,12 @@ pub struct Args {
     /// The optional fs group for service's pods' `securityContext`.
     #[clap(long, env = "ORCHESTRATOR_KUBERNETES_SERVICE_FS_GROUP")]
     orchestrator_kubernetes_service_fs_group: Option<i64>,
+    /// A command to wrap the invocation of the processes launched by the
+    /// process orchestrator in.
+    ///
+    /// Wrapped processes are executed by the wrapper, so `ps` and `top` show
+    /// them under their image rather than their service name, e.g.,
+    /// `cluster-u1-replica-u1-0`, unless the wrapper renames them itself.
     #[clap(long, env = "ORCHESTRATOR_PROCESS_WRAPPER")]
     orchestrator_process_wrapper: Option<String>,
     /// Where the process orchestrator should store secrets.
@@ -285,6 +302,11 @@ pub struct Args {
     /// processes by crashing the parent process.
     #[clap(long, env = "ORCHESTRATOR_PROCESS_PROPAGATE_CRASHES")]
     orchestrator_process_propagate_crashes: bool,
+    /// Whether the process orchestrator should attach the output of child
+    /// processes to a pseudo-terminal, so that they color and line buffer
+    /// their output as if run directly from a shell.
+    #[clap(long, env = "ORCHESTRATOR_PROCESS_PTY")]
+    orchestrator_process_pty: bool,
     /// An IP address on which the process orchestrator should bind TCP proxies
     /// for Unix domain sockets.
     ///
@@ -315,6 +337,33 @@ pub struct Args {
         env = "ORCHESTRATOR_PROCESS_PROMETHEUS_SERVICE_DISCOVERY_DIRECTORY"
     )]
     orchestrator_process_prometheus_service_discovery_directory: Option<PathBuf>,
+    /// The maximum rate, in bytes per second, at which the TCP proxies of the
+    /// services in a namespace should forward data, in the form
+    /// `NAMESPACE=BYTES` (e.g., `cluster=1MiB`).
+    ///
+    /// The limit applies to each direction of each connection separately. A
+    /// limit of zero means unlimited.
+    ///
+    /// This option is ignored unless
+    /// `--orchestrator-process-tcp-proxy-listen-addr` is set.
+    #[clap(
+        long,
+        env = "ORCHESTRATOR_PROCESS_TCP_PROXY_RATE_LIMIT",
+        use_value_delimiter = true
+    )]
+    orchestrator_process_tcp_proxy_rate_limit: Vec<KeyValueArg<String, ByteSize>>,
+    /// The latency that the TCP proxies of the services in a namespace should
+    /// inject into all forwarded data, in the form `NAMESPACE=DURATION` (e.g.,
+    /// `cluster=50ms`).
+    ///
+    /// This option is ignored unless
+    /// `--orchestrator-process-tcp-proxy-listen-addr` is set.
+    #[clap(
+        long,
+        env = "ORCHESTRATOR_PROCESS_TCP_PROXY_LATENCY",
+        use_value_delimiter = true
+    )]
+    orchestrator_process_tcp_proxy_latency: Vec<KeyValueArg<String, humantime::Duration>>,
     /// A scratch directory that orchestrated processes can use for ephemeral storage.
     #[clap(
         long,
@@ -322,6 +371,225 @@ pub struct Args {
         value_name = "PATH"
     )]
     orchestrator_process_scratch_directory: Option<PathBuf>,
+    /// **Experimental.** Remote hosts, in `[user@]host` form, on which the
+    /// process orchestrator should launch the services of the namespaces
+    /// specified by `--orchestrator-process-ssh-namespace`.
+    ///
+    /// Processes are assigned to hosts round-robin by ordinal. Images are
+    /// synced to the hosts with `rsync` and launched via `ssh`, which forwards
+    /// the services' Unix domain sockets back to the local machine.
+    #[clap(
+        long,
+        env = "ORCHESTRATOR_PROCESS_SSH_HOST",
+        use_value_delimiter = true,
+        requires = "orchestrator_process_ssh_remote_directory"
+    )]
+    orchestrator_process_ssh_host: Vec<String>,
+    /// The directory on the hosts specified by
+    /// `--orchestrator-process-ssh-host` in which to store images and run
+    /// directories.
+    #[clap(
+        long,
+        env = "ORCHESTRATOR_PROCESS_SSH_REMOTE_DIRECTORY",
+        value_name = "PATH"
+    )]
+    orchestrator_process_ssh_remote_directory: Option<PathBuf>,
+    /// The orchestrator namespaces whose services should be launched on the
+    /// hosts specified by `--orchestrator-process-ssh-host`.
+    #[clap(
+        long,
+        env = "ORCHESTRATOR_PROCESS_SSH_NAMESPACE",
+        use_value_delimiter = true,
+        default_value = "cluster"
+    )]
+    orchestrator_process_ssh_namespace: Vec<String>,
+    /// Whether the process orchestrator should remove the metadata directories
+    /// left behind by other environments on startup.
+    ///
+    /// Service run directories are only removed if none of their processes
+    /// are alive and they have not been modified for at least
+    /// `--orchestrator-process-stale-metadata-min-age`.
+    #[clap(long, env = "ORCHESTRATOR_PROCESS_CLEANUP_STALE_METADATA")]
+    orchestrator_process_cleanup_stale_metadata: bool,
+    /// The minimum age of a stale metadata directory before the process
+    /// orchestrator will remove it.
+    #[clap(
+        long,
+        env = "ORCHESTRATOR_PROCESS_STALE_METADATA_MIN_AGE",
+        parse(try_from_str = humantime::parse_duration),
+        default_value = "1h"
+    )]
+    orchestrator_process_stale_metadata_min_age: Duration,
+    /// Only log the stale metadata directories that the process orchestrator
+    /// would remove, rather than removing them.
+    #[clap(long, env = "ORCHESTRATOR_PROCESS_STALE_METADATA_DRY_RUN")]
+    orchestrator_process_stale_metadata_dry_run: bool,
+    /// CPUs to which the process orchestrator should pin all processes, as a
+    /// comma-separated list of CPU indices.
+    #[clap(
+        long,
+        env = "ORCHESTRATOR_PROCESS_CPU_AFFINITY",
+        use_value_delimiter = true,
+        conflicts_with = "orchestrator_process_cpus_per_process"
+    )]
+    orchestrator_process_cpu_affinity: Vec<usize>,
+    /// Partition the machine's CPUs into disjoint sets of this many CPUs and
+    /// have the process orchestrator pin each process to its own set.
+    #[clap(long, env = "ORCHESTRATOR_PROCESS_CPUS_PER_PROCESS")]
+    orchestrator_process_cpus_per_process: Option<NonZeroUsize>,
+    /// How the process orchestrator should enforce the disk limits of
+    /// services, by backing their scratch directories with a size-capped
+    /// filesystem. Requires the privileges to mount filesystems.
+    #[clap(long, arg_enum, env = "ORCHESTRATOR_PROCESS_DISK_LIMIT_ENFORCEMENT")]
+    orchestrator_process_disk_limit_enforcement: Option<DiskLimitEnforcementKind>,
+    /// The offset the process orchestrator should apply to the wall clocks of
+    /// the processes of a service, in the form `SERVICE=OFFSET`, where
+    /// `SERVICE` is either the full ID of a service or a namespace (e.g.,
+    /// `cluster=-500ms` or `cluster-u1-replica-u1-gen-0=+2s`).
+    ///
+    /// Clocks are offset with libfaketime, see
+    /// `--orchestrator-process-libfaketime`.
+    #[clap(
+        long,
+        env = "ORCHESTRATOR_PROCESS_CLOCK_SKEW",
+        use_value_delimiter = true
+    )]
+    orchestrator_process_clock_skew: Vec<KeyValueArg<String, ClockOffset>>,
+    /// The path to the libfaketime shared library, which the process
+    /// orchestrator preloads into processes whose clocks are offset.
+    ///
+    /// This option is ignored unless `--orchestrator-process-clock-skew` is
+    /// set.
+    #[clap(
+        long,
+        env = "ORCHESTRATOR_PROCESS_LIBFAKETIME",
+        value_name = "PATH",
+        default_value = "/usr/lib/x86_64-linux-gnu/faketime/libfaketime.so.1"
+    )]
+    orchestrator_process_libfaketime: PathBuf,
+    /// A sidecar that the process orchestrator should run alongside each
+    /// process of the services in a namespace, in the form
+    /// `NAMESPACE=COMMAND` (e.g., `cluster=tcpdump -i lo -w %N.pcap`).
+    ///
+    /// The command is split into words like a shell would and supports the
+    /// same `%N` and `%P:<port>` directives as
+    /// `--orchestrator-process-wrapper`. May be specified multiple times.
+    #[clap(long, env = "ORCHESTRATOR_PROCESS_SIDECAR")]
+    orchestrator_process_sidecar: Vec<KeyValueArg<String, String>>,
+    /// The command with which the process orchestrator should capture a
+    /// profile of a process that exceeds a profiling threshold (e.g.,
+    /// `sh -c 'perf record -g -p $MZ_PROCESS_PID -o $MZ_PROFILE_PATH -- sleep 30'`).
+    ///
+    /// The command is split into words like a shell would. Profiles are only
+    /// captured if this option and at least one of
+    /// `--orchestrator-process-profiling-memory-threshold` and
+    /// `--orchestrator-process-profiling-cpu-threshold` are set.
+    #[clap(long, env = "ORCHESTRATOR_PROCESS_PROFILING_COMMAND")]
+    orchestrator_process_profiling_command: Option<String>,
+    /// The resident memory above which the process orchestrator should
+    /// profile a process.
+    #[clap(long, env = "ORCHESTRATOR_PROCESS_PROFILING_MEMORY_THRESHOLD")]
+    orchestrator_process_profiling_memory_threshold: Option<ByteSize>,
+    /// The CPU usage, in cores, above which the process orchestrator should
+    /// profile a process.
+    #[clap(long, env = "ORCHESTRATOR_PROCESS_PROFILING_CPU_THRESHOLD")]
+    orchestrator_process_profiling_cpu_threshold: Option<f64>,
+    /// How long a process must exceed a profiling threshold before the
+    /// process orchestrator profiles it.
+    #[clap(
+        long,
+        env = "ORCHESTRATOR_PROCESS_PROFILING_SUSTAIN",
+        parse(try_from_str = humantime::parse_duration),
+        default_value = "30s"
+    )]
+    orchestrator_process_profiling_sustain: Duration,
+    /// The minimum time between two profiles of the same process.
+    #[clap(
+        long,
+        env = "ORCHESTRATOR_PROCESS_PROFILING_MIN_INTERVAL",
+        parse(try_from_str = humantime::parse_duration),
+        default_value = "10m"
+    )]
+    orchestrator_process_profiling_min_interval: Duration,
+    /// The number of times a process may be relaunched within
+    /// `--orchestrator-process-crash-loop-window` before the process
+    /// orchestrator stops relaunching it.
+    ///
+    /// The process is relaunched again when its service is next ensured,
+    /// e.g., when environmentd restarts. If unset, crash-looping processes are
+    /// relaunched forever.
+    #[clap(long, env = "ORCHESTRATOR_PROCESS_CRASH_LOOP_MAX_RESTARTS")]
+    orchestrator_process_crash_loop_max_restarts: Option<usize>,
+    /// The window over which `--orchestrator-process-crash-loop-max-restarts`
+    /// counts relaunches.
+    #[clap(
+        long,
+        env = "ORCHESTRATOR_PROCESS_CRASH_LOOP_WINDOW",
+        parse(try_from_str = humantime::parse_duration),
+        default_value = "5m"
+    )]
+    orchestrator_process_crash_loop_window: Duration,
+    /// The debugger with which the process orchestrator should open the core
+    /// dumps of crashed processes.
+    ///
+    /// When set, the exact command that opens the core dump of a crashed
+    /// process is logged. Core dumps must be enabled, e.g., with
+    /// `ulimit -c unlimited`.
+    #[clap(long, arg_enum, env = "ORCHESTRATOR_PROCESS_CRASH_DEBUGGER")]
+    orchestrator_process_crash_debugger: Option<DebuggerKind>,
+    /// Whether the process orchestrator should capture the backtraces of all
+    /// threads of a crashed process into the run directory of its service.
+    /// Requires `--orchestrator-process-crash-debugger`.
+    #[clap(long, env = "ORCHESTRATOR_PROCESS_CRASH_BACKTRACES")]
+    orchestrator_process_crash_backtraces: bool,
+    /// How long the process orchestrator should hold back the relaunch of a
+    /// crashed process, to allow inspecting its run directory. Requires
+    /// `--orchestrator-process-crash-debugger`.
+    #[clap(
+        long,
+        env = "ORCHESTRATOR_PROCESS_CRASH_HOLD",
+        parse(try_from_str = humantime::parse_duration),
+        default_value = "0s"
+    )]
+    orchestrator_process_crash_hold: Duration,
+    /// Whether the process orchestrator should start each process in its own
+    /// process group.
+    ///
+    /// Terminating a process then terminates its whole group, including any
+    /// processes spawned by `--orchestrator-process-wrapper`. The process
+    /// orchestrator forwards the SIGINT and SIGTERM that environmentd receives
+    /// to all processes, shuts them down, and then exits environmentd.
+    #[clap(long, env = "ORCHESTRATOR_PROCESS_PROCESS_GROUPS")]
+    orchestrator_process_process_groups: bool,
+    /// The order in which the process orchestrator forwards signals to the
+    /// processes of each namespace. Requires
+    /// `--orchestrator-process-process-groups`.
+    #[clap(
+        long,
+        arg_enum,
+        env = "ORCHESTRATOR_PROCESS_SIGNAL_ORDER",
+        default_value = "reverse"
+    )]
+    orchestrator_process_signal_order: SignalOrderKind,
+    /// How long the process orchestrator waits for the processes of a
+    /// namespace to exit after forwarding a signal to them, before moving on
+    /// to the next namespace. Requires `--orchestrator-process-process-groups`.
+    #[clap(
+        long,
+        env = "ORCHESTRATOR_PROCESS_SIGNAL_GRACE_PERIOD",
+        parse(try_from_str = humantime::parse_duration),
+        default_value = "10s"
+    )]
+    orchestrator_process_signal_grace_period: Duration,
+    /// The orchestrator namespaces whose processes should run with the run
+    /// directory of their service as their working directory and with a
+    /// private `TMPDIR` under `--orchestrator-process-scratch-directory`.
+    #[clap(
+        long,
+        env = "ORCHESTRATOR_PROCESS_PRIVATE_DIRS_NAMESPACE",
+        use_value_delimiter = true
+    )]
+    orchestrator_process_private_dirs_namespace: Vec<String>,
     /// Whether to use coverage build and collect coverage information. Not to be used for
     /// production, only testing.
     #[structopt(long, env = "ORCHESTRATOR_KUBERNETES_COVERAGE")]
@@ -584,6 +852,25 @@ enum OrchestratorKind {
     Process,
 }
 
+#[derive(ArgEnum, Debug, Clone, Copy)]
+enum DiskLimitEnforcementKind {
+    Tmpfs,
+    LoopbackImage,
+}
+
+#[derive(ArgEnum, Debug, Clone, Copy)]
+enum DebuggerKind {
+    Gdb,
+    Lldb,
+}
+
+#[derive(ArgEnum, Debug, Clone, Copy)]
+enum SignalOrderKind {
+    Reverse,
+    Creation,
+    Concurrent,
+}
+
 // TODO [Alex Hunt] move this to a shared function that can be imported by the
 // region-controller.
 fn aws_secrets_controller_prefix(env_id: &EnvironmentId) -> String {
@@ -595,24 +882,33 @@ fn aws_secrets_controller_key_alias(env_id: &EnvironmentId) -> String {
     format!("alias/customer_key_{}", env_id)
 }
 
-fn main() {
+fn main() -> ExitCode {
     let args = cli::parse_args(CliConfig {
         env_prefix: Some("MZ_"),
         enable_version_flag: true,
     });
-    if let Err(err) = run(args) {
-        panic!("environmentd: fatal: {}", err.display_with_causes());
+    match run(args) {
+        Ok(exit_code) => exit_code,
+        Err(err) => panic!("environmentd: fatal: {}", err.display_with_causes()),
     }
 }
 
-fn run(mut args: Args) -> Result<(), anyhow::Error> {
+fn run(mut args: Args) -> Result<ExitCode, anyhow::Error> {
     mz_ore::panic::set_abort_on_panic();
     let envd_start = Instant::now();
 
     // Configure signal handling as soon as possible. We want signals to be
     // handled to our liking ASAP.
     sys::enable_sigusr2_coverage_dump()?;
-    sys::enable_termination_signal_cleanup()?;
+    // With process groups, the process orchestrator handles SIGINT and
+    // SIGTERM itself, and we exit once it has shut down its processes.
+    let orchestrator_signals = match args.orchestrator {
+        OrchestratorKind::Process if args.orchestrator_process_process_groups => {
+            &[Signal::SIGINT, Signal::SIGTERM][..]
+        }
+        _ => &[],
+    };
+    sys::enable_termination_signal_cleanup(orchestrator_signals)?;
 
     // Configure testing options.
     if let Some(fingerprint_whitespace) = args.unsafe_builtin_table_fingerprint_whitespace {
@@ -693,6 +989,9 @@ fn run(mut args: Args) -> Result<(), anyhow::Error> {
 
     // Configure controller.
     let entered = info_span!("environmentd::configure_controller").entered();
+    // Resolves once the process orchestrator has shut down its processes after
+    // forwarding them a signal, if it handles signals.
+    let mut signal_forwarded = None;
     let (orchestrator, secrets_controller, cloud_resource_controller): (
         Arc<dyn Orchestrator>,
         Arc<dyn SecretsController>,
@@ -773,6 +1072,45 @@ fn run(mut args: Args) -> Result<(), anyhow::Error> {
                       not usable with the process orchestrator"
                 );
             }
+            let mut tcp_proxy_shaping =
+                BTreeMap::<_, ProcessOrchestratorTcpProxyShapingConfig>::new();
+            for arg in args.orchestrator_process_tcp_proxy_rate_limit {
+                tcp_proxy_shaping.entry(arg.key).or_default().rate_limit =
+                    NonZeroU64::new(arg.value.as_u64());
+            }
+            for arg in args.orchestrator_process_tcp_proxy_latency {
+                tcp_proxy_shaping.entry(arg.key).or_default().latency = arg.value.into();
+            }
+            let mut sidecars = BTreeMap::<_, Vec<_>>::new();
+            for arg in args.orchestrator_process_sidecar {
+                let command = shell_words::split(&arg.value)?;
+                let Some(program) = command.first() else {
+                    bail!(
+                        "--orchestrator-process-sidecar for {} has no command",
+                        arg.key
+                    );
+                };
+                let name = Path::new(program).file_name().map_or_else(
+                    || program.clone(),
+                    |name| name.to_string_lossy().into_owned(),
+                );
+                sidecars
+                    .entry(arg.key)
+                    .or_default()
+                    .push(ProcessOrchestratorSidecarConfig { name, command });
+            }
+            let profiling = match args.orchestrator_process_profiling_command {
+                None => None,
+                Some(command) => Some(ProcessOrchestratorProfilingConfig {
+                    memory_threshold: args
+                        .orchestrator_process_profiling_memory_threshold
+                        .map(|threshold| threshold.as_u64()),
+                    cpu_threshold: args.orchestrator_process_profiling_cpu_threshold,
+                    sustain: args.orchestrator_process_profiling_sustain,
+                    min_interval: args.orchestrator_process_profiling_min_interval,
+                    command: shell_words::split(&command)?,
+                }),
+            };
             let orchestrator = Arc::new(
                 runtime
                     .block_on(ProcessOrchestrator::new(ProcessOrchestratorConfig {
@@ -783,6 +1121,7 @@ fn run(mut args: Args) -> Result<(), anyhow::Error> {
                         // binaries.
                         image_dir: env::current_exe()?.parent().unwrap().to_path_buf(),
                         suppress_output: false,
+                        pty: args.orchestrator_process_pty,
                         environment_id: args.environment_id.to_string(),
                         secrets_dir: args
                             .orchestrator_process_secrets_directory
@@ -797,11 +1136,104 @@ fn run(mut args: Args) -> Result<(), anyhow::Error> {
                                 listen_addr,
                                 prometheus_service_discovery_dir: args
                                     .orchestrator_process_prometheus_service_discovery_directory,
+                                shaping: tcp_proxy_shaping,
                             },
                         ),
                         scratch_directory: args
                             .orchestrator_process_scratch_directory
                             .expect("process orchestrator requires scratch directory"),
+                        ssh: match args.orchestrator_process_ssh_remote_directory {
+                            Some(remote_dir) if !args.orchestrator_process_ssh_host.is_empty() => {
+                                Some(ProcessOrchestratorSshConfig {
+                                    hosts: args.orchestrator_process_ssh_host,
+                                    remote_dir,
+                                    namespaces: args
+                                        .orchestrator_process_ssh_namespace
+                                        .into_iter()
+                                        .collect(),
+                                })
+                            }
+                            _ => None,
+                        },
+                        stale_metadata_cleanup: args
+                            .orchestrator_process_cleanup_stale_metadata
+                            .then(|| ProcessOrchestratorCleanupConfig {
+                                min_age: args.orchestrator_process_stale_metadata_min_age,
+                                dry_run: args.orchestrator_process_stale_metadata_dry_run,
+                            }),
+                        cpu_affinity: match args.orchestrator_process_cpus_per_process {
+                            Some(cpus_per_process) => {
+                                Some(ProcessOrchestratorCpuAffinity::Partitioned {
+                                    cpus_per_process,
+                                })
+                            }
+                            None if !args.orchestrator_process_cpu_affinity.is_empty() => {
+                                Some(ProcessOrchestratorCpuAffinity::Cpus(
+                                    args.orchestrator_process_cpu_affinity,
+                                ))
+                            }
+                            None => None,
+                        },
+                        disk_limit_enforcement: args
+                            .orchestrator_process_disk_limit_enforcement
+                            .map(|kind| match kind {
+                                DiskLimitEnforcementKind::Tmpfs => {
+                                    ProcessOrchestratorDiskLimitEnforcement::Tmpfs
+                                }
+                                DiskLimitEnforcementKind::LoopbackImage => {
+                                    ProcessOrchestratorDiskLimitEnforcement::LoopbackImage
+                                }
+                            }),
+                        launchers: BTreeMap::new(),
+                        clock_skew: (!args.orchestrator_process_clock_skew.is_empty()).then(|| {
+                            ProcessOrchestratorClockSkewConfig {
+                                libfaketime: args.orchestrator_process_libfaketime,
+                                offsets: args
+                                    .orchestrator_process_clock_skew
+                                    .into_iter()
+                                    .map(|arg| (arg.key, arg.value))
+                                    .collect(),
+                            }
+                        }),
+                        sidecars,
+                        profiling,
+                        crash_loop: args.orchestrator_process_crash_loop_max_restarts.map(
+                            |max_restarts| ProcessOrchestratorCrashLoopConfig {
+                                max_restarts,
+                                window: args.orchestrator_process_crash_loop_window,
+                            },
+                        ),
+                        crash_debugging: args.orchestrator_process_crash_debugger.map(|kind| {
+                            ProcessOrchestratorCrashDebugConfig {
+                                debugger: match kind {
+                                    DebuggerKind::Gdb => ProcessOrchestratorDebugger::Gdb,
+                                    DebuggerKind::Lldb => ProcessOrchestratorDebugger::Lldb,
+                                },
+                                capture_backtraces: args.orchestrator_process_crash_backtraces,
+                                hold: args.orchestrator_process_crash_hold,
+                            }
+                        }),
+                        process_groups: args.orchestrator_process_process_groups.then(|| {
+                            ProcessOrchestratorProcessGroupConfig {
+                                signal_order: match args.orchestrator_process_signal_order {
+                                    SignalOrderKind::Reverse => {
+                                        ProcessOrchestratorSignalOrder::Reverse
+                                    }
+                                    SignalOrderKind::Creation => {
+                                        ProcessOrchestratorSignalOrder::Creation
+                                    }
+                                    SignalOrderKind::Concurrent => {
+                                        ProcessOrchestratorSignalOrder::Concurrent
+                                    }
+                                },
+                                grace_period: args.orchestrator_process_signal_grace_period,
+                            }
+                        }),
+                        private_dirs: args
+                            .orchestrator_process_private_dirs_namespace
+                            .into_iter()
+                            .collect(),
+                        metrics_registry: metrics_registry.clone(),
                     }))
                     .context("creating process orchestrator")?,
             );
@@ -827,6 +1259,7 @@ fn run(mut args: Args) -> Result<(), anyhow::Error> {
                     sc
                 }
             };
+            signal_forwarded = Some(orchestrator.signal_forwarded());
             (orchestrator, secrets_controller, None)
         }
     };
@@ -1058,9 +1491,24 @@ fn run(mut args: Args) -> Result<(), anyhow::Error> {
 
     println!(" Root trace ID: {id}");
 
-    // Block forever.
-    loop {
-        thread::park();
+    match signal_forwarded {
+        Some(signal_forwarded) => {
+            let signal = runtime.block_on(signal_forwarded);
+            info!("exiting after {signal}");
+            // Exit with the status a shell reports for a process killed by the
+            // signal.
+            let code = match signal {
+                Signal::SIGINT => 128 + libc::SIGINT,
+                Signal::SIGTERM => 128 + libc::SIGTERM,
+                signal => unreachable!("only SIGINT and SIGTERM are forwarded, not {signal}"),
+            };
+            let code = u8::try_from(code).expect("signal numbers are small");
+            Ok(ExitCode::from(code))
+        }
+        // Block forever.
+        None => loop {
+            thread::park();
+        },
     }
 }
 
@@ -109,7 +109,9 @@ pub fn enable_sigusr2_coverage_dump() -> Result<(), anyhow::Error> {
     Ok(())
 }
 
-pub fn enable_termination_signal_cleanup() -> Result<(), anyhow::Error> {
+/// Installs the handler that writes out coverage data on termination signals,
+/// except for those in `exclude`, which are handled elsewhere.
+pub fn enable_termination_signal_cleanup(exclude: &[signal::Signal]) -> Result<(), anyhow::Error> {
     let action = signal::SigAction::new(
         signal::SigHandler::Handler(handle_termination_signal),
         signal::SaFlags::SA_NODEFER | signal::SaFlags::SA_ONSTACK,
@@ -123,6 +125,9 @@ pub fn enable_termination_signal_cleanup() -> Result<(), anyhow::Error> {
         signal::SIGTERM,
         signal::SIGUSR1,
     ] {
+        if exclude.contains(signum) {
+            continue;
+        }
         unsafe { signal::sigaction(*signum, &action) }
             .with_context(|| format!("failed to install handler for {}", signum))?;
     }
@@ -39,6 +39,7 @@ use hyper_openssl::SslStream;
 use hyper_util::rt::TokioIo;
 use mz_adapter::session::{Session, SessionConfig};
 use mz_adapter::{AdapterError, AdapterNotice, Client, SessionClient, WebhookAppenderCache};
+use mz_dyncfg::ConfigSet;
 use mz_frontegg_auth::{Authenticator as FronteggAuthentication, Error as FronteggError};
 use mz_http_util::DynamicFilterTarget;
 use mz_ore::cast::u64_to_usize;
@@ -247,6 +248,8 @@ pub struct InternalHttpConfig {
     pub active_connection_count: Arc<Mutex<ConnectionCounter>>,
     pub deployment_state_handle: DeploymentStateHandle,
     pub internal_console_redirect_url: Option<String>,
+    /// The dynamic configs of this process, served by `/api/dyncfg/snapshot`.
+    pub dyncfgs: Arc<ConfigSet>,
 }
 
 pub struct InternalHttpServer {
@@ -293,6 +296,7 @@ impl InternalHttpServer {
             active_connection_count,
             deployment_state_handle,
             internal_console_redirect_url,
+            dyncfgs,
         }: InternalHttpConfig,
     ) -> InternalHttpServer {
         let metrics = Metrics::register_into(&metrics_registry, "mz_internal_http");
@@ -341,6 +345,12 @@ impl InternalHttpServer {
                 }),
             )
             .route("/api/tracing", routing::get(mz_http_util::handle_tracing))
+            .route(
+                "/api/dyncfg/snapshot",
+                routing::get(move || async move {
+                    mz_http_util::handle_dyncfg_snapshot(&dyncfgs).await
+                }),
+            )
             .route(
                 "/api/catalog/dump",
                 routing::get(catalog::handle_catalog_dump),
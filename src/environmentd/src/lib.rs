@@ -321,6 +321,7 @@ impl Listeners {
                 active_connection_count: Arc::clone(&active_connection_count),
                 deployment_state_handle,
                 internal_console_redirect_url: config.internal_console_redirect_url,
+                dyncfgs: Arc::clone(&config.catalog_config.persist_clients.cfg().configs),
             });
             mz_server_core::serve(internal_http_conns, internal_http_server, None)
         });
@@ -7,7 +7,7 @@
 // the Business Source License, use of this software will be governed
 // by the Apache License, Version 2.0.
 
-use std::collections::BTreeMap;
+use std::collections::{BTreeMap, BTreeSet};
 use std::error::Error;
 use std::future::IntoFuture;
 use std::net::{IpAddr, Ipv4Addr, SocketAddr, TcpStream};
@@ -375,12 +375,26 @@ impl Listeners {
                 .unwrap()
                 .to_path_buf(),
             suppress_output: false,
+            pty: false,
             environment_id: config.environment_id.to_string(),
             secrets_dir: data_directory.join("secrets"),
             command_wrapper: vec![],
             propagate_crashes: config.propagate_crashes,
             tcp_proxy: None,
             scratch_directory: scratch_dir.path().to_path_buf(),
+            ssh: None,
+            stale_metadata_cleanup: None,
+            cpu_affinity: None,
+            disk_limit_enforcement: None,
+            launchers: BTreeMap::new(),
+            clock_skew: None,
+            sidecars: BTreeMap::new(),
+            profiling: None,
+            crash_loop: None,
+            crash_debugging: None,
+            process_groups: None,
+            private_dirs: BTreeSet::new(),
+            metrics_registry: metrics_registry.clone(),
         })
         .await?;
         let orchestrator = Arc::new(orchestrator);
@@ -26,6 +26,7 @@ use futures::FutureExt;
 use http::Request;
 use itertools::Itertools;
 use jsonwebtoken::{DecodingKey, EncodingKey};
+use mz_dyncfg::{check_consistency, ConfigInconsistency, ConfigUpdates, ConfigVal};
 use mz_environmentd::test_util::{self, make_pg_tls, Ca, PostgresErrorExt, KAFKA_ADDRS};
 use mz_environmentd::{WebSocketAuth, WebSocketResponse};
 use mz_frontegg_auth::{
@@ -1004,6 +1005,80 @@ fn test_cancel_insert_select() {
     test_cancellation_cancels_dataflows("INSERT INTO t WITH MUTUALLY RECURSIVE flip(x INTEGER) AS (VALUES(1) EXCEPT ALL SELECT * FROM flip) SELECT * FROM flip;");
 }
 
+// Test that pending peeks and their read holds are reported in
+// `mz_internal.mz_read_hold_waits` and `mz_internal.mz_read_holds`.
+#[mz_ore::test]
+#[cfg_attr(miri, ignore)] // too slow
+fn test_read_hold_waits_peek() {
+    const WAITS_QUERY: &str = " \
+        SELECT w.waiter, w.cluster_id IS NOT NULL, w.timestamp::text, w.holder IS NULL \
+        FROM mz_internal.mz_read_hold_waits w \
+        JOIN mz_internal.mz_read_holds h \
+          ON w.object_id = h.object_id AND w.waiter_connection_id = h.connection_id \
+        WHERE w.object_id = $1 AND w.waiter_connection_id::text = $2 AND h.holder = 'peek'";
+
+    let server = test_util::TestHarness::default().start_blocking();
+    let mut client1 = server.connect(postgres::NoTls).unwrap();
+    let mut client2 = server.connect(postgres::NoTls).unwrap();
+    let cancel_token = client1.cancel_token();
+
+    client1
+        .batch_execute("CREATE TABLE t (i INT); CREATE INDEX t_idx ON t (i)")
+        .unwrap();
+    let index_id: String = client1
+        .query_one("SELECT id FROM mz_indexes WHERE name = 't_idx'", &[])
+        .unwrap()
+        .get(0);
+    let pid: i32 = client1
+        .query_one("SELECT pg_backend_pid()", &[])
+        .unwrap()
+        .get(0);
+    let conn_id = u32::from_le_bytes(pid.to_le_bytes()).to_string();
+
+    // The peek is never answered, as its AS OF never becomes readable.
+    let handle =
+        thread::spawn(move || client1.simple_query("SELECT * FROM t AS OF 9223372036854775807"));
+
+    Retry::default()
+        .retry(|_state| {
+            let rows = client2
+                .query(WAITS_QUERY, &[&index_id, &conn_id])
+                .map_err(|_| ())?;
+            match &rows[..] {
+                [row] => {
+                    assert_eq!(row.get::<_, String>(0), "peek");
+                    assert!(row.get::<_, bool>(1));
+                    assert_eq!(row.get::<_, String>(2), "9223372036854775807");
+                    assert!(row.get::<_, bool>(3));
+                    Ok(())
+                }
+                _ => Err(()),
+            }
+        })
+        .unwrap();
+
+    cancel_token.cancel_query(postgres::NoTls).unwrap();
+    match handle.join().unwrap() {
+        Err(e) if e.code() == Some(&postgres::error::SqlState::QUERY_CANCELED) => {}
+        Err(e) => panic!("expected error SqlState::QUERY_CANCELED, but got {:?}", e),
+        Ok(_) => panic!("expected error SqlState::QUERY_CANCELED, but query succeeded"),
+    }
+
+    // The wait and the hold go away with the peek.
+    Retry::default()
+        .retry(|_state| {
+            let rows = client2
+                .query(WAITS_QUERY, &[&index_id, &conn_id])
+                .map_err(|_| ())?;
+            if rows.is_empty() {
+                Ok(())
+            } else {
+                Err(())
+            }
+        })
+        .unwrap();
+}
+
 fn test_closing_connection_cancels_dataflows(query: String) {
     // Query that returns how many dataflows are currently installed.
     // Accounts for the presence of introspection subscribe dataflows by ignoring those.
@@ -2370,6 +2445,62 @@ fn test_internal_http_auth() {
     assert_eq!(res.status(), StatusCode::UNAUTHORIZED, "{:?}", res.text());
 }
 
+#[mz_ore::test]
+#[cfg_attr(miri, ignore)] // too slow
+fn test_dyncfg_snapshot() {
+    let server = test_util::TestHarness::default().start_blocking();
+    let url = Url::parse(&format!(
+        "http://{}/api/dyncfg/snapshot",
+        server.inner().internal_http_local_addr()
+    ))
+    .unwrap();
+    let fetch_snapshot = || {
+        let res = Client::new().get(url.clone()).send().unwrap();
+        assert_eq!(res.status(), StatusCode::OK);
+        res.json::<ConfigUpdates>().unwrap()
+    };
+
+    // Snapshots of the same, unchanged configs agree.
+    let before = fetch_snapshot();
+    assert!(before
+        .updates
+        .contains_key("persist_sink_minimum_batch_updates"));
+    let again = fetch_snapshot();
+    assert_eq!(
+        check_consistency([("before", &before), ("again", &again)]),
+        vec![]
+    );
+
+    let mut mz_client = server
+        .pg_config_internal()
+        .user(&SYSTEM_USER.name)
+        .connect(postgres::NoTls)
+        .unwrap();
+    mz_client
+        .batch_execute("ALTER SYSTEM SET persist_sink_minimum_batch_updates = 42")
+        .unwrap();
+
+    // Once the change has been applied, the snapshots disagree on exactly the
+    // changed config.
+    let inconsistencies = Retry::default()
+        .max_duration(Duration::from_secs(30))
+        .retry(|_| {
+            let after = fetch_snapshot();
+            let inconsistencies = check_consistency([("before", &before), ("after", &after)]);
+            if inconsistencies.is_empty() {
+                Err("change not applied yet")
+            } else {
+                Ok(inconsistencies)
+            }
+        })
+        .unwrap();
+    let [ConfigInconsistency { name, values }] = &inconsistencies[..] else {
+        panic!("unexpected inconsistencies: {inconsistencies:?}");
+    };
+    assert_eq!(name, "persist_sink_minimum_batch_updates");
+    assert_eq!(values.get("after"), Some(&ConfigVal::Usize(42)));
+}
+
 #[mz_ore::test]
 #[cfg_attr(miri, ignore)] // too slow
 fn test_internal_ws_auth() {
@@ -16,6 +16,7 @@ use axum::response::{Html, IntoResponse};
 use axum::Json;
 use axum_extra::TypedHeader;
 use headers::ContentType;
+use mz_dyncfg::{ConfigSet, ConfigUpdates};
 use mz_ore::metrics::MetricsRegistry;
 use mz_ore::tracing::TracingHandle;
 use prometheus::Encoder;
@@ -113,6 +114,15 @@ pub async fn handle_prometheus(registry: &MetricsRegistry) -> impl IntoResponse
     Ok::<_, (StatusCode, String)>((TypedHeader(ContentType::text()), buffer))
 }
 
+/// Serves a snapshot of the current values of the configs in `set`.
+///
+/// Snapshots collected from multiple processes can be compared with
+/// [`mz_dyncfg::check_consistency`].
+#[allow(clippy::unused_async)]
+pub async fn handle_dyncfg_snapshot(set: &ConfigSet) -> impl IntoResponse {
+    (StatusCode::OK, Json(ConfigUpdates::snapshot(set)))
+}
+
 #[derive(Serialize, Deserialize)]
 pub struct DynamicFilterTarget {
     targets: String,
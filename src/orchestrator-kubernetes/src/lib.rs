@@ -42,8 +42,8 @@ use mz_cloud_resources::crd::vpc_endpoint::v1::VpcEndpoint;
 use mz_cloud_resources::AwsExternalIdPrefix;
 use mz_orchestrator::{
     scheduling_config::*, DiskLimit, LabelSelectionLogic, LabelSelector as MzLabelSelector,
-    NamespacedOrchestrator, OfflineReason, Orchestrator, Service, ServiceConfig, ServiceEvent,
-    ServiceProcessMetrics, ServiceStatus,
+    NamespacedOrchestrator, OfflineReason, Orchestrator, Service, ServiceAddressEvent,
+    ServiceConfig, ServiceEvent, ServiceProcessMetrics, ServiceStatus,
 };
 use mz_ore::retry::Retry;
 use mz_ore::task::AbortOnDropHandle;
@@ -1263,6 +1263,14 @@ impl NamespacedOrchestrator for NamespacedKubernetesOrchestrator {
         Box::pin(stream)
     }
 
+    fn watch_service_addresses(
+        &self,
+    ) -> BoxStream<'static, Result<ServiceAddressEvent, anyhow::Error>> {
+        // Service addresses are stable DNS names derived from the service
+        // name, so they never change.
+        Box::pin(futures::stream::pending())
+    }
+
     fn update_scheduling_config(&self, config: ServiceSchedulingConfig) {
         *self.scheduling_config.write().expect("poisoned") = config;
     }
@@ -7,14 +7,16 @@
 // the Business Source License, use of this software will be governed
 // by the Apache License, Version 2.0.
 
-use std::collections::BTreeMap;
+use std::collections::{BTreeMap, BTreeSet, VecDeque};
 use std::env;
 use std::ffi::OsStr;
 use std::fmt::Debug;
 use std::fs::Permissions;
 use std::future::Future;
 use std::net::{IpAddr, SocketAddr, TcpListener as StdTcpListener};
+use std::num::{NonZeroU64, NonZeroUsize};
 use std::os::unix::fs::PermissionsExt;
+use std::os::unix::io::{AsRawFd, FromRawFd};
 use std::os::unix::process::ExitStatusExt;
 use std::path::{Path, PathBuf};
 use std::pin::Pin;
@@ -30,25 +32,35 @@ use chrono::{DateTime, Utc};
 use futures::future;
 use futures::stream::{BoxStream, FuturesUnordered, TryStreamExt};
 use itertools::Itertools;
-use libc::{SIGABRT, SIGBUS, SIGILL, SIGSEGV, SIGTRAP};
+use libc::{SIGABRT, SIGBUS, SIGILL, SIGKILL, SIGSEGV, SIGTRAP};
 use maplit::btreemap;
 use mz_orchestrator::{
-    CpuLimit, MemoryLimit, NamespacedOrchestrator, Orchestrator, Service, ServiceConfig,
-    ServiceEvent, ServiceProcessMetrics, ServiceStatus,
+    CpuLimit, DiskLimit, MemoryLimit, NamespacedOrchestrator, OfflineReason, Orchestrator, Service,
+    ServiceAddressEvent, ServiceConfig, ServiceEvent, ServiceProcessMetrics, ServiceStatus,
 };
-use mz_ore::cast::{CastFrom, TryCastFrom};
+use mz_ore::cast::{CastFrom, CastLossy, TryCastFrom};
 use mz_ore::error::ErrorExt;
+use mz_ore::metric;
+use mz_ore::metrics::raw::{HistogramVec, IntCounterVec};
+use mz_ore::metrics::{IntCounter, MetricsRegistry};
 use mz_ore::netio::UnixSocketAddr;
 use mz_ore::result::ResultExt;
-use mz_ore::task::AbortOnDropHandle;
+use mz_ore::stats::histogram_seconds_buckets;
+use mz_ore::task::{AbortOnDropHandle, JoinHandleExt};
+use nix::fcntl::{fcntl, FcntlArg, FdFlag};
+use nix::pty::{openpty, OpenptyResult};
+use nix::sys::signal::{kill, killpg, Signal};
+use nix::sys::termios::{self, OutputFlags, SetArg};
 use scopeguard::defer;
 use serde::Serialize;
 use sha1::{Digest, Sha1};
 use sysinfo::{Pid, PidExt, Process, ProcessExt, ProcessRefreshKind, System, SystemExt};
 use tokio::fs::remove_dir_all;
+use tokio::io::{AsyncRead, AsyncReadExt, AsyncWrite, AsyncWriteExt};
 use tokio::net::{TcpListener, UnixStream};
 use tokio::process::{Child, Command};
-use tokio::sync::{broadcast, mpsc, oneshot};
+use tokio::signal::unix::{signal as unix_signal, Signal as UnixSignal, SignalKind};
+use tokio::sync::{broadcast, mpsc, oneshot, watch};
 use tokio::time::{self, Duration};
 use tokio::{fs, io, select};
 use tracing::{debug, error, info, warn};
@@ -63,11 +75,19 @@ pub struct ProcessOrchestratorConfig {
     pub image_dir: PathBuf,
     /// Whether to supress output from spawned subprocesses.
     pub suppress_output: bool,
+    /// Whether to attach the output of spawned subprocesses to a
+    /// pseudo-terminal, so that they color and line buffer their output as
+    /// when run from a shell. Ignored when `suppress_output` is set.
+    pub pty: bool,
     /// The ID of the environment under orchestration.
     pub environment_id: String,
     /// The directory in which to store secrets.
     pub secrets_dir: PathBuf,
     /// A command to wrap the child command invocation
+    ///
+    /// The wrapper executes the image of a process by its path, so wrapped
+    /// processes are not shown under their [name](ProcessLaunch::name) by
+    /// tools like `ps`, unless the wrapper renames them itself.
     pub command_wrapper: Vec<String>,
     /// Whether to crash this process if a child process crashes.
     pub propagate_crashes: bool,
@@ -84,6 +104,382 @@ pub struct ProcessOrchestratorConfig {
     pub tcp_proxy: Option<ProcessOrchestratorTcpProxyConfig>,
     /// A scratch directory that orchestrated processes can use for ephemeral storage.
     pub scratch_directory: PathBuf,
+    /// Remote execution configuration.
+    ///
+    /// **Experimental.** When enabled, the processes of services in the
+    /// configured namespaces are launched on remote hosts over SSH rather than
+    /// on the local machine. Remote processes only support the options below
+    /// that affect how processes are launched and supervised: the
+    /// pseudo-terminal, clock skew, sidecars, profiling, crash debugging,
+    /// process groups, private directories, and disk limit enforcement only
+    /// apply to local processes.
+    pub ssh: Option<ProcessOrchestratorSshConfig>,
+    /// Stale metadata cleanup configuration.
+    ///
+    /// When enabled, the orchestrator removes the on-disk state left behind
+    /// by the process orchestrators of other environments on startup.
+    pub stale_metadata_cleanup: Option<ProcessOrchestratorCleanupConfig>,
+    /// CPU affinity configuration.
+    ///
+    /// When enabled, each process is pinned to a set of CPUs.
+    pub cpu_affinity: Option<ProcessOrchestratorCpuAffinity>,
+    /// Disk limit enforcement configuration.
+    ///
+    /// When enabled, the scratch directory of each service with a disk limit
+    /// is backed by a filesystem of that size.
+    pub disk_limit_enforcement: Option<ProcessOrchestratorDiskLimitEnforcement>,
+    /// The launchers to use for the services in specific namespaces, keyed by
+    /// namespace.
+    ///
+    /// Namespaces without a launcher use [`SystemdProcessLauncher`] if the
+    /// machine was booted with systemd, and [`DirectProcessLauncher`]
+    /// otherwise.
+    pub launchers: BTreeMap<String, Arc<dyn ProcessLauncher>>,
+    /// Clock skew injection configuration.
+    ///
+    /// When enabled, the processes of selected services are launched with
+    /// libfaketime preloaded, which offsets their wall clock from that of this
+    /// process.
+    pub clock_skew: Option<ProcessOrchestratorClockSkewConfig>,
+    /// The sidecars to run alongside each process of the services in specific
+    /// namespaces, keyed by namespace.
+    ///
+    /// Like Kubernetes sidecar containers, each sidecar is launched and
+    /// relaunched alongside its process, and killed when the process is torn
+    /// down.
+    pub sidecars: BTreeMap<String, Vec<ProcessOrchestratorSidecarConfig>>,
+    /// Automatic profiling configuration.
+    ///
+    /// When enabled, a profile of a process is captured into the run
+    /// directory of its service whenever the process exceeds a memory or CPU
+    /// threshold for a sustained period.
+    pub profiling: Option<ProcessOrchestratorProfilingConfig>,
+    /// Crash-loop detection configuration.
+    ///
+    /// When enabled, a process that is relaunched too often within a window
+    /// is not relaunched again, and is reported as offline with
+    /// [`OfflineReason::CrashLooping`], until its service is next ensured.
+    pub crash_loop: Option<ProcessOrchestratorCrashLoopConfig>,
+    /// Crash debugging configuration.
+    ///
+    /// When enabled, the debugger command that opens the core dump of a
+    /// crashed process is logged, and optionally its backtraces are captured
+    /// and its relaunch is held back.
+    pub crash_debugging: Option<ProcessOrchestratorCrashDebugConfig>,
+    /// Process group configuration.
+    ///
+    /// When enabled, each process is started in its own process group, which
+    /// is terminated as a whole, and the `SIGINT` and `SIGTERM` this process
+    /// receives are forwarded to all processes. See
+    /// [`ProcessOrchestrator::signal_forwarded`].
+    pub process_groups: Option<ProcessOrchestratorProcessGroupConfig>,
+    /// The namespaces whose processes run in private directories.
+    ///
+    /// Each process of a service in these namespaces runs in the run
+    /// directory of its service, with `TMPDIR` pointing to a private directory
+    /// under the scratch directory that is removed when the service is
+    /// dropped.
+    pub private_dirs: BTreeSet<String>,
+    /// The registry in which to register the orchestrator's metrics.
+    pub metrics_registry: MetricsRegistry,
+}
+
+/// Configures clock skew injection for a [`ProcessOrchestrator`].
+///
+/// See [`ProcessOrchestratorConfig::clock_skew`].
+#[derive(Debug, Clone)]
+pub struct ProcessOrchestratorClockSkewConfig {
+    /// The path to the libfaketime shared library, e.g.,
+    /// `/usr/lib/x86_64-linux-gnu/faketime/libfaketime.so.1`.
+    pub libfaketime: PathBuf,
+    /// The offsets to apply to the wall clocks of services.
+    ///
+    /// Each key is either the full ID of a service, i.e., its namespace and
+    /// ID joined by `-` (e.g., `cluster-u1-replica-u1-gen-0`), or a namespace,
+    /// which applies the offset to every service in the namespace. The offset
+    /// for the full ID takes precedence. Services without an offset run with
+    /// an unmodified clock.
+    pub offsets: BTreeMap<String, ClockOffset>,
+}
+
+impl ProcessOrchestratorClockSkewConfig {
+    /// Returns the offset to apply to the service with the given namespace
+    /// and full ID, if any.
+    fn offset(&self, namespace: &str, full_id: &str) -> Option<ClockOffset> {
+        self.offsets
+            .get(full_id)
+            .or_else(|| self.offsets.get(namespace))
+            .copied()
+    }
+}
+
+/// An offset of a process's wall clock from the wall clock of the
+/// [`ProcessOrchestrator`].
+///
+/// Parses from and displays as a signed `humantime` duration, e.g., `+500ms`
+/// or `-2s`. A duration without a sign is ahead.
+#[derive(Debug, Clone, Copy, PartialEq, Eq)]
+pub enum ClockOffset {
+    /// The process's clock is ahead by the given duration.
+    Ahead(Duration),
+    /// The process's clock is behind by the given duration.
+    Behind(Duration),
+}
+
+impl ClockOffset {
+    /// Returns the offset in the relative format of libfaketime's `FAKETIME`
+    /// variable, in seconds.
+    fn faketime_spec(&self) -> String {
+        match self {
+            ClockOffset::Ahead(offset) => format!("+{}", offset.as_secs_f64()),
+            ClockOffset::Behind(offset) => format!("-{}", offset.as_secs_f64()),
+        }
+    }
+}
+
+impl FromStr for ClockOffset {
+    type Err = anyhow::Error;
+
+    fn from_str(s: &str) -> Result<ClockOffset, anyhow::Error> {
+        let s = s.trim();
+        let (ahead, duration) = match s.strip_prefix('-') {
+            Some(duration) => (false, duration),
+            None => (true, s.strip_prefix('+').unwrap_or(s)),
+        };
+        let duration = humantime::parse_duration(duration.trim())
+            .with_context(|| format!("invalid clock offset: {s}"))?;
+        match ahead {
+            true => Ok(ClockOffset::Ahead(duration)),
+            false => Ok(ClockOffset::Behind(duration)),
+        }
+    }
+}
+
+impl std::fmt::Display for ClockOffset {
+    fn fmt(&self, f: &mut std::fmt::Formatter<'_>) -> std::fmt::Result {
+        match self {
+            ClockOffset::Ahead(offset) => write!(f, "+{}", humantime::format_duration(*offset)),
+            ClockOffset::Behind(offset) => write!(f, "-{}", humantime::format_duration(*offset)),
+        }
+    }
+}
+
+/// How a [`ProcessOrchestrator`] enforces the disk limits of services.
+///
+/// See [`ProcessOrchestratorConfig::disk_limit_enforcement`].
+///
+/// Both methods mount a filesystem over the scratch directory of the service,
+/// which requires the privileges to run `mount` (typically root). The
+/// filesystem is unmounted when the service is dropped.
+#[derive(Debug, Clone, Copy, PartialEq, Eq)]
+pub enum ProcessOrchestratorDiskLimitEnforcement {
+    /// Back scratch directories with a size-capped `tmpfs`.
+    ///
+    /// The contents of a scratch directory count against the memory of the
+    /// machine and do not survive an unmount.
+    Tmpfs,
+    /// Back scratch directories with an ext4 filesystem in a sparse loopback
+    /// image file next to the scratch directory.
+    ///
+    /// Requires `mkfs.ext4`. The image is reused when the service's scratch
+    /// directory is mounted again, e.g., after a restart of the orchestrator,
+    /// and removed when the service is dropped.
+    LoopbackImage,
+}
+
+/// Configures the CPU affinity of the processes of a [`ProcessOrchestrator`].
+///
+/// See [`ProcessOrchestratorConfig::cpu_affinity`].
+///
+/// Affinity is applied with `taskset` or, when launching processes via
+/// systemd, with the `AllowedCPUs` property. It is not applied to remote
+/// processes.
+#[derive(Debug, Clone)]
+pub enum ProcessOrchestratorCpuAffinity {
+    /// Pins every process to the given CPUs.
+    Cpus(Vec<usize>),
+    /// Partitions the CPUs of the machine into disjoint sets of the given
+    /// size and pins each process to its own set.
+    ///
+    /// Sets are assigned in launch order and are returned when their service
+    /// is dropped. If there are more processes than sets, the least used sets
+    /// are shared.
+    Partitioned {
+        /// The number of CPUs in each set.
+        cpus_per_process: NonZeroUsize,
+    },
+}
+
+/// Configures the cleanup of stale metadata directories for a
+/// [`ProcessOrchestrator`].
+///
+/// See [`ProcessOrchestratorConfig::stale_metadata_cleanup`].
+///
+/// Every process orchestrator stores the run directories of its services in a
+/// metadata directory named after its environment in the system's temporary
+/// directory. When an environment is abandoned, e.g., because its data
+/// directory was reset, nothing removes that state. On startup, the
+/// orchestrator considers the metadata directories of all other environments
+/// and removes any service run directory in which no PID file refers to a live
+/// process and that has not been modified for at least `min_age`. A metadata
+/// directory is removed once it no longer contains any service run
+/// directories.
+///
+/// The metadata directory of the current environment is left alone, as its
+/// services are reconciled by the controllers via
+/// [`NamespacedOrchestrator::list_services`] and
+/// [`NamespacedOrchestrator::drop_service`].
+#[derive(Debug, Clone)]
+pub struct ProcessOrchestratorCleanupConfig {
+    /// The minimum time since a directory was last modified before it is
+    /// considered stale.
+    pub min_age: Duration,
+    /// Whether to only log the directories that would be removed, rather than
+    /// removing them.
+    pub dry_run: bool,
+}
+
+/// Configures a sidecar of the processes of a [`ProcessOrchestrator`].
+///
+/// See [`ProcessOrchestratorConfig::sidecars`].
+///
+/// Sidecars run in the run directory of the service, which holds the sockets
+/// and PID files of its processes. The `MZ_SERVICE_ID`, `MZ_PROCESS_INDEX`,
+/// and `MZ_PROCESS_PID_FILE` environment variables of a sidecar name the
+/// process it runs alongside.
+#[derive(Debug, Clone)]
+pub struct ProcessOrchestratorSidecarConfig {
+    /// A descriptive name for the sidecar, used in logs.
+    pub name: String,
+    /// The program to run, followed by its arguments.
+    ///
+    /// The arguments support the same `%N` and `%P:<port>` directives as
+    /// [`ProcessOrchestratorConfig::command_wrapper`].
+    pub command: Vec<String>,
+}
+
+/// Configures automatic profiling for a [`ProcessOrchestrator`].
+///
+/// See [`ProcessOrchestratorConfig::profiling`].
+///
+/// The capture command runs in the run directory of the service. The
+/// `MZ_SERVICE_ID`, `MZ_PROCESS_INDEX`, and `MZ_PROCESS_PID` environment
+/// variables of the command name the process to profile, and `MZ_PROFILE_PATH`
+/// names the path at which to store the profile, e.g.,
+/// `sh -c 'perf record -g -p $MZ_PROCESS_PID -o $MZ_PROFILE_PATH -- sleep 30'`.
+/// The command may also signal the process to make it write a profile of its
+/// own. The usage of a process is not sampled while a profile is captured.
+#[derive(Debug, Clone)]
+pub struct ProcessOrchestratorProfilingConfig {
+    /// The resident memory, in bytes, above which a process is profiled, if
+    /// any.
+    pub memory_threshold: Option<u64>,
+    /// The CPU usage, in cores, above which a process is profiled, if any.
+    pub cpu_threshold: Option<f64>,
+    /// How long a process must exceed a threshold before it is profiled.
+    pub sustain: Duration,
+    /// The minimum time between the starts of two captures for the same
+    /// process.
+    pub min_interval: Duration,
+    /// The program that captures a profile, followed by its arguments.
+    pub command: Vec<String>,
+}
+
+/// Configures crash-loop detection for a [`ProcessOrchestrator`].
+///
+/// See [`ProcessOrchestratorConfig::crash_loop`].
+#[derive(Debug, Clone)]
+pub struct ProcessOrchestratorCrashLoopConfig {
+    /// The number of relaunches within `window` above which a process is
+    /// quarantined.
+    pub max_restarts: usize,
+    /// The window over which relaunches are counted.
+    pub window: Duration,
+}
+
+/// Configures crash debugging for a [`ProcessOrchestrator`].
+///
+/// See [`ProcessOrchestratorConfig::crash_debugging`].
+///
+/// Core dumps must be enabled for the orchestrator's processes, e.g., with
+/// `ulimit -c unlimited`, before the orchestrator is started. On Linux, the
+/// core dump is located by interpreting `/proc/sys/kernel/core_pattern`; core
+/// dumps handled by `systemd-coredump` are opened with `coredumpctl`. Relative
+/// core patterns are relative to the working directory of the crashed process,
+/// which is the run directory of its service if it runs in private
+/// directories, see [`ProcessOrchestratorConfig::private_dirs`].
+#[derive(Debug, Clone)]
+pub struct ProcessOrchestratorCrashDebugConfig {
+    /// The debugger with which to open core dumps.
+    pub debugger: ProcessOrchestratorDebugger,
+    /// Whether to capture the backtraces of all threads of a crashed process
+    /// into the run directory of its service.
+    pub capture_backtraces: bool,
+    /// How long to hold back the relaunch of a crashed process.
+    pub hold: Duration,
+}
+
+/// Configures process groups for a [`ProcessOrchestrator`].
+///
+/// See [`ProcessOrchestratorConfig::process_groups`].
+#[derive(Debug, Clone)]
+pub struct ProcessOrchestratorProcessGroupConfig {
+    /// The order in which to forward signals to the namespaces.
+    pub signal_order: ProcessOrchestratorSignalOrder,
+    /// How long to wait for the processes of a namespace to exit after
+    /// forwarding a signal to them, before moving on.
+    pub grace_period: Duration,
+}
+
+/// The order in which a [`ProcessOrchestrator`] forwards signals to its
+/// namespaces. See [`ProcessOrchestratorProcessGroupConfig`].
+#[derive(Debug, Clone, Copy, PartialEq, Eq)]
+pub enum ProcessOrchestratorSignalOrder {
+    /// In the reverse order of their creation, as services in later namespaces
+    /// generally depend on those in earlier ones.
+    Reverse,
+    /// In the order of their creation.
+    Creation,
+    /// To all namespaces at once.
+    Concurrent,
+}
+
+/// A debugger supported by [`ProcessOrchestratorCrashDebugConfig`].
+#[derive(Debug, Clone, Copy, PartialEq, Eq)]
+pub enum ProcessOrchestratorDebugger {
+    /// The GNU debugger.
+    Gdb,
+    /// The LLVM debugger.
+    Lldb,
+}
+
+/// Configures remote execution over SSH for a [`ProcessOrchestrator`].
+///
+/// See [`ProcessOrchestratorConfig::ssh`].
+///
+/// Before each launch, the service's image is synced to the remote host with
+/// `rsync`. The process is then started via `ssh`, which forwards each of the
+/// process's local Unix domain sockets to the corresponding socket on the
+/// remote host. The process is considered ready for as long as the SSH
+/// connection is alive, and the remote process is terminated when the
+/// connection is torn down.
+///
+/// Both `ssh` and `rsync` must be on the `PATH`, and the remote hosts must
+/// accept non-interactive logins. Any other locations passed to the services
+/// (e.g., persist blob and consensus URLs) must be reachable from the remote
+/// hosts. Memory and CPU limits and the command wrapper are not applied to
+/// remote processes.
+#[derive(Debug, Clone)]
+pub struct ProcessOrchestratorSshConfig {
+    /// The remote hosts, in `[user@]host` form, on which to launch processes.
+    ///
+    /// Processes are assigned to hosts round-robin by their ordinal.
+    pub hosts: Vec<String>,
+    /// The directory on the remote hosts in which to store images, run
+    /// directories, and scratch directories.
+    pub remote_dir: PathBuf,
+    /// The namespaces whose services should be launched remotely.
+    pub namespaces: BTreeSet<String>,
 }
 
 /// Configures the TCP proxy for a [`ProcessOrchestrator`].
@@ -101,8 +497,37 @@ pub struct ProcessOrchestratorTcpProxyConfig {
     /// all extant services. The scrape targets will use the TCP proxy address,
     /// as Prometheus does not support scraping over Unix domain sockets.
     ///
+    /// The file is replaced atomically whenever the services of the namespace
+    /// change, and removed when the namespace has no services left or is shut
+    /// down. The orchestrator owns the directory: on startup, it removes the
+    /// JSON files left behind by a previous run, so that Prometheus doesn't
+    /// scrape dead targets of namespaces that no longer exist.
+    ///
     /// See also: <https://prometheus.io/docs/guides/file-sd/>
     pub prometheus_service_discovery_dir: Option<PathBuf>,
+    /// Traffic shaping to apply to the TCP proxy connections of the services
+    /// in each namespace.
+    ///
+    /// Namespaces without an entry are not shaped.
+    pub shaping: BTreeMap<String, ProcessOrchestratorTcpProxyShapingConfig>,
+}
+
+/// Configures traffic shaping for the TCP proxy connections of a
+/// [`ProcessOrchestrator`].
+///
+/// See [`ProcessOrchestratorTcpProxyConfig::shaping`].
+///
+/// The primary use is simulating slow networks locally, e.g., cross-region
+/// latency or slow client links. Only connections made through the TCP proxy
+/// are shaped; connections made directly to a service's Unix domain sockets
+/// are not.
+#[derive(Debug, Clone, Default)]
+pub struct ProcessOrchestratorTcpProxyShapingConfig {
+    /// The maximum rate, in bytes per second, at which to forward data in each
+    /// direction of each connection.
+    pub rate_limit: Option<NonZeroU64>,
+    /// The delay to impose on all data forwarded in either direction.
+    pub latency: Duration,
 }
 
 /// An orchestrator backed by processes on the local machine.
@@ -114,111 +539,334 @@ pub struct ProcessOrchestratorTcpProxyConfig {
 pub struct ProcessOrchestrator {
     image_dir: PathBuf,
     suppress_output: bool,
-    namespaces: Mutex<BTreeMap<String, Arc<dyn NamespacedOrchestrator>>>,
+    pty: bool,
+    namespaces: Arc<Mutex<BTreeMap<String, Arc<NamespacedProcessOrchestrator>>>>,
     metadata_dir: PathBuf,
     secrets_dir: PathBuf,
     command_wrapper: Vec<String>,
     propagate_crashes: bool,
     tcp_proxy: Option<ProcessOrchestratorTcpProxyConfig>,
     scratch_directory: PathBuf,
-    launch_spec: LaunchSpec,
+    default_launcher: Arc<dyn ProcessLauncher>,
+    launchers: BTreeMap<String, Arc<dyn ProcessLauncher>>,
+    ssh: Option<ProcessOrchestratorSshConfig>,
+    cpu_allocator: Option<Arc<CpuAllocator>>,
+    disk_limit_enforcement: Option<ProcessOrchestratorDiskLimitEnforcement>,
+    clock_skew: Option<ProcessOrchestratorClockSkewConfig>,
+    sidecars: BTreeMap<String, Vec<ProcessOrchestratorSidecarConfig>>,
+    profiling: Option<ProcessOrchestratorProfilingConfig>,
+    crash_loop: Option<ProcessOrchestratorCrashLoopConfig>,
+    crash_debugging: Option<ProcessOrchestratorCrashDebugConfig>,
+    process_groups: bool,
+    _signal_forwarder: Option<AbortOnDropHandle<()>>,
+    /// The signal forwarded by the signal forwarder, once it has shut down all
+    /// namespaces.
+    signal_forwarded: watch::Receiver<Option<Signal>>,
+    private_dirs: BTreeSet<String>,
+    metrics: ProcessOrchestratorMetrics,
 }
 
-#[derive(Debug, Clone, Copy)]
-enum LaunchSpec {
-    /// Directly execute the provided binary
-    Direct,
-    /// Use Systemd to start the binary
-    Systemd,
+/// Metrics for a [`ProcessOrchestrator`].
+#[derive(Debug, Clone)]
+struct ProcessOrchestratorMetrics {
+    oom_kills: IntCounterVec,
+    profiles: IntCounterVec,
+    start_latency: HistogramVec,
+    readiness: HistogramVec,
 }
 
-impl LaunchSpec {
-    fn determine_implementation() -> Result<Self, anyhow::Error> {
-        // According to https://www.freedesktop.org/software/systemd/man/latest/sd_booted.html
-        // checking for `/run/systemd/system/` is the canonical way to determine if the system
-        // was booted up with systemd.
-        match Path::new("/run/systemd/system/").try_exists()? {
-            true => Ok(Self::Systemd),
-            false => Ok(Self::Direct),
+impl ProcessOrchestratorMetrics {
+    fn register_into(registry: &MetricsRegistry) -> ProcessOrchestratorMetrics {
+        ProcessOrchestratorMetrics {
+            oom_kills: registry.register(metric!(
+                name: "mz_orchestrator_process_oom_kills_total",
+                help: "The number of service processes killed by the OOM killer.",
+                var_labels: ["namespace", "service_id"],
+            )),
+            profiles: registry.register(metric!(
+                name: "mz_orchestrator_process_profiles_total",
+                help: "The number of profiles captured of service processes over a threshold.",
+                var_labels: ["namespace", "service_id"],
+            )),
+            start_latency: registry.register(metric!(
+                name: "mz_orchestrator_process_start_latency_seconds",
+                help: "The time from ensuring a service to spawning each of its processes.",
+                var_labels: ["namespace", "service_id"],
+                buckets: histogram_seconds_buckets(0.000_500, 32.),
+            )),
+            readiness: registry.register(metric!(
+                name: "mz_orchestrator_process_readiness_seconds",
+                help: "The time from spawning a service process to it first accepting connections on all of its ports, as awaited by ensure_service_ready.",
+                var_labels: ["namespace", "service_id"],
+                buckets: histogram_seconds_buckets(0.000_500, 32.),
+            )),
         }
     }
+}
 
-    fn refine_command(
-        &self,
-        image: impl AsRef<OsStr>,
-        args: &[impl AsRef<OsStr>],
-        wrapper: &[String],
-        full_id: &str,
-        listen_addrs: &BTreeMap<String, String>,
-        memory_limit: Option<&MemoryLimit>,
-        cpu_limit: Option<&CpuLimit>,
-    ) -> Command {
-        let wrapper_parts = || {
-            (
-                &wrapper[0],
-                wrapper[1..]
-                    .iter()
-                    .map(|part| interpolate_command(part, full_id, listen_addrs)),
-            )
-        };
+/// Assigns sets of CPUs to processes according to a
+/// [`ProcessOrchestratorCpuAffinity`].
+#[derive(Debug)]
+struct CpuAllocator {
+    /// The sets of CPUs, formatted as CPU lists.
+    cpu_sets: Vec<String>,
+    /// The index of the set assigned to each process, keyed by the process's
+    /// full ID and ordinal.
+    assignments: Mutex<BTreeMap<(String, usize), usize>>,
+}
 
-        let mut cmd = match self {
-            Self::Direct => {
-                if wrapper.is_empty() {
-                    Command::new(image)
-                } else {
-                    let (program, wrapper_args) = wrapper_parts();
-                    let mut cmd = Command::new(program);
-                    cmd.args(wrapper_args);
-                    cmd.arg(image);
-                    cmd
+impl CpuAllocator {
+    fn new(affinity: &ProcessOrchestratorCpuAffinity) -> Result<CpuAllocator, anyhow::Error> {
+        let format_cpus = |cpus: &[usize]| cpus.iter().map(|cpu| cpu.to_string()).join(",");
+        let cpu_sets = match affinity {
+            ProcessOrchestratorCpuAffinity::Cpus(cpus) => {
+                if cpus.is_empty() {
+                    bail!("CPU affinity requires at least one CPU");
                 }
+                vec![format_cpus(cpus)]
             }
-            Self::Systemd => {
-                let mut cmd = Command::new("systemd-run");
-                cmd.args(["--user", "--scope", "--quiet"]);
-                if let Some(memory_limit) = memory_limit {
-                    let memory_limit = memory_limit.0.as_u64();
-                    cmd.args(["-p", &format!("MemoryMax={memory_limit}")]);
-                    // TODO: We could set `-p MemorySwapMax=0` here to disable regular swap.
-                }
-                if let Some(cpu_limit) = cpu_limit {
-                    let cpu_limit = (cpu_limit.as_millicpus() + 9) / 10;
-                    cmd.args(["-p", &format!("CPUQuota={cpu_limit}%")]);
+            ProcessOrchestratorCpuAffinity::Partitioned { cpus_per_process } => {
+                let cpus = std::thread::available_parallelism()?.get();
+                let cpus_per_process = cpus_per_process.get();
+                if cpus_per_process > cpus {
+                    bail!(
+                        "cannot assign {cpus_per_process} CPUs per process with only {cpus} CPUs"
+                    );
                 }
+                (0..cpus)
+                    .collect::<Vec<_>>()
+                    .chunks_exact(cpus_per_process)
+                    .map(format_cpus)
+                    .collect()
+            }
+        };
+        Ok(CpuAllocator {
+            cpu_sets,
+            assignments: Mutex::new(BTreeMap::new()),
+        })
+    }
 
-                if !wrapper.is_empty() {
-                    let (program, wrapper_args) = wrapper_parts();
-                    cmd.arg(program);
-                    cmd.args(wrapper_args);
-                };
-                cmd.arg(image);
+    /// Returns the CPU list for the `i`th process of the service with the
+    /// given full ID, assigning it the least used set if it does not have one
+    /// yet.
+    fn assign(&self, full_id: &str, i: usize) -> String {
+        let mut assignments = self.assignments.lock().expect("lock poisoned");
+        let key = (full_id.to_string(), i);
+        if let Some(set) = assignments.get(&key) {
+            return self.cpu_sets[*set].clone();
+        }
+        let mut uses = vec![0; self.cpu_sets.len()];
+        for set in assignments.values() {
+            uses[*set] += 1;
+        }
+        let set = (0..uses.len())
+            .min_by_key(|set| uses[*set])
+            .expect("at least one CPU set");
+        assignments.insert(key, set);
+        self.cpu_sets[set].clone()
+    }
+
+    /// Releases the sets assigned to the processes of the service with the
+    /// given full ID, so that they can be assigned to other processes.
+    fn release(&self, full_id: &str) {
+        let mut assignments = self.assignments.lock().expect("lock poisoned");
+        assignments.retain(|(id, _), _| id != full_id);
+    }
+}
+
+/// Builds the commands that launch the processes of a [`ProcessOrchestrator`]
+/// on the local machine.
+///
+/// The orchestrator ships with [`DirectProcessLauncher`] and
+/// [`SystemdProcessLauncher`], one of which is chosen automatically. Custom
+/// launchers can wrap processes in other programs, e.g., `docker run`,
+/// `rr record`, or `heaptrack`, and are selected per namespace with
+/// [`ProcessOrchestratorConfig::launchers`]. Remote processes are always
+/// launched over SSH.
+pub trait ProcessLauncher: Debug + Send + Sync {
+    /// Returns the command that launches the described process.
+    fn command(&self, launch: &ProcessLaunch<'_>) -> Command;
+}
+
+/// A process to be launched by a [`ProcessLauncher`].
+#[derive(Debug)]
+pub struct ProcessLaunch<'a> {
+    /// The path of the executable image.
+    pub image: &'a OsStr,
+    /// The arguments to the executable.
+    pub args: &'a [String],
+    /// The command to wrap the invocation of the executable in.
+    ///
+    /// See [`ProcessOrchestratorConfig::command_wrapper`].
+    pub wrapper: &'a [String],
+    /// The ID of the process, which is unique across namespaces.
+    pub full_id: &'a str,
+    /// The name of the process, i.e., its full ID followed by its index, like
+    /// `cluster-u1-replica-u2-0`.
+    ///
+    /// Launchers should make the name visible to tools like `ps` and `top`
+    /// where they can, so that the many processes of an environment can be
+    /// told apart.
+    pub name: &'a str,
+    /// The addresses the process listens on, by port name.
+    pub listen_addrs: &'a BTreeMap<String, String>,
+    /// The memory limit of the process, if any.
+    pub memory_limit: Option<&'a MemoryLimit>,
+    /// The CPU limit of the process, if any.
+    pub cpu_limit: Option<&'a CpuLimit>,
+    /// The CPUs the process should be pinned to, in `taskset --cpu-list`
+    /// format, if any.
+    ///
+    /// See [`ProcessOrchestratorConfig::cpu_affinity`].
+    pub cpu_list: Option<&'a str>,
+}
+
+impl ProcessLaunch<'_> {
+    /// Returns the program and arguments of the command wrapper, with the
+    /// `%N` and `%P:<port>` directives interpolated, if there is a wrapper.
+    pub fn wrapper_parts(&self) -> Option<(&str, Vec<String>)> {
+        let (program, args) = self.wrapper.split_first()?;
+        let args = args
+            .iter()
+            .map(|part| interpolate_command(part, self.full_id, self.listen_addrs))
+            .collect();
+        Some((program.as_str(), args))
+    }
+
+    /// Appends the invocation of the executable, wrapped in the command
+    /// wrapper if there is one, to `cmd`.
+    pub fn append_invocation(&self, cmd: &mut Command) {
+        if let Some((program, wrapper_args)) = self.wrapper_parts() {
+            cmd.arg(program);
+            cmd.args(wrapper_args);
+        }
+        cmd.arg(self.image);
+        cmd.args(self.args);
+    }
+}
+
+/// A [`ProcessLauncher`] that executes processes directly.
+///
+/// Memory and CPU limits are not enforced. Processes get their
+/// [name](ProcessLaunch::name) as `argv[0]`, unless they are wrapped in a
+/// [command wrapper](ProcessOrchestratorConfig::command_wrapper), which
+/// executes the image by its path.
+#[derive(Debug, Clone, Copy, Default)]
+pub struct DirectProcessLauncher;
+
+impl ProcessLauncher for DirectProcessLauncher {
+    fn command(&self, launch: &ProcessLaunch<'_>) -> Command {
+        match (launch.cpu_list, launch.wrapper_parts()) {
+            (Some(cpu_list), wrapper) => {
+                let mut cmd = Command::new("taskset");
+                cmd.args(["--cpu-list", cpu_list]);
+                if wrapper.is_none() {
+                    // `taskset` executes the image by its path, so have a
+                    // shell rename it.
+                    cmd.args(["bash", "-c", r#"exec -a "$0" "$@""#, launch.name]);
+                }
+                launch.append_invocation(&mut cmd);
                 cmd
             }
-        };
-        cmd.args(args);
+            (None, None) => {
+                let mut cmd = Command::new(launch.image);
+                cmd.arg0(launch.name);
+                cmd.args(launch.args);
+                cmd
+            }
+            (None, Some((program, wrapper_args))) => {
+                let mut cmd = Command::new(program);
+                cmd.args(wrapper_args);
+                cmd.arg(launch.image);
+                cmd.args(launch.args);
+                cmd
+            }
+        }
+    }
+}
+
+/// A [`ProcessLauncher`] that executes processes in transient systemd scopes,
+/// which enforce their memory and CPU limits.
+///
+/// Each scope is described by the [name](ProcessLaunch::name) of its process,
+/// as shown by, e.g., `systemctl --user status`.
+#[derive(Debug, Clone, Copy, Default)]
+pub struct SystemdProcessLauncher;
+
+impl ProcessLauncher for SystemdProcessLauncher {
+    fn command(&self, launch: &ProcessLaunch<'_>) -> Command {
+        let mut cmd = Command::new("systemd-run");
+        cmd.args(["--user", "--scope", "--quiet"]);
+        cmd.arg(format!("--description={}", launch.name));
+        if let Some(memory_limit) = launch.memory_limit {
+            let memory_limit = memory_limit.0.as_u64();
+            cmd.args(["-p", &format!("MemoryMax={memory_limit}")]);
+            // TODO: We could set `-p MemorySwapMax=0` here to disable regular swap.
+        }
+        if let Some(cpu_limit) = launch.cpu_limit {
+            let cpu_limit = (cpu_limit.as_millicpus() + 9) / 10;
+            cmd.args(["-p", &format!("CPUQuota={cpu_limit}%")]);
+        }
+        if let Some(cpu_list) = launch.cpu_list {
+            cmd.args(["-p", &format!("AllowedCPUs={cpu_list}")]);
+        }
+        launch.append_invocation(&mut cmd);
         cmd
     }
 }
 
+/// Determines the [`ProcessLauncher`] to use for namespaces without a
+/// configured launcher.
+fn default_launcher() -> Result<Arc<dyn ProcessLauncher>, anyhow::Error> {
+    // According to https://www.freedesktop.org/software/systemd/man/latest/sd_booted.html
+    // checking for `/run/systemd/system/` is the canonical way to determine if the system
+    // was booted up with systemd.
+    match Path::new("/run/systemd/system/").try_exists()? {
+        true => Ok(Arc::new(SystemdProcessLauncher)),
+        false => Ok(Arc::new(DirectProcessLauncher)),
+    }
+}
+
 impl ProcessOrchestrator {
     /// Creates a new process orchestrator from the provided configuration.
     pub async fn new(
         ProcessOrchestratorConfig {
             image_dir,
             suppress_output,
+            pty,
             environment_id,
             secrets_dir,
             command_wrapper,
             propagate_crashes,
             tcp_proxy,
             scratch_directory,
+            ssh,
+            stale_metadata_cleanup,
+            cpu_affinity,
+            disk_limit_enforcement,
+            launchers,
+            clock_skew,
+            sidecars,
+            profiling,
+            crash_loop,
+            crash_debugging,
+            process_groups,
+            private_dirs,
+            metrics_registry,
         }: ProcessOrchestratorConfig,
     ) -> Result<ProcessOrchestrator, anyhow::Error> {
         let metadata_dir = env::temp_dir().join(format!("environmentd-{environment_id}"));
         fs::create_dir_all(&metadata_dir)
             .await
             .context("creating metadata directory")?;
+        if let Some(cleanup) = &stale_metadata_cleanup {
+            if let Err(e) = cleanup_stale_metadata_dirs(&metadata_dir, cleanup).await {
+                warn!(
+                    "error cleaning up stale metadata directories: {}",
+                    e.display_with_causes()
+                );
+            }
+        }
         fs::create_dir_all(&secrets_dir)
             .await
             .context("creating secrets directory")?;
@@ -232,50 +880,275 @@ impl ProcessOrchestrator {
             fs::create_dir_all(&prometheus_dir)
                 .await
                 .context("creating prometheus directory")?;
+            // The files of extant namespaces are rewritten once their services
+            // are ensured again.
+            if let Err(e) = remove_service_discovery_files(prometheus_dir).await {
+                warn!(
+                    "failed to remove stale prometheus service discovery files: {}",
+                    e.display_with_causes()
+                );
+            }
+        }
+
+        let default_launcher = default_launcher()?;
+        info!(
+            driver = ?default_launcher,
+            ?launchers,
+            "Process orchestrator launchers"
+        );
+
+        if let Some(ssh) = &ssh {
+            if ssh.hosts.is_empty() {
+                bail!("remote execution requires at least one SSH host");
+            }
+            info!(
+                hosts = ?ssh.hosts,
+                namespaces = ?ssh.namespaces,
+                "Process orchestrator remote execution enabled"
+            );
+        }
+
+        if let Some(clock_skew) = &clock_skew {
+            if !clock_skew.libfaketime.exists() {
+                bail!(
+                    "clock skew injection requires libfaketime, which was not found at {}",
+                    clock_skew.libfaketime.display()
+                );
+            }
+            info!(
+                libfaketime = %clock_skew.libfaketime.display(),
+                offsets = ?clock_skew.offsets,
+                "Process orchestrator clock skew injection enabled"
+            );
+        }
+
+        for (namespace, sidecars) in &sidecars {
+            if let Some(sidecar) = sidecars.iter().find(|sidecar| sidecar.command.is_empty()) {
+                bail!(
+                    "sidecar {} in namespace {namespace} has no command",
+                    sidecar.name
+                );
+            }
+        }
+
+        if let Some(profiling) = &profiling {
+            if profiling.command.is_empty() {
+                bail!("automatic profiling requires a capture command");
+            }
+            if profiling.memory_threshold.is_none() && profiling.cpu_threshold.is_none() {
+                bail!("automatic profiling requires a memory or CPU threshold");
+            }
+            info!(
+                memory_threshold = ?profiling.memory_threshold,
+                cpu_threshold = ?profiling.cpu_threshold,
+                sustain = ?profiling.sustain,
+                command = ?profiling.command,
+                "Process orchestrator automatic profiling enabled"
+            );
+        }
+
+        if let Some(crash_loop) = &crash_loop {
+            info!(
+                max_restarts = crash_loop.max_restarts,
+                window = ?crash_loop.window,
+                "Process orchestrator crash-loop detection enabled"
+            );
+        }
+
+        if let Some(crash_debugging) = &crash_debugging {
+            info!(
+                debugger = ?crash_debugging.debugger,
+                capture_backtraces = crash_debugging.capture_backtraces,
+                hold = ?crash_debugging.hold,
+                "Process orchestrator crash debugging enabled"
+            );
+        }
+
+        if !private_dirs.is_empty() {
+            info!(
+                namespaces = ?private_dirs,
+                "Process orchestrator private directories enabled"
+            );
         }
 
-        let launch_spec = LaunchSpec::determine_implementation()?;
-        info!(driver = ?launch_spec, "Process orchestrator launch spec");
+        let namespaces = Arc::new(Mutex::new(BTreeMap::new()));
+        let (signal_forwarded_tx, signal_forwarded) = watch::channel(None);
+        let signal_forwarder = match &process_groups {
+            None => None,
+            Some(config) => {
+                // Register the handlers before returning, so that signals are
+                // never missed once processes have been launched.
+                let sigint =
+                    unix_signal(SignalKind::interrupt()).context("installing SIGINT handler")?;
+                let sigterm =
+                    unix_signal(SignalKind::terminate()).context("installing SIGTERM handler")?;
+                info!(
+                    signal_order = ?config.signal_order,
+                    grace_period = ?config.grace_period,
+                    "Process orchestrator process groups enabled"
+                );
+                let handle = mz_ore::task::spawn(
+                    || "process-orchestrator:signal-forwarder",
+                    forward_signals(
+                        Arc::clone(&namespaces),
+                        config.clone(),
+                        sigint,
+                        sigterm,
+                        signal_forwarded_tx,
+                    ),
+                );
+                Some(handle.abort_on_drop())
+            }
+        };
+
+        let cpu_allocator = match &cpu_affinity {
+            None => None,
+            Some(affinity) => {
+                let allocator = CpuAllocator::new(affinity)?;
+                info!(
+                    cpu_sets = ?allocator.cpu_sets,
+                    "Process orchestrator CPU affinity enabled"
+                );
+                Some(Arc::new(allocator))
+            }
+        };
 
         Ok(ProcessOrchestrator {
             image_dir: fs::canonicalize(image_dir).await?,
             suppress_output,
-            namespaces: Mutex::new(BTreeMap::new()),
+            pty,
+            namespaces,
             metadata_dir: fs::canonicalize(metadata_dir).await?,
             secrets_dir: fs::canonicalize(secrets_dir).await?,
             command_wrapper,
             propagate_crashes,
             tcp_proxy,
             scratch_directory,
-            launch_spec,
+            default_launcher,
+            launchers,
+            ssh,
+            cpu_allocator,
+            disk_limit_enforcement,
+            clock_skew,
+            sidecars,
+            profiling,
+            crash_loop,
+            crash_debugging,
+            process_groups: process_groups.is_some(),
+            _signal_forwarder: signal_forwarder,
+            signal_forwarded,
+            private_dirs,
+            metrics: ProcessOrchestratorMetrics::register_into(&metrics_registry),
         })
     }
-}
 
-impl Orchestrator for ProcessOrchestrator {
-    fn namespace(&self, namespace: &str) -> Arc<dyn NamespacedOrchestrator> {
+    /// Shuts down all services in all namespaces, returning when all of their
+    /// processes have exited.
+    ///
+    /// Namespaces are shut down in the reverse order of their creation, as
+    /// services in later namespaces generally depend on those in earlier ones.
+    /// Shutting down a namespace stops the supervisors of its processes, which
+    /// closes their TCP proxies and terminates the processes, gives the
+    /// processes a grace period to exit, and removes their PID files and
+    /// sockets. Unlike dropping a service, this leaves the scratch directories
+    /// of the services in place.
+    ///
+    /// The orchestrator should not be used once it has been shut down.
+    pub async fn shutdown(&self) {
+        shutdown_namespaces(&self.namespaces).await
+    }
+
+    /// Returns a future that resolves to the `SIGINT` or `SIGTERM` this process
+    /// received once it has been forwarded to the processes of all namespaces
+    /// and the namespaces have been shut down.
+    ///
+    /// Handling the signals suppresses their default action, so the caller is
+    /// expected to exit once the future resolves. The future never resolves
+    /// unless process groups are enabled, see
+    /// [`ProcessOrchestratorConfig::process_groups`].
+    pub fn signal_forwarded(&self) -> impl Future<Output = Signal> + Send + 'static {
+        let mut signal_forwarded = self.signal_forwarded.clone();
+        async move {
+            match signal_forwarded.wait_for(Option::is_some).await {
+                Ok(signal) => (*signal).expect("waited for a signal"),
+                // The signal forwarder is gone without forwarding a signal.
+                Err(_) => future::pending().await,
+            }
+        }
+    }
+
+    /// Like [`NamespacedOrchestrator::ensure_service`], but returns only once
+    /// every process of the service has been spawned and accepts connections
+    /// on all of its ports.
+    ///
+    /// Only this method probes the sockets of the processes. Their reported
+    /// status changes to online as soon as they are spawned, as it does for
+    /// services ensured with `ensure_service`. Processes whose spec is
+    /// unchanged are not restarted, so they count as spawned if they have been
+    /// spawned before, but they must still accept connections. Returns an
+    /// error if the processes do not all become ready within `timeout`, if
+    /// the service is dropped while waiting, or if a process can't be launched
+    /// because one of its sockets is held by a process that is not ours.
+    pub async fn ensure_service_ready(
+        &self,
+        namespace: &str,
+        id: &str,
+        config: ServiceConfig,
+        timeout: Duration,
+    ) -> Result<Box<dyn Service>, anyhow::Error> {
+        let namespace = self.namespaced(namespace);
+        let service = namespace.ensure_service(id, config)?;
+        namespace.wait_for_service_ready(id, timeout).await?;
+        Ok(service)
+    }
+
+    fn namespaced(&self, namespace: &str) -> Arc<NamespacedProcessOrchestrator> {
         let mut namespaces = self.namespaces.lock().expect("lock poisoned");
+        let ordinal = namespaces.len();
         Arc::clone(namespaces.entry(namespace.into()).or_insert_with(|| {
             let config = Arc::new(NamespacedProcessOrchestratorConfig {
                 namespace: namespace.into(),
                 image_dir: self.image_dir.clone(),
                 suppress_output: self.suppress_output,
+                pty: self.pty,
                 metadata_dir: self.metadata_dir.clone(),
                 command_wrapper: self.command_wrapper.clone(),
                 propagate_crashes: self.propagate_crashes,
                 tcp_proxy: self.tcp_proxy.clone(),
                 scratch_directory: self.scratch_directory.clone(),
-                launch_spec: self.launch_spec,
+                launcher: Arc::clone(
+                    self.launchers
+                        .get(namespace)
+                        .unwrap_or(&self.default_launcher),
+                ),
+                ssh: self
+                    .ssh
+                    .as_ref()
+                    .filter(|ssh| ssh.namespaces.contains(namespace))
+                    .cloned(),
+                cpu_allocator: self.cpu_allocator.clone(),
+                disk_limit_enforcement: self.disk_limit_enforcement,
+                clock_skew: self.clock_skew.clone(),
+                sidecars: self.sidecars.get(namespace).cloned().unwrap_or_default(),
+                profiling: self.profiling.clone(),
+                crash_loop: self.crash_loop.clone(),
+                crash_debugging: self.crash_debugging.clone(),
+                process_groups: self.process_groups,
+                stopping: AtomicBool::new(false),
+                private_dirs: self.private_dirs.contains(namespace),
+                metrics: self.metrics.clone(),
             });
 
             let services = Arc::new(Mutex::new(BTreeMap::new()));
             let (service_event_tx, service_event_rx) = broadcast::channel(16384);
+            let (service_address_tx, service_address_rx) = broadcast::channel(16384);
             let (command_tx, command_rx) = mpsc::unbounded_channel();
 
             let worker = OrchestratorWorker {
                 config: Arc::clone(&config),
                 services: Arc::clone(&services),
                 service_event_tx,
+                service_address_tx,
                 system: System::new(),
                 command_rx,
             }
@@ -283,8 +1156,10 @@ impl Orchestrator for ProcessOrchestrator {
 
             Arc::new(NamespacedProcessOrchestrator {
                 config,
+                ordinal,
                 services,
                 service_event_rx,
+                service_address_rx,
                 command_tx,
                 _worker: worker,
             })
@@ -292,18 +1167,113 @@ impl Orchestrator for ProcessOrchestrator {
     }
 }
 
+impl Orchestrator for ProcessOrchestrator {
+    fn namespace(&self, namespace: &str) -> Arc<dyn NamespacedOrchestrator> {
+        self.namespaced(namespace)
+    }
+}
+
+/// How long [`ProcessOrchestrator::shutdown`] waits for terminated processes
+/// to exit.
+const SHUTDOWN_GRACE_PERIOD: Duration = Duration::from_secs(10);
+
+/// Shuts down the given namespaces. See [`ProcessOrchestrator::shutdown`].
+async fn shutdown_namespaces(
+    namespaces: &Mutex<BTreeMap<String, Arc<NamespacedProcessOrchestrator>>>,
+) {
+    let mut namespaces: Vec<_> = {
+        let namespaces = namespaces.lock().expect("lock poisoned");
+        namespaces.values().cloned().collect()
+    };
+    namespaces.sort_by_key(|namespace| std::cmp::Reverse(namespace.ordinal));
+    for namespace in namespaces {
+        namespace.shutdown().await;
+    }
+}
+
+/// Waits for this process to receive `SIGINT` or `SIGTERM`, forwards the
+/// signal to the processes of all namespaces in the configured order, shuts
+/// the namespaces down, and reports the signal on `signal_forwarded_tx`.
+///
+/// Handling the signals suppresses their default action, so this process must
+/// exit by itself, see [`ProcessOrchestrator::signal_forwarded`].
+async fn forward_signals(
+    namespaces: Arc<Mutex<BTreeMap<String, Arc<NamespacedProcessOrchestrator>>>>,
+    config: ProcessOrchestratorProcessGroupConfig,
+    mut sigint: UnixSignal,
+    mut sigterm: UnixSignal,
+    signal_forwarded_tx: watch::Sender<Option<Signal>>,
+) {
+    let signal = select! {
+        _ = sigint.recv() => Signal::SIGINT,
+        _ = sigterm.recv() => Signal::SIGTERM,
+    };
+    info!("received {signal}; forwarding it to all processes");
+
+    let mut ordered: Vec<_> = {
+        let namespaces = namespaces.lock().expect("lock poisoned");
+        namespaces.values().cloned().collect()
+    };
+    let grace_period = config.grace_period;
+    match config.signal_order {
+        ProcessOrchestratorSignalOrder::Reverse => {
+            ordered.sort_by_key(|namespace| std::cmp::Reverse(namespace.ordinal));
+        }
+        ProcessOrchestratorSignalOrder::Creation => {
+            ordered.sort_by_key(|namespace| namespace.ordinal);
+        }
+        ProcessOrchestratorSignalOrder::Concurrent => {}
+    }
+    if config.signal_order == ProcessOrchestratorSignalOrder::Concurrent {
+        let forwards = ordered
+            .iter()
+            .map(|namespace| namespace.forward_signal(signal, grace_period));
+        future::join_all(forwards).await;
+    } else {
+        for namespace in &ordered {
+            namespace.forward_signal(signal, grace_period).await;
+        }
+    }
+
+    // Clean up after the processes, and kill those that outlived the grace
+    // period.
+    shutdown_namespaces(&namespaces).await;
+    info!("all processes shut down after {signal}");
+    let _ = signal_forwarded_tx.send(Some(signal));
+}
+
 /// Configuration for a [`NamespacedProcessOrchestrator`].
 #[derive(Debug)]
 struct NamespacedProcessOrchestratorConfig {
     namespace: String,
     image_dir: PathBuf,
     suppress_output: bool,
+    pty: bool,
     metadata_dir: PathBuf,
     command_wrapper: Vec<String>,
     propagate_crashes: bool,
     tcp_proxy: Option<ProcessOrchestratorTcpProxyConfig>,
     scratch_directory: PathBuf,
-    launch_spec: LaunchSpec,
+    launcher: Arc<dyn ProcessLauncher>,
+    /// Remote execution configuration, if this namespace's services are
+    /// launched remotely.
+    ssh: Option<ProcessOrchestratorSshConfig>,
+    cpu_allocator: Option<Arc<CpuAllocator>>,
+    disk_limit_enforcement: Option<ProcessOrchestratorDiskLimitEnforcement>,
+    clock_skew: Option<ProcessOrchestratorClockSkewConfig>,
+    sidecars: Vec<ProcessOrchestratorSidecarConfig>,
+    profiling: Option<ProcessOrchestratorProfilingConfig>,
+    crash_loop: Option<ProcessOrchestratorCrashLoopConfig>,
+    crash_debugging: Option<ProcessOrchestratorCrashDebugConfig>,
+    /// Whether local processes are started in their own process groups.
+    process_groups: bool,
+    /// Whether a signal has been forwarded to the processes of this namespace,
+    /// in which case processes that exit are not relaunched.
+    stopping: AtomicBool,
+    /// Whether local processes run in private working and temporary
+    /// directories.
+    private_dirs: bool,
+    metrics: ProcessOrchestratorMetrics,
 }
 
 impl NamespacedProcessOrchestratorConfig {
@@ -318,13 +1288,112 @@ impl NamespacedProcessOrchestratorConfig {
     fn service_scratch_dir(&self, id: &str) -> PathBuf {
         self.scratch_directory.join(&self.full_id(id))
     }
-}
 
-#[derive(Debug)]
-struct NamespacedProcessOrchestrator {
-    config: Arc<NamespacedProcessOrchestratorConfig>,
-    services: Arc<Mutex<BTreeMap<String, Vec<ProcessState>>>>,
+    /// Returns the path of the directory that holds the private temporary
+    /// directories of the processes of the service.
+    fn service_tmp_dir(&self, id: &str) -> PathBuf {
+        self.scratch_directory
+            .join(format!("{}.tmp", self.full_id(id)))
+    }
+
+    /// Returns the path of the loopback image that backs the scratch directory
+    /// of the service, if disk limits are enforced with loopback images.
+    fn service_scratch_image(&self, id: &str) -> PathBuf {
+        self.scratch_directory
+            .join(format!("{}.img", self.full_id(id)))
+    }
+
+    /// Returns the remote placement of the `i`th process of the service
+    /// running `image`, if this namespace's services are launched remotely.
+    fn remote_process(&self, id: &str, image: &str, i: usize) -> Option<RemoteProcess> {
+        let ssh = self.ssh.as_ref()?;
+        let full_id = self.full_id(id);
+        Some(RemoteProcess {
+            host: ssh.hosts[i % ssh.hosts.len()].clone(),
+            image: ssh.remote_dir.join("images").join(image),
+            run_dir: ssh.remote_dir.join("run").join(&full_id),
+            scratch_dir: ssh.remote_dir.join("scratch").join(&full_id),
+        })
+    }
+}
+
+/// The placement of a service process on a remote host.
+#[derive(Debug, Clone)]
+struct RemoteProcess {
+    /// The host, in `[user@]host` form, on which the process runs.
+    host: String,
+    /// The path of the image on the remote host.
+    image: PathBuf,
+    /// The run directory of the service on the remote host.
+    run_dir: PathBuf,
+    /// The scratch directory of the service on the remote host.
+    scratch_dir: PathBuf,
+}
+
+impl RemoteProcess {
+    /// Syncs the local `image` to the remote host.
+    async fn sync_image(&self, image: &Path) -> Result<(), anyhow::Error> {
+        let status = Command::new("rsync")
+            .args(["--archive", "--compress", "--mkpath"])
+            .arg(image)
+            .arg(format!("{}:{}", self.host, self.image.display()))
+            .status()
+            .await
+            .context("running rsync")?;
+        if !status.success() {
+            bail!(
+                "syncing {} to {} failed: {status}",
+                image.display(),
+                self.host
+            );
+        }
+        Ok(())
+    }
+
+    /// Builds the command that launches the process on the remote host.
+    ///
+    /// `forwards` maps the local Unix domain sockets to the remote sockets to
+    /// which connections should be forwarded.
+    fn command(&self, args: &[String], forwards: &[(String, String)]) -> Command {
+        let mut cmd = Command::new("ssh");
+        // Force allocation of a pseudo-terminal so that the remote process
+        // receives SIGHUP when the connection is torn down.
+        cmd.arg("-tt");
+        cmd.args(["-o", "BatchMode=yes"]);
+        cmd.args(["-o", "ExitOnForwardFailure=yes"]);
+        cmd.args(["-o", "StreamLocalBindUnlink=yes"]);
+        for (local, remote) in forwards {
+            cmd.arg("-L").arg(format!("{local}:{remote}"));
+        }
+        let mut remote_cmd = format!(
+            "mkdir -p {} {} && exec {}",
+            shell_quote(&self.run_dir.display().to_string()),
+            shell_quote(&self.scratch_dir.display().to_string()),
+            shell_quote(&self.image.display().to_string()),
+        );
+        for arg in args {
+            remote_cmd.push(' ');
+            remote_cmd.push_str(&shell_quote(arg));
+        }
+        cmd.arg(&self.host).arg("--").arg(remote_cmd);
+        cmd
+    }
+}
+
+/// Quotes `s` for interpretation by a POSIX shell.
+fn shell_quote(s: &str) -> String {
+    format!("'{}'", s.replace('\'', r"'\''"))
+}
+
+#[derive(Debug)]
+struct NamespacedProcessOrchestrator {
+    config: Arc<NamespacedProcessOrchestratorConfig>,
+    /// The order in which this namespace was created among the namespaces of
+    /// its [`ProcessOrchestrator`].
+    ordinal: usize,
+    services: Arc<Mutex<BTreeMap<String, Vec<ProcessState>>>>,
     service_event_rx: broadcast::Receiver<ServiceEvent>,
+    service_address_rx: broadcast::Receiver<ServiceAddressEvent>,
     command_tx: mpsc::UnboundedSender<WorkerCommand>,
     _worker: AbortOnDropHandle<()>,
 }
@@ -333,6 +1402,118 @@ impl NamespacedProcessOrchestrator {
     fn send_command(&self, cmd: WorkerCommand) {
         self.command_tx.send(cmd).expect("worker task not dropped");
     }
+
+    /// Shuts down all services in this namespace.
+    ///
+    /// See [`ProcessOrchestrator::shutdown`].
+    async fn shutdown(&self) {
+        let (result_tx, result_rx) = oneshot::channel();
+        self.send_command(WorkerCommand::Shutdown { result_tx });
+
+        result_rx.await.expect("worker task not dropped")
+    }
+
+    /// Forwards `signal` to the processes of all services in this namespace,
+    /// and waits up to `grace_period` for them to exit.
+    ///
+    /// See [`ProcessOrchestratorConfig::process_groups`].
+    async fn forward_signal(&self, signal: Signal, grace_period: Duration) {
+        let (result_tx, result_rx) = oneshot::channel();
+        self.send_command(WorkerCommand::ForwardSignal {
+            signal,
+            grace_period,
+            result_tx,
+        });
+
+        result_rx.await.expect("worker task not dropped")
+    }
+
+    /// Waits until every process of the service has been spawned and accepts
+    /// connections.
+    ///
+    /// See [`ProcessOrchestrator::ensure_service_ready`].
+    async fn wait_for_service_ready(
+        &self,
+        id: &str,
+        timeout: Duration,
+    ) -> Result<(), anyhow::Error> {
+        let full_id = self.config.full_id(id);
+        // Subscribe before synchronizing with the worker, so we can't miss
+        // a status change that happens in between.
+        let mut service_event_rx = self.service_event_rx.resubscribe();
+        // Commands are processed in order, so once this returns, the worker
+        // has processed all previously sent `ensure_service` calls and the
+        // process states reflect the latest spec of the service.
+        self.list_services().await?;
+
+        let wait = async {
+            let addresses = loop {
+                {
+                    let services = self.services.lock().expect("lock poisoned");
+                    let Some(process_states) = services.get(id) else {
+                        bail!("service {full_id} dropped while waiting for it to become ready");
+                    };
+                    if process_states.iter().all(|state| state.ever_ready) {
+                        break process_states
+                            .iter()
+                            .map(|state| state.addresses.clone())
+                            .collect::<Vec<_>>();
+                    }
+                    // Fail fast on errors that won't resolve by themselves,
+                    // rather than waiting for the timeout.
+                    if let Some(error) = process_states.iter().find_map(|s| s.launch_error.as_ref())
+                    {
+                        bail!("service {full_id} cannot become ready: {error}");
+                    }
+                }
+                // The process states are the source of truth, so we don't
+                // care about the contents of the event, nor about lagging.
+                loop {
+                    match service_event_rx.recv().await {
+                        Ok(event) if event.service_id != id => continue,
+                        Ok(_) | Err(broadcast::error::RecvError::Lagged(_)) => break,
+                        Err(broadcast::error::RecvError::Closed) => {
+                            bail!("orchestrator worker for {full_id} terminated")
+                        }
+                    }
+                }
+            };
+            // A process that was spawned does not necessarily accept
+            // connections yet. Only callers that asked to wait probe the
+            // sockets, so the status of a process keeps meaning that it was
+            // spawned.
+            for (i, addresses) in addresses.iter().enumerate() {
+                for path in addresses.values() {
+                    while UnixStream::connect(path).await.is_err() {
+                        time::sleep(Duration::from_millis(100)).await;
+                    }
+                }
+                self.observe_readiness(id, i);
+            }
+            Ok(())
+        };
+        time::timeout(timeout, wait).await.map_err(|_| {
+            anyhow!("timed out after {timeout:?} waiting for service {full_id} to become ready")
+        })?
+    }
+
+    /// Records the time from the first launch of process `i` of the service
+    /// `id` to it accepting connections, unless it was already recorded or the
+    /// process wasn't launched for the latest spec.
+    fn observe_readiness(&self, id: &str, i: usize) {
+        let mut services = self.services.lock().expect("lock poisoned");
+        let launched_at = services
+            .get_mut(id)
+            .and_then(|process_states| process_states.get_mut(i))
+            .and_then(|process_state| process_state.launched_at.take());
+        if let Some(launched_at) = launched_at {
+            self.config
+                .metrics
+                .readiness
+                .with_label_values(&[self.config.namespace.as_str(), id])
+                .observe(launched_at.elapsed().as_secs_f64());
+        }
+    }
 }
 
 #[async_trait]
@@ -342,6 +1523,16 @@ impl NamespacedOrchestrator for NamespacedProcessOrchestrator {
         id: &str,
         config: ServiceConfig,
     ) -> Result<Box<dyn Service>, anyhow::Error> {
+        // Conflicting socket holders would otherwise only show up as the
+        // supervisor failing to launch the process over and over.
+        let ports: Vec<_> = config.ports.iter().map(|p| p.name.clone()).collect();
+        check_socket_conflicts(
+            &self.config.full_id(id),
+            &self.config.service_run_dir(id),
+            &ports,
+            config.scale,
+        )?;
+
         let service = ProcessService {
             run_dir: self.config.service_run_dir(id),
             scale: config.scale,
@@ -393,6 +1584,29 @@ impl NamespacedOrchestrator for NamespacedProcessOrchestrator {
         })
     }
 
+    fn watch_service_addresses(
+        &self,
+    ) -> BoxStream<'static, Result<ServiceAddressEvent, anyhow::Error>> {
+        let mut initial_events = vec![];
+        let mut service_address_rx = {
+            let services = self.services.lock().expect("lock poisoned");
+            for (service_id, process_states) in &*services {
+                for (process_id, process_state) in process_states.iter().enumerate() {
+                    initial_events.push(process_state.address_event(service_id, process_id));
+                }
+            }
+            self.service_address_rx.resubscribe()
+        };
+        Box::pin(stream! {
+            for event in initial_events {
+                yield Ok(event);
+            }
+            loop {
+                yield service_address_rx.recv().await.err_into();
+            }
+        })
+    }
+
     async fn fetch_service_metrics(
         &self,
         id: &str,
@@ -434,6 +1648,14 @@ enum WorkerCommand {
         id: String,
         result_tx: oneshot::Sender<Result<Vec<ServiceProcessMetrics>, anyhow::Error>>,
     },
+    Shutdown {
+        result_tx: oneshot::Sender<()>,
+    },
+    ForwardSignal {
+        signal: Signal,
+        grace_period: Duration,
+        result_tx: oneshot::Sender<()>,
+    },
 }
 
 /// A task executing blocking work for a [`NamespacedProcessOrchestrator`] in the background.
@@ -451,6 +1673,7 @@ struct OrchestratorWorker {
     config: Arc<NamespacedProcessOrchestratorConfig>,
     services: Arc<Mutex<BTreeMap<String, Vec<ProcessState>>>>,
     service_event_tx: broadcast::Sender<ServiceEvent>,
+    service_address_tx: broadcast::Sender<ServiceAddressEvent>,
     system: System,
     command_rx: mpsc::UnboundedReceiver<WorkerCommand>,
 }
@@ -475,6 +1698,20 @@ impl OrchestratorWorker {
                     let _ = result_tx.send(self.fetch_service_metrics(&id));
                     Ok(())
                 }
+                Shutdown { result_tx } => {
+                    self.shutdown().await;
+                    let _ = result_tx.send(());
+                    Ok(())
+                }
+                ForwardSignal {
+                    signal,
+                    grace_period,
+                    result_tx,
+                } => {
+                    self.forward_signal(signal, grace_period).await;
+                    let _ = result_tx.send(());
+                    Ok(())
+                }
             };
 
             if let Err(error) = result {
@@ -550,10 +1787,11 @@ impl OrchestratorWorker {
             other_replicas_selector: _,
             replicas_selector: _,
             disk,
-            disk_limit: _,
+            disk_limit,
             node_selector: _,
         }: ServiceConfig,
     ) -> Result<(), anyhow::Error> {
+        let ensured_at = time::Instant::now();
         let full_id = self.config.full_id(&id);
 
         let run_dir = self.config.service_run_dir(&id);
@@ -565,18 +1803,70 @@ impl OrchestratorWorker {
             fs::create_dir_all(&scratch_dir)
                 .await
                 .context("creating scratch directory")?;
+            // Disk limits of remote processes would have to be enforced on
+            // the remote host.
+            let enforcement = self
+                .config
+                .disk_limit_enforcement
+                .filter(|_| self.config.ssh.is_none());
+            if let (Some(enforcement), Some(disk_limit)) = (enforcement, disk_limit) {
+                if disk_limit != DiskLimit::MAX {
+                    let image = self.config.service_scratch_image(&id);
+                    if let Err(e) = mount_scratch_filesystem(
+                        enforcement,
+                        &scratch_dir,
+                        &image,
+                        disk_limit.0.as_u64(),
+                    )
+                    .await
+                    {
+                        warn!(
+                            "{full_id}: failed to enforce disk limit of {}: {}; \
+                             scratch directory is unbounded",
+                            disk_limit.0,
+                            e.display_with_causes()
+                        );
+                    }
+                }
+            }
             Some(fs::canonicalize(&scratch_dir).await?)
         } else {
             None
         };
 
+        let labels: BTreeMap<_, _> = labels.iter().map(|(k, v)| (k.clone(), v.clone())).collect();
+        let specs: Vec<_> = (0..scale.into())
+            .map(|i| {
+                let listen_addrs: BTreeMap<_, _> = ports_in
+                    .iter()
+                    .map(|p| (p.name.clone(), socket_path(&run_dir, &p.name, i)))
+                    .collect();
+                ProcessSpec {
+                    image: image.clone(),
+                    args: args(&listen_addrs),
+                    ports: ports_in.iter().map(|p| p.name.clone()).collect(),
+                    memory_limit,
+                    cpu_limit,
+                    disk,
+                }
+            })
+            .collect();
+
         {
             let mut services = self.services.lock().expect("lock poisoned");
             let process_states = services.entry(id.clone()).or_default();
 
-            // Create the state for new processes.
-            let mut new_process_states = vec![];
-            for i in process_states.len()..scale.into() {
+            // Create the state for new processes, for existing processes
+            // whose spec changed, which must be restarted to converge to the
+            // new spec, and for quarantined processes, which are given another
+            // chance. Other processes are left running.
+            let mut new_process_states = BTreeMap::new();
+            for (i, spec) in specs.into_iter().enumerate() {
+                if process_states.get(i).map_or(false, |state| {
+                    state.spec == spec && !matches!(state.status, ProcessStatus::Quarantined)
+                }) {
+                    continue;
+                }
                 // Allocate listeners for each TCP proxy, if requested.
                 let mut ports = vec![];
                 let mut tcp_proxy_addrs = BTreeMap::new();
@@ -602,6 +1892,33 @@ impl OrchestratorWorker {
                     });
                 }
 
+                new_process_states.insert(i, (spec, ports, tcp_proxy_addrs));
+            }
+
+            // Update the in-memory process state. We do this after we've
+            // allocated the resources of all process states to avoid partially
+            // updating our in-memory state.
+            let mut old_process_states = std::mem::take(process_states).into_iter();
+            for i in 0..scale.into() {
+                let old_process_state = old_process_states.next();
+                let Some((spec, ports, tcp_proxy_addrs)) = new_process_states.remove(&i) else {
+                    let mut process_state = old_process_state.expect("unchanged process exists");
+                    process_state.labels = labels.clone();
+                    // The process was launched for a previous call, so the
+                    // time until it accepts connections says nothing about
+                    // this one.
+                    process_state.launched_at = None;
+                    process_states.push(process_state);
+                    continue;
+                };
+                match &old_process_state {
+                    Some(state) if state.spec == spec => {
+                        info!("{full_id}-{i} lifting crash-loop quarantine; restarting")
+                    }
+                    Some(_) => info!("{full_id}-{i} spec changed; restarting"),
+                    None => (),
+                }
+
                 // Launch supervisor process.
                 let handle = mz_ore::task::spawn(
                     || format!("process-orchestrator:{full_id}-{i}"),
@@ -616,23 +1933,35 @@ impl OrchestratorWorker {
                         memory_limit,
                         cpu_limit,
                         disk,
-                        launch_spec: self.config.launch_spec,
+                        launcher: Arc::clone(&self.config.launcher),
+                        predecessor: old_process_state.map(|state| state.handle),
+                        ensured_at,
                     }),
                 );
 
-                new_process_states.push(ProcessState {
-                    _handle: handle.abort_on_drop(),
+                let addresses = ports_in
+                    .iter()
+                    .map(|p| (p.name.clone(), socket_path(&run_dir, &p.name, i)))
+                    .collect();
+                let process_state = ProcessState {
+                    handle: handle.abort_on_drop(),
+                    spec,
                     status: ProcessStatus::NotReady,
                     status_time: Utc::now(),
-                    labels: labels.iter().map(|(k, v)| (k.clone(), v.clone())).collect(),
+                    ever_ready: false,
+                    launched_at: None,
+                    launch_error: None,
+                    labels: labels.clone(),
+                    addresses,
                     tcp_proxy_addrs,
-                });
+                };
+                // A new or restarted process may listen on new TCP proxy
+                // addresses, so notify watchers.
+                let _ = self
+                    .service_address_tx
+                    .send(process_state.address_event(&id, i));
+                process_states.push(process_state);
             }
-
-            // Update the in-memory process state. We do this after we've created
-            // all process states to avoid partially updating our in-memory state.
-            process_states.truncate(scale.into());
-            process_states.extend(new_process_states);
         }
 
         self.maybe_write_prometheus_service_discovery_file().await;
@@ -644,6 +1973,7 @@ impl OrchestratorWorker {
         let full_id = self.config.full_id(id);
         let run_dir = self.config.service_run_dir(id);
         let scratch_dir = self.config.service_scratch_dir(id);
+        let tmp_dir = self.config.service_tmp_dir(id);
 
         // Drop the supervisor for the service, if it exists. If this service
         // was under supervision, this will kill all processes associated with
@@ -672,7 +2002,27 @@ impl OrchestratorWorker {
             }
         }
 
+        if let Some(cpu_allocator) = &self.config.cpu_allocator {
+            cpu_allocator.release(&full_id);
+        }
+
         // Clean up the on-disk state of the service.
+        if is_mount_point(&scratch_dir).await {
+            if let Err(e) = unmount_scratch_filesystem(&scratch_dir).await {
+                warn!(
+                    "error unmounting scratch directory for {full_id}: {}",
+                    e.display_with_causes()
+                );
+            }
+        }
+        if let Err(e) = fs::remove_file(self.config.service_scratch_image(id)).await {
+            if e.kind() != io::ErrorKind::NotFound {
+                warn!(
+                    "error removing scratch image for {full_id}: {}",
+                    e.display_with_causes()
+                );
+            }
+        }
         if let Err(e) = remove_dir_all(run_dir).await {
             if e.kind() != io::ErrorKind::NotFound {
                 warn!(
@@ -689,11 +2039,122 @@ impl OrchestratorWorker {
                 );
             }
         }
+        if let Err(e) = remove_dir_all(tmp_dir).await {
+            if e.kind() != io::ErrorKind::NotFound {
+                warn!(
+                    "error cleaning up temporary directory for {full_id}: {}",
+                    e.display_with_causes()
+                );
+            }
+        }
 
         self.maybe_write_prometheus_service_discovery_file().await;
         Ok(())
     }
 
+    async fn shutdown(&mut self) {
+        let services = {
+            let mut services = self.services.lock().expect("lock poisoned");
+            std::mem::take(&mut *services)
+        };
+
+        // Stopping a supervisor drops its TCP proxies and terminates its
+        // process.
+        let mut pids = vec![];
+        let mut paths = vec![];
+        let mut supervisors = vec![];
+        for (id, process_states) in services {
+            let run_dir = self.config.service_run_dir(&id);
+            for (i, process_state) in process_states.into_iter().enumerate() {
+                info!("{}-{i} shutting down", self.config.full_id(&id));
+                pids.extend(process_state.pid());
+                paths.push(run_dir.join(format!("{i}.pid")));
+                paths.extend(process_state.addresses.values().map(PathBuf::from));
+                supervisors.push(process_state.handle.abort_and_wait());
+            }
+        }
+        future::join_all(supervisors).await;
+
+        // The processes have received `SIGKILL` at the latest, but may not
+        // have exited yet.
+        let deadline = time::Instant::now() + SHUTDOWN_GRACE_PERIOD;
+        let exits = pids.into_iter().map(|pid| async move {
+            let exit = wait_for_exit(pid, Duration::from_millis(100));
+            time::timeout_at(deadline, exit).await.err().map(|_| pid)
+        });
+        let pids: Vec<_> = future::join_all(exits)
+            .await
+            .into_iter()
+            .flatten()
+            .collect();
+        if !pids.is_empty() {
+            warn!(
+                "{}: processes {pids:?} did not exit during shutdown",
+                self.config.namespace
+            );
+        }
+
+        for path in paths {
+            if let Err(e) = fs::remove_file(&path).await {
+                if e.kind() != io::ErrorKind::NotFound {
+                    warn!(
+                        "error removing {} during shutdown: {}",
+                        path.display(),
+                        e.display_with_causes()
+                    );
+                }
+            }
+        }
+
+        self.maybe_write_prometheus_service_discovery_file().await;
+    }
+
+    async fn forward_signal(&self, signal: Signal, grace_period: Duration) {
+        self.config.stopping.store(true, Ordering::SeqCst);
+
+        let pids: Vec<_> = {
+            let services = self.services.lock().expect("lock poisoned");
+            services
+                .values()
+                .flatten()
+                .filter_map(|process_state| process_state.pid())
+                .collect()
+        };
+        for pid in &pids {
+            let Ok(raw_pid) = i32::try_from(pid.as_u32()) else {
+                continue;
+            };
+            // Processes adopted from a previous orchestrator may not lead a
+            // process group, so fall back to signaling the process alone.
+            let group = nix::unistd::Pid::from_raw(raw_pid);
+            if killpg(group, signal).is_err() {
+                if let Err(e) = kill(group, signal) {
+                    warn!(
+                        "{}: failed to forward {signal} to process {pid}: {e}",
+                        self.config.namespace
+                    );
+                }
+            }
+        }
+
+        let deadline = time::Instant::now() + grace_period;
+        let exits = pids.into_iter().map(|pid| async move {
+            let exit = wait_for_exit(pid, Duration::from_millis(100));
+            time::timeout_at(deadline, exit).await.err().map(|_| pid)
+        });
+        let pids: Vec<_> = future::join_all(exits)
+            .await
+            .into_iter()
+            .flatten()
+            .collect();
+        if !pids.is_empty() {
+            warn!(
+                "{}: processes {pids:?} did not exit within {grace_period:?} of {signal}",
+                self.config.namespace
+            );
+        }
+    }
+
     async fn list_services(&self) -> Result<Vec<String>, anyhow::Error> {
         let mut services = vec![];
         let namespace_prefix = format!("{}-", self.config.namespace);
@@ -723,16 +2184,75 @@ impl OrchestratorWorker {
             memory_limit,
             cpu_limit,
             disk,
-            launch_spec,
+            launcher,
+            predecessor,
+            ensured_at,
         }: ServiceProcessConfig,
     ) -> impl Future<Output = ()> {
         let suppress_output = self.config.suppress_output;
         let propagate_crashes = self.config.propagate_crashes;
+        let crash_loop = self.config.crash_loop.clone();
         let command_wrapper = self.config.command_wrapper.clone();
-        let image = self.config.image_dir.join(image);
+        let remote = self.config.remote_process(&id, &image, i);
+        let crash_debugging = self
+            .config
+            .crash_debugging
+            .clone()
+            .filter(|_| remote.is_none());
+        let pty = self.config.pty && !suppress_output && remote.is_none();
+        let process_group = self.config.process_groups && remote.is_none();
+        let tmp_dir = (self.config.private_dirs && remote.is_none())
+            .then(|| self.config.service_tmp_dir(&id).join(i.to_string()));
+        let namespace_config = Arc::clone(&self.config);
+        let shaping = self
+            .config
+            .tcp_proxy
+            .as_ref()
+            .and_then(|p| p.shaping.get(&self.config.namespace))
+            .cloned();
+        let image_dir = self.config.image_dir.clone();
+        let image = image_dir.join(image);
         let pid_file = run_dir.join(format!("{i}.pid"));
         let full_id = self.config.full_id(&id);
 
+        let oom_kills = self
+            .config
+            .metrics
+            .oom_kills
+            .with_label_values(&[self.config.namespace.as_str(), id.as_str()]);
+        let profiles = self
+            .config
+            .metrics
+            .profiles
+            .with_label_values(&[self.config.namespace.as_str(), id.as_str()]);
+        let start_latency = self
+            .config
+            .metrics
+            .start_latency
+            .with_label_values(&[self.config.namespace.as_str(), id.as_str()]);
+
+        let cpu_list = match (&self.config.cpu_allocator, &remote) {
+            (Some(cpu_allocator), None) => Some(cpu_allocator.assign(&full_id, i)),
+            _ => None,
+        };
+
+        let sidecars = match &remote {
+            None => self.config.sidecars.clone(),
+            Some(_) => vec![],
+        };
+
+        let profiling = match &remote {
+            None => self.config.profiling.clone(),
+            Some(_) => None,
+        };
+
+        let clock_skew = match (&self.config.clock_skew, &remote) {
+            (Some(clock_skew), None) => clock_skew
+                .offset(&self.config.namespace, &full_id)
+                .map(|offset| (clock_skew.libfaketime.clone(), offset)),
+            _ => None,
+        };
+
         let state_updater = ProcessStateUpdater {
             namespace: self.config.namespace.clone(),
             id,
@@ -741,17 +2261,41 @@ impl OrchestratorWorker {
             service_event_tx: self.service_event_tx.clone(),
         };
 
-        let listen_addrs = ports
+        let listen_addrs: BTreeMap<_, _> = ports
             .iter()
             .map(|p| {
                 let addr = socket_path(&run_dir, &p.name, i);
                 (p.name.clone(), addr)
             })
             .collect();
-        let mut args = args(&listen_addrs);
+
+        // Remote processes listen on sockets in their remote run directory,
+        // which `ssh` forwards to the local sockets.
+        let (mut args, forwards) = match &remote {
+            None => (args(&listen_addrs), vec![]),
+            Some(remote) => {
+                let remote_listen_addrs: BTreeMap<_, _> = ports
+                    .iter()
+                    .map(|p| {
+                        let addr = remote.run_dir.join(format!("{}-{i}", p.name));
+                        (p.name.clone(), addr.display().to_string())
+                    })
+                    .collect();
+                let forwards = listen_addrs
+                    .iter()
+                    .map(|(name, local)| (local.clone(), remote_listen_addrs[name].clone()))
+                    .collect();
+                (args(&remote_listen_addrs), forwards)
+            }
+        };
 
         if disk {
-            if let Some(scratch) = &scratch_dir {
+            if let Some(remote) = &remote {
+                args.push(format!(
+                    "--scratch-directory={}",
+                    remote.scratch_dir.display()
+                ));
+            } else if let Some(scratch) = &scratch_dir {
                 args.push(format!("--scratch-directory={}", scratch.display()));
             } else {
                 panic!("internal error: service requested disk but no scratch directory was configured");
@@ -759,6 +2303,13 @@ impl OrchestratorWorker {
         }
 
         async move {
+            // Wait for the supervisor of the process's previous spec to
+            // terminate the process, so the two don't contend for the PID file
+            // and sockets.
+            if let Some(predecessor) = predecessor {
+                predecessor.abort_and_wait().await;
+            }
+
             let mut proxy_handles = vec![];
             for port in ports {
                 if let Some(tcp_listener) = port.tcp_proxy_listener {
@@ -768,29 +2319,142 @@ impl OrchestratorWorker {
                     );
                     let uds_path = &listen_addrs[&port.name];
                     let handle = mz_ore::task::spawn(
-                        || format!("{full_id}-{i}-proxy-{}", port.name),
+                        || format!("process-orchestrator:{full_id}-{i}-proxy-{}", port.name),
                         tcp_proxy(TcpProxyConfig {
                             name: format!("{full_id}-{i}-{}", port.name),
                             tcp_listener,
                             uds_path: uds_path.clone(),
+                            shaping: shaping.clone(),
                         }),
                     );
                     proxy_handles.push(handle.abort_on_drop());
                 }
             }
 
-            supervise_existing_process(&state_updater, &pid_file).await;
+            let mut sidecar_handles = vec![];
+            for sidecar in sidecars {
+                let handle = mz_ore::task::spawn(
+                    || {
+                        format!(
+                            "process-orchestrator:{full_id}-{i}-sidecar-{}",
+                            sidecar.name
+                        )
+                    },
+                    supervise_sidecar(SidecarConfig {
+                        name: format!("{full_id}-{i}-{}", sidecar.name),
+                        command: sidecar
+                            .command
+                            .iter()
+                            .map(|part| interpolate_command(part, &full_id, &listen_addrs))
+                            .collect(),
+                        run_dir: run_dir.clone(),
+                        full_id: full_id.clone(),
+                        i,
+                        pid_file: pid_file.clone(),
+                        suppress_output,
+                    }),
+                );
+                sidecar_handles.push(handle.abort_on_drop());
+            }
+
+            let _profiling_handle = profiling.map(|config| {
+                mz_ore::task::spawn(
+                    || format!("process-orchestrator:{full_id}-{i}-profiling"),
+                    monitor_for_profiling(ProfilingMonitorConfig {
+                        name: format!("{full_id}-{i}"),
+                        config,
+                        run_dir: run_dir.clone(),
+                        full_id: full_id.clone(),
+                        i,
+                        pid_file: pid_file.clone(),
+                        suppress_output,
+                        profiles,
+                    }),
+                )
+                .abort_on_drop()
+            });
+
+            // Only the first launch after the service was ensured counts
+            // towards the start latency, unless an existing process was
+            // adopted instead.
+            let adopted = supervise_existing_process(&state_updater, &pid_file).await;
+            let mut ensured_at = (!adopted).then_some(ensured_at);
 
+            let process_name = format!("{full_id}-{i}");
+            let mut relaunches = VecDeque::new();
             loop {
-                let mut cmd = launch_spec.refine_command(
-                    &image,
-                    &args,
-                    &command_wrapper,
-                    &full_id,
-                    &listen_addrs,
-                    memory_limit.as_ref(),
-                    cpu_limit.as_ref(),
-                );
+                if namespace_config.stopping.load(Ordering::SeqCst) {
+                    return;
+                }
+                // The local sockets of remote processes are held by the `ssh`
+                // processes that forward them.
+                if remote.is_none() {
+                    if let Err(e) = free_sockets(&process_name, &listen_addrs, &pid_file).await {
+                        let e = e.display_with_causes().to_string();
+                        error!("{e}; retrying in 5s");
+                        state_updater.update_launch_error(Some(e));
+                        time::sleep(Duration::from_secs(5)).await;
+                        continue;
+                    }
+                    state_updater.update_launch_error(None);
+                }
+                let mut cmd = match &remote {
+                    None => launcher.command(&ProcessLaunch {
+                        image: OsStr::new(&image),
+                        args: &args,
+                        wrapper: &command_wrapper,
+                        full_id: &full_id,
+                        name: &process_name,
+                        listen_addrs: &listen_addrs,
+                        memory_limit: memory_limit.as_ref(),
+                        cpu_limit: cpu_limit.as_ref(),
+                        cpu_list: cpu_list.as_deref(),
+                    }),
+                    Some(remote) => {
+                        // Sync on every launch to pick up rebuilt images.
+                        if let Err(e) = remote.sync_image(&image).await {
+                            error!(
+                                "{full_id}-{i} failed to sync image: {}; retrying in 5s",
+                                e.display_with_causes()
+                            );
+                            time::sleep(Duration::from_secs(5)).await;
+                            continue;
+                        }
+                        remote.command(&args, &forwards)
+                    }
+                };
+                if let Some((libfaketime, offset)) = &clock_skew {
+                    info!("{full_id}-{i}: offsetting wall clock by {offset}");
+                    cmd.env("LD_PRELOAD", libfaketime);
+                    cmd.env("FAKETIME", offset.faketime_spec());
+                    // Only the wall clock is skewed. Monotonic clocks, which
+                    // drive timers, are left alone.
+                    cmd.env("DONT_FAKE_MONOTONIC", "1");
+                }
+                if let Some(tmp_dir) = &tmp_dir {
+                    // Recreated on every launch, in case the process or a
+                    // developer removed it. The path must be absolute, as the
+                    // process runs in another working directory.
+                    let created = match fs::create_dir_all(tmp_dir).await {
+                        Ok(()) => fs::canonicalize(tmp_dir).await,
+                        Err(e) => Err(e),
+                    };
+                    match created {
+                        Ok(tmp_dir) => {
+                            cmd.current_dir(&run_dir);
+                            cmd.env("TMPDIR", tmp_dir);
+                        }
+                        Err(e) => {
+                            error!(
+                                "{full_id}-{i} failed to create temporary directory: {}; \
+                                 retrying in 5s",
+                                e.display_with_causes()
+                            );
+                            time::sleep(Duration::from_secs(5)).await;
+                            continue;
+                        }
+                    }
+                }
                 info!(
                     "launching {full_id}-{i} via {} {}...",
                     cmd.as_std().get_program().to_string_lossy(),
@@ -799,24 +2463,119 @@ impl OrchestratorWorker {
                         .map(|arg| arg.to_string_lossy())
                         .join(" ")
                 );
+                if process_group {
+                    cmd.process_group(0);
+                }
                 if suppress_output {
                     cmd.stdout(Stdio::null());
                     cmd.stderr(Stdio::null());
+                } else if pty {
+                    match attach_pty(&mut cmd) {
+                        Ok(master) => {
+                            mz_ore::task::spawn(
+                                || format!("process-orchestrator:{full_id}-{i}-pty"),
+                                forward_pty_output(master),
+                            );
+                        }
+                        Err(e) => warn!(
+                            "{full_id}-{i} failed to attach pseudo-terminal: {}; \
+                             inheriting output instead",
+                            e.display_with_causes()
+                        ),
+                    }
                 }
-                match spawn_process(&state_updater, cmd, &pid_file, !command_wrapper.is_empty())
-                    .await
-                {
-                    Ok(status) => {
-                        if propagate_crashes && did_process_crash(status) {
+                let launched_at = ensured_at.take().map(|ensured_at| {
+                    let now = time::Instant::now();
+                    start_latency.observe(now.duration_since(ensured_at).as_secs_f64());
+                    now
+                });
+                if let Some(launched_at) = launched_at {
+                    state_updater.update_launched_at(launched_at);
+                }
+                let exit = spawn_process(
+                    &state_updater,
+                    cmd,
+                    &pid_file,
+                    !command_wrapper.is_empty(),
+                    process_group,
+                    remote.is_none(),
+                )
+                .await;
+                if namespace_config.stopping.load(Ordering::SeqCst) {
+                    info!("{full_id}-{i} exited after a forwarded signal; not relaunching");
+                    return;
+                }
+                let mut crashed = false;
+                let status = match exit {
+                    Ok(ProcessExit {
+                        status,
+                        pid,
+                        oom_killed,
+                    }) => {
+                        crashed = did_process_crash(status);
+                        if let (true, Some(crash_debugging)) = (crashed, &crash_debugging) {
+                            report_crash(
+                                crash_debugging,
+                                &process_name,
+                                &image,
+                                pid,
+                                // Processes with private directories run in
+                                // their run directory.
+                                tmp_dir.is_some().then_some(run_dir.as_path()),
+                                &run_dir,
+                                i,
+                            )
+                            .await;
+                        }
+                        if propagate_crashes && crashed {
                             panic!("{full_id}-{i} crashed; aborting because propagate_crashes is enabled");
                         }
-                        error!("{full_id}-{i} exited: {:?}; relaunching in 5s", status);
+                        if oom_killed {
+                            oom_kills.inc();
+                            error!("{full_id}-{i} was killed by the OOM killer; relaunching in 5s");
+                            ProcessStatus::OomKilled
+                        } else {
+                            error!("{full_id}-{i} exited: {:?}; relaunching in 5s", status);
+                            ProcessStatus::NotReady
+                        }
                     }
                     Err(e) => {
                         error!("{full_id}-{i} failed to spawn: {}; relaunching in 5s", e);
+                        ProcessStatus::NotReady
                     }
                 };
-                state_updater.update_state(ProcessStatus::NotReady);
+                if let Some(crash_loop) = &crash_loop {
+                    let now = time::Instant::now();
+                    relaunches.push_back(now);
+                    while relaunches
+                        .front()
+                        .map_or(false, |at| now.duration_since(*at) > crash_loop.window)
+                    {
+                        relaunches.pop_front();
+                    }
+                    if relaunches.len() > crash_loop.max_restarts {
+                        error!(
+                            "{full_id}-{i} exited {} times within {:?}; quarantining it until \
+                             the service is ensured again",
+                            relaunches.len(),
+                            crash_loop.window,
+                        );
+                        state_updater.update_state(ProcessStatus::Quarantined);
+                        return;
+                    }
+                }
+                state_updater.update_state(status);
+                if let (true, Some(crash_debugging)) = (crashed, &crash_debugging) {
+                    if !crash_debugging.hold.is_zero() {
+                        info!(
+                            "{full_id}-{i}: holding back relaunch for {:?} to allow inspecting \
+                             {}",
+                            crash_debugging.hold,
+                            run_dir.display(),
+                        );
+                        time::sleep(crash_debugging.hold).await;
+                    }
+                }
                 time::sleep(Duration::from_secs(5)).await;
             }
         }
@@ -861,9 +2620,29 @@ impl OrchestratorWorker {
             }
         }
 
-        let path = dir.join(Path::new(&self.config.namespace).with_extension("json"));
+        let path = dir.join(format!("{}.json", self.config.namespace));
+        if static_configs.is_empty() {
+            if let Err(e) = fs::remove_file(&path).await {
+                if e.kind() != io::ErrorKind::NotFound {
+                    warn!(
+                        "{}: failed to remove prometheus service discovery file: {}",
+                        self.config.namespace,
+                        e.display_with_causes()
+                    );
+                }
+            }
+            return;
+        }
+
+        // Prometheus may read the file at any time, so replace it atomically.
+        let tmp_path = dir.join(format!("{}.json.tmp", self.config.namespace));
         let contents = serde_json::to_vec_pretty(&static_configs).expect("valid json");
-        if let Err(e) = fs::write(&path, &contents).await {
+        let result = async {
+            fs::write(&tmp_path, &contents).await?;
+            fs::rename(&tmp_path, &path).await
+        }
+        .await;
+        if let Err(e) = result {
             warn!(
                 "{}: failed to write prometheus service discovery file: {}",
                 self.config.namespace,
@@ -884,7 +2663,28 @@ struct ServiceProcessConfig<'a> {
     disk: bool,
     memory_limit: Option<MemoryLimit>,
     cpu_limit: Option<CpuLimit>,
-    launch_spec: LaunchSpec,
+    launcher: Arc<dyn ProcessLauncher>,
+    /// The supervisor of the process with the same ordinal that this one
+    /// replaces, if its spec changed.
+    predecessor: Option<AbortOnDropHandle<()>>,
+    /// When the service was ensured, for the start latency metric.
+    ensured_at: time::Instant,
+}
+
+/// The parts of a [`ServiceConfig`] that determine how a process is launched.
+///
+/// When a service is ensured again with a different spec for one of its
+/// processes, that process is restarted, like Kubernetes rolls out a changed
+/// pod template. Processes whose spec is unchanged keep running.
+#[derive(Debug, Clone, PartialEq)]
+struct ProcessSpec {
+    image: String,
+    /// The arguments of the process, as evaluated for its listen addresses.
+    args: Vec<String>,
+    ports: Vec<String>,
+    memory_limit: Option<MemoryLimit>,
+    cpu_limit: Option<CpuLimit>,
+    disk: bool,
 }
 
 struct ServiceProcessPort {
@@ -893,7 +2693,9 @@ struct ServiceProcessPort {
 }
 
 /// Supervises an existing process, if it exists.
-async fn supervise_existing_process(state_updater: &ProcessStateUpdater, pid_file: &Path) {
+///
+/// Returns whether an existing process was found.
+async fn supervise_existing_process(state_updater: &ProcessStateUpdater, pid_file: &Path) -> bool {
     let name = format!(
         "{}-{}-{}",
         state_updater.namespace, state_updater.id, state_updater.i
@@ -901,7 +2703,7 @@ async fn supervise_existing_process(state_updater: &ProcessStateUpdater, pid_fil
 
     let mut system = System::new();
     let Some(process) = find_process_from_pid_file(&mut system, pid_file).await else {
-        return;
+        return false;
     };
     let pid = process.pid();
 
@@ -918,22 +2720,79 @@ async fn supervise_existing_process(state_updater: &ProcessStateUpdater, pid_fil
         }
     }
 
-    // Periodically check if the process has terminated.
-    let mut system = System::new();
-    while system.refresh_process_specifics(pid, ProcessRefreshKind::new()) {
-        time::sleep(Duration::from_secs(5)).await;
-    }
+    // Wait for the process to terminate.
+    wait_for_exit(pid, Duration::from_secs(5)).await;
 
     // The process has crashed. Exit the function without attempting to
     // kill it.
     warn!(%pid, "process for {name} has crashed; will reboot");
-    need_kill.store(false, Ordering::SeqCst)
+    need_kill.store(false, Ordering::SeqCst);
+    true
 }
 
-fn interpolate_command(
-    command_part: &str,
-    full_id: &str,
-    ports: &BTreeMap<String, String>,
+/// Waits for the process with the given PID to exit.
+///
+/// The process need not be a child of this process. On Linux, this waits on a
+/// pidfd, which the kernel makes readable as soon as the process exits.
+/// Elsewhere, or if the kernel doesn't support pidfds, this falls back to
+/// checking whether the process is still running every `poll_interval`.
+async fn wait_for_exit(pid: Pid, poll_interval: Duration) {
+    #[cfg(target_os = "linux")]
+    match wait_for_pidfd(pid).await {
+        Ok(()) => return,
+        // The process exited before the pidfd could be opened.
+        Err(e) if e.raw_os_error() == Some(libc::ESRCH) => return,
+        Err(e) => debug!(
+            %pid,
+            "cannot wait on pidfd: {}; polling for exit instead",
+            e.display_with_causes()
+        ),
+    }
+
+    let mut system = System::new();
+    while is_process_running(&mut system, pid) {
+        time::sleep(poll_interval).await;
+    }
+}
+
+/// Waits for the process with the given PID to exit by polling a pidfd for
+/// it.
+#[cfg(target_os = "linux")]
+async fn wait_for_pidfd(pid: Pid) -> Result<(), io::Error> {
+    use std::os::fd::OwnedFd;
+
+    use tokio::io::unix::AsyncFd;
+    use tokio::io::Interest;
+
+    let pid = libc::pid_t::try_from(pid.as_u32())
+        .map_err(|_| io::Error::from_raw_os_error(libc::ESRCH))?;
+    // SAFETY: `pidfd_open` takes no pointers.
+    let fd = unsafe { libc::syscall(libc::SYS_pidfd_open, pid, 0) };
+    if fd < 0 {
+        return Err(io::Error::last_os_error());
+    }
+    let fd = i32::try_from(fd).expect("file descriptors fit in an i32");
+    // SAFETY: `pidfd_open` returns a newly opened file descriptor that is
+    // owned by nothing else.
+    let fd = unsafe { OwnedFd::from_raw_fd(fd) };
+    let fd = AsyncFd::with_interest(fd, Interest::READABLE)?;
+    let _guard = fd.readable().await?;
+    Ok(())
+}
+
+/// Reports whether the process with the given PID is running, i.e., exists
+/// and is not a zombie.
+fn is_process_running(system: &mut System, pid: Pid) -> bool {
+    system.refresh_process_specifics(pid, ProcessRefreshKind::new())
+        && system.process(pid).map_or(false, |process| {
+            process.status() != sysinfo::ProcessStatus::Zombie
+        })
+}
+
+fn interpolate_command(
+    command_part: &str,
+    full_id: &str,
+    ports: &BTreeMap<String, String>,
 ) -> String {
     let mut command_part = command_part.replace("%N", full_id);
     for (endpoint, port) in ports {
@@ -942,29 +2801,221 @@ fn interpolate_command(
     command_part
 }
 
+/// Configures a sidecar supervised by [`supervise_sidecar`].
+struct SidecarConfig {
+    /// The name of the sidecar, qualified by its process, for logs.
+    name: String,
+    /// The interpolated command of the sidecar.
+    command: Vec<String>,
+    run_dir: PathBuf,
+    full_id: String,
+    i: usize,
+    pid_file: PathBuf,
+    suppress_output: bool,
+}
+
+/// Runs a sidecar, relaunching it whenever it exits.
+///
+/// The sidecar is killed when the returned future is dropped.
+async fn supervise_sidecar(
+    SidecarConfig {
+        name,
+        command,
+        run_dir,
+        full_id,
+        i,
+        pid_file,
+        suppress_output,
+    }: SidecarConfig,
+) {
+    let (program, args) = command.split_first().expect("validated non-empty");
+    loop {
+        let mut cmd = Command::new(program);
+        cmd.args(args)
+            .current_dir(&run_dir)
+            .env("MZ_SERVICE_ID", &full_id)
+            .env("MZ_PROCESS_INDEX", i.to_string())
+            .env("MZ_PROCESS_PID_FILE", &pid_file)
+            .kill_on_drop(true);
+        if suppress_output {
+            cmd.stdout(Stdio::null());
+            cmd.stderr(Stdio::null());
+        }
+        info!("launching sidecar {name} via {}...", command.join(" "));
+        match cmd.spawn() {
+            Ok(mut child) => match child.wait().await {
+                Ok(status) => warn!("sidecar {name} exited: {:?}; relaunching in 5s", status),
+                Err(e) => warn!("sidecar {name} failed: {}; relaunching in 5s", e),
+            },
+            Err(e) => warn!("sidecar {name} failed to spawn: {}; relaunching in 5s", e),
+        }
+        time::sleep(Duration::from_secs(5)).await;
+    }
+}
+
+/// Configures a monitor run by [`monitor_for_profiling`].
+struct ProfilingMonitorConfig {
+    /// The name of the monitored process, for logs.
+    name: String,
+    config: ProcessOrchestratorProfilingConfig,
+    run_dir: PathBuf,
+    full_id: String,
+    i: usize,
+    pid_file: PathBuf,
+    suppress_output: bool,
+    profiles: IntCounter,
+}
+
+/// Samples the resource usage of a process every second, capturing a profile
+/// of the process whenever it exceeds a threshold of the profiling
+/// configuration for a sustained period.
+///
+/// The process is found through its PID file, so the monitor follows the
+/// process across relaunches. An in-progress capture is killed when the
+/// returned future is dropped.
+async fn monitor_for_profiling(
+    ProfilingMonitorConfig {
+        name,
+        config,
+        run_dir,
+        full_id,
+        i,
+        pid_file,
+        suppress_output,
+        profiles,
+    }: ProfilingMonitorConfig,
+) {
+    let (program, args) = config.command.split_first().expect("validated non-empty");
+    let mut system = System::new();
+    let mut pid = None;
+    let mut exceeded_since = None;
+    let mut last_capture: Option<time::Instant> = None;
+    let mut interval = time::interval(Duration::from_secs(1));
+    interval.set_missed_tick_behavior(time::MissedTickBehavior::Delay);
+    loop {
+        interval.tick().await;
+
+        let running = pid.map_or(false, |pid| {
+            system.refresh_process_specifics(pid, ProcessRefreshKind::new().with_cpu())
+        });
+        if !running {
+            // The process has not been launched yet or was relaunched.
+            exceeded_since = None;
+            pid = find_process_from_pid_file(&mut system, &pid_file)
+                .await
+                .map(|process| process.pid());
+            continue;
+        }
+        let Some(process) = pid.and_then(|pid| system.process(pid)) else {
+            continue;
+        };
+        let process_pid = process.pid();
+        let memory = process.memory();
+        let cpu = f64::from(process.cpu_usage()) / 100.0;
+        let exceeded = config.memory_threshold.map_or(false, |t| memory > t)
+            || config.cpu_threshold.map_or(false, |t| cpu > t);
+        if !exceeded {
+            exceeded_since = None;
+            continue;
+        }
+
+        let now = time::Instant::now();
+        let since = *exceeded_since.get_or_insert(now);
+        let rate_limited =
+            last_capture.map_or(false, |at| now.duration_since(at) < config.min_interval);
+        if now.duration_since(since) < config.sustain || rate_limited {
+            continue;
+        }
+        last_capture = Some(now);
+        exceeded_since = None;
+
+        let path = run_dir.join(format!(
+            "{i}-profile-{}",
+            Utc::now().format("%Y%m%dT%H%M%SZ")
+        ));
+        info!(
+            "{name} used {memory} bytes of memory and {cpu:.2} CPU cores for at least {:?}; \
+             capturing profile to {}",
+            config.sustain,
+            path.display()
+        );
+        let mut cmd = Command::new(program);
+        cmd.args(args)
+            .current_dir(&run_dir)
+            .env("MZ_SERVICE_ID", &full_id)
+            .env("MZ_PROCESS_INDEX", i.to_string())
+            .env("MZ_PROCESS_PID", process_pid.to_string())
+            .env("MZ_PROFILE_PATH", &path)
+            .kill_on_drop(true);
+        if suppress_output {
+            cmd.stdout(Stdio::null());
+            cmd.stderr(Stdio::null());
+        }
+        match cmd.status().await {
+            Ok(status) if status.success() => {
+                profiles.inc();
+                info!("{name}: captured profile to {}", path.display());
+            }
+            Ok(status) => warn!("{name}: profile capture exited: {:?}", status),
+            Err(e) => warn!("{name}: profile capture failed to spawn: {}", e),
+        }
+    }
+}
+
+/// How a process launched by [`spawn_process`] exited.
+struct ProcessExit {
+    status: ExitStatus,
+    /// The PID the process had.
+    pid: Pid,
+    /// Whether the process was killed by the OOM killer.
+    oom_killed: bool,
+}
+
 async fn spawn_process(
     state_updater: &ProcessStateUpdater,
     mut cmd: Command,
     pid_file: &Path,
     send_sigterm: bool,
-) -> Result<ExitStatus, anyhow::Error> {
-    struct KillOnDropChild(Child, bool);
+    process_group: bool,
+    detect_oom_kills: bool,
+) -> Result<ProcessExit, anyhow::Error> {
+    /// A child that is killed when dropped. If the child leads its own process
+    /// group, the whole group is signaled.
+    struct KillOnDropChild {
+        child: Child,
+        send_sigterm: bool,
+        process_group: bool,
+    }
 
     impl Drop for KillOnDropChild {
         fn drop(&mut self) {
-            if let (Some(pid), true) = (self.0.id().and_then(|id| i32::try_from(id).ok()), self.1) {
-                let _ = nix::sys::signal::kill(
-                    nix::unistd::Pid::from_raw(pid),
-                    nix::sys::signal::Signal::SIGTERM,
-                );
+            let pid = self.child.id().and_then(|id| i32::try_from(id).ok());
+            let signal = |signal| {
+                if let Some(pid) = pid {
+                    let pid = nix::unistd::Pid::from_raw(pid);
+                    let _ = match self.process_group {
+                        true => killpg(pid, signal),
+                        false => kill(pid, signal),
+                    };
+                }
+            };
+            if pid.is_some() && (self.send_sigterm || self.process_group) {
+                signal(Signal::SIGTERM);
                 // Give the process a bit of time to react to the signal
                 tokio::task::block_in_place(|| std::thread::sleep(Duration::from_millis(500)));
             }
-            let _ = self.0.start_kill();
+            if self.process_group {
+                signal(Signal::SIGKILL);
+            }
+            let _ = self.child.start_kill();
         }
     }
 
-    let mut child = KillOnDropChild(cmd.spawn()?, send_sigterm);
+    let mut child = KillOnDropChild {
+        child: cmd.spawn()?,
+        send_sigterm,
+        process_group,
+    };
 
     // Immediately write out a file containing the PID of the child process and
     // its start time. We'll use this state to rediscover our children if we
@@ -974,10 +3025,156 @@ async fn spawn_process(
     // anything more robust given the Unix APIs available to us, and the
     // solution here is good enough given that the process orchestrator is only
     // used in development/testing.
-    let pid = Pid::from_u32(child.0.id().unwrap());
+    let pid = Pid::from_u32(child.child.id().unwrap());
     write_pid_file(pid_file, pid).await?;
     state_updater.update_state(ProcessStatus::Ready { pid });
-    Ok(child.0.wait().await?)
+
+    wait_for_process(&mut child.child, pid, detect_oom_kills).await
+}
+
+/// Waits for the spawned process `child` with the given `pid` to exit.
+async fn wait_for_process(
+    child: &mut Child,
+    pid: Pid,
+    detect_oom_kills: bool,
+) -> Result<ProcessExit, anyhow::Error> {
+    if !detect_oom_kills {
+        let status = child.wait().await?;
+        return Ok(ProcessExit {
+            status,
+            pid,
+            oom_killed: false,
+        });
+    }
+
+    // Watch the OOM kill counter of the process's cgroup while it runs. The
+    // cgroup of a systemd scope is removed as soon as its process exits, so
+    // the counter can't be read only after the fact.
+    let memory_events = cgroup_memory_events_path(pid).await;
+    let initial_oom_kills = match &memory_events {
+        Some(path) => read_oom_kills(path).await,
+        None => None,
+    };
+    let mut oom_kills = initial_oom_kills;
+    let mut interval = time::interval(Duration::from_secs(1));
+    let status = loop {
+        select! {
+            // `Child::wait` is cancel safe.
+            status = child.wait() => break status?,
+            _ = interval.tick() => {
+                if let Some(path) = &memory_events {
+                    oom_kills = read_oom_kills(path).await.or(oom_kills);
+                }
+            }
+        }
+    };
+    if let Some(path) = &memory_events {
+        oom_kills = read_oom_kills(path).await.or(oom_kills);
+    }
+
+    // The OOM killer kills with `SIGKILL`. Directly launched processes share
+    // the cgroup of this process, so the counter alone could also count kills
+    // of other processes.
+    let oom_killed = status.signal() == Some(SIGKILL)
+        && match (initial_oom_kills, oom_kills) {
+            (Some(before), Some(after)) if after > before => true,
+            _ => kernel_log_reports_oom_kill(pid).await,
+        };
+    Ok(ProcessExit {
+        status,
+        pid,
+        oom_killed,
+    })
+}
+
+/// Removes the Prometheus service discovery files in `dir`, including partially
+/// written ones.
+async fn remove_service_discovery_files(dir: &Path) -> Result<(), anyhow::Error> {
+    let mut entries = fs::read_dir(dir).await?;
+    while let Some(entry) = entries.next_entry().await? {
+        let name = entry.file_name();
+        let name = name.to_string_lossy();
+        if name.ends_with(".json") || name.ends_with(".json.tmp") {
+            info!("removing stale {}", entry.path().display());
+            fs::remove_file(entry.path()).await?;
+        }
+    }
+    Ok(())
+}
+
+/// Returns the path to the `memory.events` file of the cgroup of the process
+/// with the given PID, if the process is in a cgroup v2 hierarchy.
+async fn cgroup_memory_events_path(pid: Pid) -> Option<PathBuf> {
+    let contents = fs::read_to_string(format!("/proc/{pid}/cgroup"))
+        .await
+        .ok()?;
+    let cgroup = contents.lines().find_map(|line| line.strip_prefix("0::"))?;
+    let path = Path::new("/sys/fs/cgroup")
+        .join(cgroup.trim_start_matches('/'))
+        .join("memory.events");
+    Some(path)
+}
+
+/// Reads the `oom_kill` counter from a cgroup's `memory.events` file.
+async fn read_oom_kills(memory_events: &Path) -> Option<u64> {
+    let contents = fs::read_to_string(memory_events).await.ok()?;
+    contents
+        .lines()
+        .find_map(|line| line.strip_prefix("oom_kill "))
+        .and_then(|n| n.trim().parse().ok())
+}
+
+/// Reports whether the kernel log records that the OOM killer killed the
+/// process with the given PID.
+///
+/// Reading the kernel log often requires privileges, in which case this
+/// conservatively reports `false`.
+async fn kernel_log_reports_oom_kill(pid: Pid) -> bool {
+    let Ok(output) = Command::new("dmesg").output().await else {
+        return false;
+    };
+    let needle = format!("Killed process {pid} (");
+    String::from_utf8_lossy(&output.stdout)
+        .lines()
+        .any(|line| line.contains(&needle))
+}
+
+/// Connects the standard output and standard error of `cmd` to a new
+/// pseudo-terminal, returning the master end of the pseudo-terminal.
+fn attach_pty(cmd: &mut Command) -> Result<std::fs::File, anyhow::Error> {
+    let OpenptyResult { master, slave } = openpty(None, None)?;
+    // SAFETY: `openpty` returns newly opened file descriptors that are owned
+    // by nothing else.
+    let (master, slave) = unsafe {
+        (
+            std::fs::File::from_raw_fd(master),
+            std::fs::File::from_raw_fd(slave),
+        )
+    };
+    // Keep the master end out of the child. The slave end is duplicated onto
+    // the child's standard streams, which clears the flag.
+    for fd in [master.as_raw_fd(), slave.as_raw_fd()] {
+        fcntl(fd, FcntlArg::F_SETFD(FdFlag::FD_CLOEXEC))?;
+    }
+    // Don't translate newlines into carriage return-newline pairs, so that
+    // the forwarded output can be redirected to a file.
+    let mut attrs = termios::tcgetattr(slave.as_raw_fd())?;
+    attrs.output_flags.remove(OutputFlags::ONLCR);
+    termios::tcsetattr(slave.as_raw_fd(), SetArg::TCSANOW, &attrs)?;
+    cmd.stdout(slave.try_clone()?);
+    cmd.stderr(slave);
+    Ok(master)
+}
+
+/// Forwards the output written to a pseudo-terminal to the standard output of
+/// this process.
+async fn forward_pty_output(master: std::fs::File) {
+    let mut master = fs::File::from_std(master);
+    let mut stdout = io::stdout();
+    // Reads from the master end fail with `EIO` once all slave ends are
+    // closed, i.e., once the process has exited, which ends the copy.
+    let _ = io::copy(&mut master, &mut stdout).await;
+    let _ = stdout.flush().await;
 }
 
 fn did_process_crash(status: ExitStatus) -> bool {
@@ -990,6 +3187,210 @@ fn did_process_crash(status: ExitStatus) -> bool {
     )
 }
 
+/// Reports how to debug the core dump of the crashed process `name`, which had
+/// PID `pid`, ran `image`, and ran in `working_dir` if it didn't inherit the
+/// working directory of this process, and captures its backtraces into
+/// `run_dir` if configured.
+async fn report_crash(
+    config: &ProcessOrchestratorCrashDebugConfig,
+    name: &str,
+    image: &Path,
+    pid: Pid,
+    working_dir: Option<&Path>,
+    run_dir: &Path,
+    i: usize,
+) {
+    let core_dump = match locate_core_dump(pid, working_dir).await {
+        Ok(core_dump) => core_dump,
+        Err(e) => {
+            warn!(
+                "{name} crashed, but its core dump cannot be located: {}",
+                e.display_with_causes()
+            );
+            return;
+        }
+    };
+    if let CoreDump::File(path) = &core_dump {
+        if !fs::try_exists(path).await.unwrap_or(false) {
+            warn!(
+                "{name} crashed, but left no core dump at {}; are core dumps enabled \
+                 (`ulimit -c`)?",
+                path.display()
+            );
+            return;
+        }
+    }
+
+    let command = debugger_command(config.debugger, image, &core_dump, pid, false);
+    error!(
+        "{name} crashed; to debug its core dump, run: {}",
+        command.join(" ")
+    );
+
+    if !config.capture_backtraces {
+        return;
+    }
+    let command = debugger_command(config.debugger, image, &core_dump, pid, true);
+    let mut cmd = Command::new(&command[0]);
+    cmd.args(&command[1..])
+        .current_dir(run_dir)
+        .stdin(Stdio::null())
+        .kill_on_drop(true);
+    let path = run_dir.join(format!(
+        "{i}-crash-{}.txt",
+        Utc::now().format("%Y%m%dT%H%M%SZ")
+    ));
+    let result = async {
+        let output = cmd.output().await?;
+        let mut contents = output.stdout;
+        contents.extend(output.stderr);
+        fs::write(&path, contents).await?;
+        Ok::<_, anyhow::Error>(output.status)
+    }
+    .await;
+    match result {
+        Ok(status) if status.success() => {
+            info!("{name}: wrote backtraces to {}", path.display())
+        }
+        Ok(status) => warn!(
+            "{name}: debugger exited with {status} while capturing backtraces; its output is \
+             in {}",
+            path.display()
+        ),
+        Err(e) => warn!(
+            "{name}: failed to capture backtraces: {}",
+            e.display_with_causes()
+        ),
+    }
+}
+
+/// The location of the core dump of a crashed process.
+#[derive(Debug)]
+enum CoreDump {
+    /// A file at the given path.
+    File(PathBuf),
+    /// The store of `systemd-coredump`, from which `coredumpctl` retrieves core
+    /// dumps by PID.
+    Coredumpctl,
+}
+
+/// Determines where the kernel stores the core dump of the process with the
+/// given PID, which ran in `working_dir`, or in the working directory of this
+/// process if `None`.
+async fn locate_core_dump(
+    pid: Pid,
+    working_dir: Option<&Path>,
+) -> Result<CoreDump, anyhow::Error> {
+    if cfg!(target_os = "macos") {
+        return Ok(CoreDump::File(PathBuf::from(format!("/cores/core.{pid}"))));
+    }
+
+    let pattern = fs::read_to_string("/proc/sys/kernel/core_pattern").await?;
+    let uses_pid = fs::read_to_string("/proc/sys/kernel/core_uses_pid")
+        .await
+        .map_or(false, |s| s.trim() == "1");
+    let working_dir = match working_dir {
+        Some(working_dir) => env::current_dir()?.join(working_dir),
+        None => env::current_dir()?,
+    };
+    core_dump_location(pattern.trim(), uses_pid, pid, &working_dir)
+}
+
+/// Interprets the Linux core pattern `pattern` for the process with the given
+/// PID, which ran in `working_dir`. If `uses_pid` is set, the kernel appends
+/// the PID to patterns that don't contain it.
+///
+/// Of the specifiers in a core pattern, only `%p` and `%%` are supported,
+/// which covers the patterns in common use.
+fn core_dump_location(
+    pattern: &str,
+    uses_pid: bool,
+    pid: Pid,
+    working_dir: &Path,
+) -> Result<CoreDump, anyhow::Error> {
+    if let Some(handler) = pattern.strip_prefix('|') {
+        if handler.contains("systemd-coredump") {
+            return Ok(CoreDump::Coredumpctl);
+        }
+        bail!("core dumps are piped to unsupported handler {handler}");
+    }
+
+    let mut path = String::new();
+    let mut has_pid = false;
+    let mut chars = pattern.chars();
+    while let Some(c) = chars.next() {
+        if c != '%' {
+            path.push(c);
+            continue;
+        }
+        match chars.next() {
+            Some('%') => path.push('%'),
+            Some('p') => {
+                has_pid = true;
+                path.push_str(&pid.to_string());
+            }
+            Some(c) => bail!("core pattern {pattern} uses unsupported specifier %{c}"),
+            None => bail!("core pattern {pattern} ends in a lone %"),
+        }
+    }
+    // Without a PID in the pattern, the kernel appends it if asked to.
+    if uses_pid && !has_pid {
+        path.push_str(&format!(".{pid}"));
+    }
+    // Relative paths are relative to the working directory of the crashed
+    // process.
+    Ok(CoreDump::File(working_dir.join(path)))
+}
+
+/// Returns the program and arguments that open `core_dump` of `image`, which
+/// had PID `pid`, with `debugger`.
+///
+/// If `batch` is set, the debugger prints the backtraces of all threads and
+/// exits instead of starting an interactive session.
+fn debugger_command(
+    debugger: ProcessOrchestratorDebugger,
+    image: &Path,
+    core_dump: &CoreDump,
+    pid: Pid,
+    batch: bool,
+) -> Vec<String> {
+    let image = image.display().to_string();
+    match (core_dump, debugger) {
+        (CoreDump::File(path), ProcessOrchestratorDebugger::Gdb) => {
+            let mut command = vec!["gdb".into()];
+            if batch {
+                command.extend(["-batch".into(), "-ex".into(), "thread apply all bt".into()]);
+            }
+            command.extend([image, path.display().to_string()]);
+            command
+        }
+        (CoreDump::File(path), ProcessOrchestratorDebugger::Lldb) => {
+            let mut command = vec!["lldb".into(), image, "--core".into()];
+            command.push(path.display().to_string());
+            if batch {
+                command.extend(["--batch".into(), "-o".into(), "thread backtrace all".into()]);
+            }
+            command
+        }
+        (CoreDump::Coredumpctl, debugger) => {
+            let (name, batch_args) = match debugger {
+                ProcessOrchestratorDebugger::Gdb => ("gdb", "-batch -ex 'thread apply all bt'"),
+                ProcessOrchestratorDebugger::Lldb => ("lldb", "--batch -o 'thread backtrace all'"),
+            };
+            let mut command = vec![
+                "coredumpctl".into(),
+                "debug".into(),
+                format!("--debugger={name}"),
+            ];
+            if batch {
+                command.push(format!("--debugger-arguments={batch_args}"));
+            }
+            command.push(pid.to_string());
+            command
+        }
+    }
+}
+
 async fn write_pid_file(pid_file: &Path, pid: Pid) -> Result<(), anyhow::Error> {
     let mut system = System::new();
     system.refresh_process_specifics(pid, ProcessRefreshKind::new());
@@ -1005,6 +3406,14 @@ async fn find_process_from_pid_file<'a>(
     let Ok(contents) = fs::read_to_string(pid_file).await else {
         return None;
     };
+    process_from_pid_file_contents(system, &contents)
+}
+
+/// Like [`find_process_from_pid_file`], but for the contents of the PID file.
+fn process_from_pid_file_contents<'a>(
+    system: &'a mut System,
+    contents: &str,
+) -> Option<&'a Process> {
     let lines = contents.trim().split('\n').collect::<Vec<_>>();
     let [pid, start_time] = lines.as_slice() else {
         return None;
@@ -1025,10 +3434,340 @@ async fn find_process_from_pid_file<'a>(
     Some(process)
 }
 
+/// Makes sure that no process outside of our supervision listens on the
+/// sockets in `listen_addrs` before the process `name` is launched.
+///
+/// Such a process makes the new process fail to bind its sockets and be
+/// respawned over and over. If the holder is the process recorded in
+/// `pid_file`, i.e., a previous instance of the process that is still running,
+/// it is killed, and its socket is removed once it has exited. Otherwise, e.g.,
+/// if the holder was left behind by another orchestrator sharing the image
+/// directory, this returns an error naming the holder.
+async fn free_sockets(
+    name: &str,
+    listen_addrs: &BTreeMap<String, String>,
+    pid_file: &Path,
+) -> Result<(), anyhow::Error> {
+    let mut system = System::new();
+    for (port, path) in listen_addrs {
+        if UnixStream::connect(path).await.is_err() {
+            continue;
+        }
+        // Scanning `/proc` does blocking I/O for every file descriptor of
+        // every process.
+        let holder = mz_ore::task::spawn_blocking(
+            || format!("process-orchestrator:{name}-socket-holder"),
+            {
+                let path = path.clone();
+                move || find_unix_socket_holder(&path)
+            },
+        )
+        .wait_and_assert_finished()
+        .await;
+        let recorded = find_process_from_pid_file(&mut system, pid_file)
+            .await
+            .map(|process| process.pid());
+        match holder {
+            Some(pid) if Some(pid) == recorded => {
+                warn!("{name}: killing stale process {pid} listening on {path}");
+                if let Some(process) = system.process(pid) {
+                    process.kill();
+                }
+                wait_for_exit(pid, Duration::from_millis(100)).await;
+                let _ = fs::remove_file(path).await;
+            }
+            Some(pid) => {
+                system.refresh_process_specifics(pid, ProcessRefreshKind::new());
+                bail!(
+                    "cannot launch {name}: socket {path} for port {port} is held by process \
+                     {pid} ({}), which is not ours",
+                    system.process(pid).map_or("unknown", |p| p.name())
+                )
+            }
+            None => bail!(
+                "cannot launch {name}: socket {path} for port {port} is held by an unknown \
+                 process"
+            ),
+        }
+    }
+    Ok(())
+}
+
+/// Returns an error if one of the sockets of the `scale` processes of the
+/// service `full_id`, which listen on `ports` and keep their PID files in
+/// `run_dir`, is held by a process that is not ours.
+///
+/// While the process recorded in the PID file of a process is running, the
+/// sockets of the process are assumed to be held by it or its children, and
+/// conflicts are left to [`free_sockets`] to detect once the process is
+/// relaunched. Only supported on Linux, see [`unix_socket_inode`].
+fn check_socket_conflicts(
+    full_id: &str,
+    run_dir: &Path,
+    ports: &[String],
+    scale: u16,
+) -> Result<(), anyhow::Error> {
+    let mut system = System::new();
+    for i in 0..usize::from(scale) {
+        let mut recorded = None;
+        for port in ports {
+            let path = socket_path(run_dir, port, i);
+            if unix_socket_inode(&path).is_none() {
+                continue;
+            }
+            let running = recorded.get_or_insert_with(|| {
+                std::fs::read_to_string(run_dir.join(format!("{i}.pid")))
+                    .ok()
+                    .and_then(|contents| process_from_pid_file_contents(&mut system, &contents))
+                    .map(|process| process.pid())
+            });
+            if running.is_some() {
+                continue;
+            }
+            match find_unix_socket_holder(&path) {
+                Some(pid) => {
+                    system.refresh_process_specifics(pid, ProcessRefreshKind::new());
+                    bail!(
+                        "cannot launch {full_id}-{i}: socket {path} for port {port} is held by \
+                         process {pid} ({}), which is not ours",
+                        system.process(pid).map_or("unknown", |p| p.name())
+                    )
+                }
+                None => bail!(
+                    "cannot launch {full_id}-{i}: socket {path} for port {port} is held by an \
+                     unknown process"
+                ),
+            }
+        }
+    }
+    Ok(())
+}
+
+/// Returns the PID of a process with an open listening Unix domain socket at
+/// `path`, if one can be found.
+///
+/// Only supported on Linux, where the sockets of all processes are listed in
+/// `/proc/net/unix` and their file descriptors in `/proc/<pid>/fd`.
+fn find_unix_socket_holder(path: &str) -> Option<Pid> {
+    let inode = unix_socket_inode(path)?;
+    let link = format!("socket:[{inode}]");
+    for entry in std::fs::read_dir("/proc").ok()? {
+        let Ok(entry) = entry else { continue };
+        let Some(pid) = entry
+            .file_name()
+            .to_str()
+            .and_then(|s| Pid::from_str(s).ok())
+        else {
+            continue;
+        };
+        let Ok(fds) = std::fs::read_dir(entry.path().join("fd")) else {
+            continue;
+        };
+        for fd in fds.flatten() {
+            if std::fs::read_link(fd.path())
+                .map_or(false, |target| target.as_os_str() == link.as_str())
+            {
+                return Some(pid);
+            }
+        }
+    }
+    None
+}
+
+/// Returns the inode of the open Unix domain socket at `path`, if there is one.
+///
+/// Only supported on Linux, where the sockets of all processes are listed in
+/// `/proc/net/unix`.
+fn unix_socket_inode(path: &str) -> Option<String> {
+    // Each line is of the form `Num RefCount Protocol Flags Type St Inode Path`.
+    let sockets = std::fs::read_to_string("/proc/net/unix").ok()?;
+    sockets.lines().skip(1).find_map(|line| {
+        let fields: Vec<_> = line.split_whitespace().collect();
+        match fields.as_slice() {
+            [_, _, _, _, _, _, inode, socket_path] if *socket_path == path => {
+                Some(inode.to_string())
+            }
+            _ => None,
+        }
+    })
+}
+
+/// Mounts a filesystem of at most `limit_bytes` bytes over `scratch_dir`, unless a
+/// filesystem is already mounted there.
+///
+/// See [`ProcessOrchestratorDiskLimitEnforcement`].
+async fn mount_scratch_filesystem(
+    enforcement: ProcessOrchestratorDiskLimitEnforcement,
+    scratch_dir: &Path,
+    image: &Path,
+    limit_bytes: u64,
+) -> Result<(), anyhow::Error> {
+    if is_mount_point(scratch_dir).await {
+        return Ok(());
+    }
+    let mut cmd = Command::new("mount");
+    match enforcement {
+        ProcessOrchestratorDiskLimitEnforcement::Tmpfs => {
+            cmd.args(["-t", "tmpfs", "-o"])
+                .arg(format!("size={limit_bytes}"))
+                .arg("tmpfs");
+        }
+        ProcessOrchestratorDiskLimitEnforcement::LoopbackImage => {
+            if fs::metadata(image).await.is_err() {
+                let file = fs::File::create(image)
+                    .await
+                    .context("creating scratch image")?;
+                file.set_len(limit_bytes)
+                    .await
+                    .context("sizing scratch image")?;
+                run_command(Command::new("mkfs.ext4").args(["-q", "-F"]).arg(image)).await?;
+            }
+            cmd.args(["-o", "loop"]).arg(image);
+        }
+    }
+    cmd.arg(scratch_dir);
+    run_command(&mut cmd).await
+}
+
+/// Unmounts the filesystem mounted over `scratch_dir` by
+/// [`mount_scratch_filesystem`].
+async fn unmount_scratch_filesystem(scratch_dir: &Path) -> Result<(), anyhow::Error> {
+    // Unmount lazily, as the service's processes may not have exited yet.
+    run_command(Command::new("umount").arg("-l").arg(scratch_dir)).await
+}
+
+/// Reports whether a filesystem is mounted at `path`.
+async fn is_mount_point(path: &Path) -> bool {
+    let Ok(path) = fs::canonicalize(path).await else {
+        return false;
+    };
+    let Ok(mounts) = fs::read_to_string("/proc/self/mounts").await else {
+        return false;
+    };
+    let path = path.to_string_lossy();
+    mounts
+        .lines()
+        .filter_map(|line| line.split(' ').nth(1))
+        .any(|mount_point| mount_point == path)
+}
+
+/// Runs `cmd` to completion, failing if it exits unsuccessfully.
+async fn run_command(cmd: &mut Command) -> Result<(), anyhow::Error> {
+    let program = cmd.as_std().get_program().to_string_lossy().into_owned();
+    let output = cmd
+        .output()
+        .await
+        .with_context(|| format!("running {program}"))?;
+    if !output.status.success() {
+        bail!(
+            "{program} failed: {}: {}",
+            output.status,
+            String::from_utf8_lossy(&output.stderr).trim()
+        );
+    }
+    Ok(())
+}
+
+/// Removes the stale metadata directories of environments other than the one
+/// whose metadata directory is `current`.
+///
+/// See [`ProcessOrchestratorCleanupConfig`] for details.
+async fn cleanup_stale_metadata_dirs(
+    current: &Path,
+    config: &ProcessOrchestratorCleanupConfig,
+) -> Result<(), anyhow::Error> {
+    let Some(parent) = current.parent() else {
+        return Ok(());
+    };
+    let mut entries = fs::read_dir(parent).await?;
+    while let Some(entry) = entries.next_entry().await? {
+        let path = entry.path();
+        let is_metadata_dir = entry
+            .file_name()
+            .to_str()
+            .map_or(false, |name| name.starts_with("environmentd-"));
+        if !is_metadata_dir || path == current || !entry.file_type().await?.is_dir() {
+            continue;
+        }
+
+        let mut live_run_dirs = 0;
+        let mut run_dirs = fs::read_dir(&path).await?;
+        while let Some(run_dir) = run_dirs.next_entry().await? {
+            let run_dir = run_dir.path();
+            if is_stale_run_dir(&run_dir, config.min_age).await? {
+                remove_stale_dir(&run_dir, config.dry_run).await;
+            } else {
+                live_run_dirs += 1;
+            }
+        }
+
+        // In a dry run nothing was actually removed, so only report the
+        // metadata directory if all of its contents would have been.
+        if live_run_dirs == 0 {
+            remove_stale_dir(&path, config.dry_run).await;
+        }
+    }
+    Ok(())
+}
+
+/// Reports whether the service run directory at `run_dir` has not been
+/// modified for at least `min_age` and none of its PID files refer to a live
+/// process.
+async fn is_stale_run_dir(run_dir: &Path, min_age: Duration) -> Result<bool, anyhow::Error> {
+    let metadata = fs::metadata(run_dir).await?;
+    // A modification time in the future is treated as fresh.
+    if metadata
+        .modified()?
+        .elapsed()
+        .map_or(true, |age| age < min_age)
+    {
+        return Ok(false);
+    }
+    if !metadata.is_dir() {
+        return Ok(true);
+    }
+    let mut system = System::new();
+    let mut entries = fs::read_dir(run_dir).await?;
+    while let Some(entry) = entries.next_entry().await? {
+        let path = entry.path();
+        if path.extension() == Some(OsStr::new("pid"))
+            && find_process_from_pid_file(&mut system, &path)
+                .await
+                .is_some()
+        {
+            return Ok(false);
+        }
+    }
+    Ok(true)
+}
+
+async fn remove_stale_dir(path: &Path, dry_run: bool) {
+    if dry_run {
+        info!("would remove stale metadata directory {}", path.display());
+        return;
+    }
+    info!("removing stale metadata directory {}", path.display());
+    let result = match fs::metadata(path).await {
+        Ok(metadata) if metadata.is_dir() => remove_dir_all(path).await,
+        Ok(_) => fs::remove_file(path).await,
+        Err(e) => Err(e),
+    };
+    if let Err(e) = result {
+        if e.kind() != io::ErrorKind::NotFound {
+            warn!(
+                "error removing stale metadata directory {}: {}",
+                path.display(),
+                e.display_with_causes()
+            );
+        }
+    }
+}
+
 struct TcpProxyConfig {
     name: String,
     tcp_listener: AddressedTcpListener,
     uds_path: String,
+    shaping: Option<ProcessOrchestratorTcpProxyShapingConfig>,
 }
 
 async fn tcp_proxy(
@@ -1036,6 +3775,7 @@ async fn tcp_proxy(
         name,
         tcp_listener,
         uds_path,
+        shaping,
     }: TcpProxyConfig,
 ) {
     let mut conns = FuturesUnordered::<Pin<Box<dyn Future<Output = _> + Send>>>::new();
@@ -1045,14 +3785,30 @@ async fn tcp_proxy(
             res = tcp_listener.listener.accept() => {
                 debug!("{name}: accepting tcp proxy connection");
                 let uds_path = uds_path.clone();
+                let shaping = shaping.clone();
                 conns.push(Box::pin(async move {
                     let (mut tcp_conn, _) = res.context("accepting tcp connection")?;
                     let mut uds_conn = UnixStream::connect(uds_path)
                         .await
                         .context("making uds connection")?;
-                    io::copy_bidirectional(&mut tcp_conn, &mut uds_conn)
-                        .await
-                        .context("proxying")
+                    match shaping {
+                        None => {
+                            io::copy_bidirectional(&mut tcp_conn, &mut uds_conn)
+                                .await
+                                .context("proxying")?;
+                        }
+                        Some(shaping) => {
+                            let (tcp_read, tcp_write) = tcp_conn.into_split();
+                            let (uds_read, uds_write) = uds_conn.into_split();
+                            future::try_join(
+                                shaped_copy(tcp_read, uds_write, &shaping),
+                                shaped_copy(uds_read, tcp_write, &shaping),
+                            )
+                            .await
+                            .context("proxying")?;
+                        }
+                    }
+                    Ok(())
                 }));
             }
             res = conns.try_next() => {
@@ -1064,6 +3820,56 @@ async fn tcp_proxy(
     }
 }
 
+/// Copies data from `reader` to `writer` until `reader` reaches EOF, applying
+/// the given traffic shaping.
+///
+/// Reading and writing happen concurrently, so the injected latency does not
+/// reduce throughput.
+async fn shaped_copy(
+    mut reader: impl AsyncRead + Unpin,
+    mut writer: impl AsyncWrite + Unpin,
+    shaping: &ProcessOrchestratorTcpProxyShapingConfig,
+) -> Result<(), io::Error> {
+    // Bound the chunk size by the rate limit, so that the limit is enforced
+    // at a granularity of at most a second.
+    let chunk_size = match shaping.rate_limit {
+        None => 8192,
+        Some(rate_limit) => usize::cast_from(rate_limit.get().min(8192)),
+    };
+    let (tx, mut rx) = mpsc::channel::<(time::Instant, Vec<u8>)>(64);
+
+    let read = async move {
+        let mut buf = vec![0; chunk_size];
+        loop {
+            let n = reader.read(&mut buf).await?;
+            if n == 0
+                || tx
+                    .send((time::Instant::now(), buf[..n].to_vec()))
+                    .await
+                    .is_err()
+            {
+                return Ok::<_, io::Error>(());
+            }
+        }
+    };
+
+    let write = async move {
+        let mut next_write = time::Instant::now();
+        while let Some((received_at, data)) = rx.recv().await {
+            time::sleep_until(next_write.max(received_at + shaping.latency)).await;
+            writer.write_all(&data).await?;
+            if let Some(rate_limit) = shaping.rate_limit {
+                let secs = f64::cast_lossy(data.len()) / f64::cast_lossy(rate_limit.get());
+                next_write = time::Instant::now() + Duration::from_secs_f64(secs);
+            }
+        }
+        writer.shutdown().await
+    };
+
+    future::try_join(read, write).await?;
+    Ok(())
+}
+
 struct ProcessStateUpdater {
     namespace: String,
     id: String,
@@ -1084,6 +3890,9 @@ impl ProcessStateUpdater {
         let status_time = Utc::now();
         process_state.status = status;
         process_state.status_time = status_time;
+        if let ProcessStatus::Ready { .. } = status {
+            process_state.ever_ready = true;
+        }
         let _ = self.service_event_tx.send(ServiceEvent {
             service_id: self.id.to_string(),
             process_id: u64::cast_from(self.i),
@@ -1091,21 +3900,85 @@ impl ProcessStateUpdater {
             time: status_time,
         });
     }
+
+    /// Records when the process was first launched after the service was
+    /// ensured, for [`ProcessOrchestrator::ensure_service_ready`] to observe
+    /// its readiness against.
+    fn update_launched_at(&self, launched_at: time::Instant) {
+        let mut services = self.services.lock().expect("lock poisoned");
+        let Some(process_states) = services.get_mut(&self.id) else {
+            return;
+        };
+        let Some(process_state) = process_states.get_mut(self.i) else {
+            return;
+        };
+        process_state.launched_at = Some(launched_at);
+    }
+
+    /// Records the error that keeps the process from being launched, or that
+    /// there is none anymore, for [`ProcessOrchestrator::ensure_service_ready`]
+    /// to report.
+    fn update_launch_error(&self, error: Option<String>) {
+        let mut services = self.services.lock().expect("lock poisoned");
+        let Some(process_states) = services.get_mut(&self.id) else {
+            return;
+        };
+        let Some(process_state) = process_states.get_mut(self.i) else {
+            return;
+        };
+        if process_state.launch_error == error {
+            return;
+        }
+        process_state.launch_error = error;
+        // Wake up waiters, which learn about the error from the process state.
+        let _ = self.service_event_tx.send(ServiceEvent {
+            service_id: self.id.to_string(),
+            process_id: u64::cast_from(self.i),
+            status: process_state.status.into(),
+            time: process_state.status_time,
+        });
+    }
 }
 
 #[derive(Debug)]
 struct ProcessState {
-    _handle: AbortOnDropHandle<()>,
+    handle: AbortOnDropHandle<()>,
+    spec: ProcessSpec,
     status: ProcessStatus,
     status_time: DateTime<Utc>,
+    /// Whether the process has been ready at least once.
+    ever_ready: bool,
+    /// When the process was first launched after the service was ensured, if
+    /// its readiness has yet to be observed by
+    /// [`ProcessOrchestrator::ensure_service_ready`].
+    launched_at: Option<time::Instant>,
+    /// The error that currently keeps the process from being launched, if any,
+    /// e.g., a conflicting holder of one of its sockets.
+    launch_error: Option<String>,
     labels: BTreeMap<String, String>,
+    /// The socket path of each named port.
+    addresses: BTreeMap<String, String>,
     tcp_proxy_addrs: BTreeMap<String, SocketAddr>,
 }
 
 impl ProcessState {
+    fn address_event(&self, service_id: &str, process_id: usize) -> ServiceAddressEvent {
+        ServiceAddressEvent {
+            service_id: service_id.to_string(),
+            process_id: u64::cast_from(process_id),
+            addresses: self.addresses.clone(),
+            proxy_addresses: self
+                .tcp_proxy_addrs
+                .iter()
+                .map(|(name, addr)| (name.clone(), addr.to_string()))
+                .collect(),
+            time: self.status_time,
+        }
+    }
+
     fn pid(&self) -> Option<Pid> {
         match &self.status {
-            ProcessStatus::NotReady => None,
+            ProcessStatus::NotReady | ProcessStatus::OomKilled | ProcessStatus::Quarantined => None,
             ProcessStatus::Ready { pid } => Some(*pid),
         }
     }
@@ -1114,13 +3987,23 @@ impl ProcessState {
 #[derive(Debug, Clone, Copy)]
 enum ProcessStatus {
     NotReady,
-    Ready { pid: Pid },
+    /// The process was killed by the OOM killer and has not yet been
+    /// relaunched.
+    OomKilled,
+    /// The process crash-looped and is not relaunched until the service is
+    /// ensured again.
+    Quarantined,
+    Ready {
+        pid: Pid,
+    },
 }
 
 impl From<ProcessStatus> for ServiceStatus {
     fn from(status: ProcessStatus) -> ServiceStatus {
         match status {
             ProcessStatus::NotReady => ServiceStatus::Offline(None),
+            ProcessStatus::OomKilled => ServiceStatus::Offline(Some(OfflineReason::OomKilled)),
+            ProcessStatus::Quarantined => ServiceStatus::Offline(Some(OfflineReason::CrashLooping)),
             ProcessStatus::Ready { .. } => ServiceStatus::Online,
         }
     }
@@ -1161,3 +4044,279 @@ impl Service for ProcessService {
             .collect()
     }
 }
+
+#[cfg(test)]
+mod tests {
+    use super::*;
+
+    #[mz_ore::test]
+    fn clock_offset_from_str() {
+        let cases = [
+            ("+500ms", ClockOffset::Ahead(Duration::from_millis(500))),
+            ("2s", ClockOffset::Ahead(Duration::from_secs(2))),
+            ("-2s", ClockOffset::Behind(Duration::from_secs(2))),
+            (" - 1m ", ClockOffset::Behind(Duration::from_secs(60))),
+        ];
+        for (s, expected) in cases {
+            let offset: ClockOffset = s.parse().unwrap();
+            assert_eq!(offset, expected, "parsing {s:?}");
+            // Displayed offsets parse back to the same offset.
+            assert_eq!(offset.to_string().parse::<ClockOffset>().unwrap(), offset);
+        }
+        assert_eq!(
+            ClockOffset::Behind(Duration::from_secs(2)).faketime_spec(),
+            "-2"
+        );
+        assert_eq!(
+            ClockOffset::Ahead(Duration::from_millis(500)).faketime_spec(),
+            "+0.5"
+        );
+
+        for s in ["", "+", "soon", "+-1s"] {
+            assert!(s.parse::<ClockOffset>().is_err(), "parsing {s:?}");
+        }
+    }
+
+    #[mz_ore::test]
+    fn cpu_allocator_assign() {
+        let allocator = CpuAllocator {
+            cpu_sets: vec!["0,1".into(), "2,3".into(), "4,5".into()],
+            assignments: Mutex::new(BTreeMap::new()),
+        };
+
+        // Processes are spread across the least used sets.
+        assert_eq!(allocator.assign("cluster-u1", 0), "0,1");
+        assert_eq!(allocator.assign("cluster-u1", 1), "2,3");
+        assert_eq!(allocator.assign("cluster-u2", 0), "4,5");
+        assert_eq!(allocator.assign("cluster-u2", 1), "0,1");
+
+        // Processes keep their set across relaunches.
+        assert_eq!(allocator.assign("cluster-u1", 1), "2,3");
+        assert_eq!(allocator.assign("cluster-u2", 1), "0,1");
+
+        // Released sets are reused first.
+        allocator.release("cluster-u1");
+        assert_eq!(allocator.assign("cluster-u3", 0), "2,3");
+        assert_eq!(allocator.assign("cluster-u3", 1), "0,1");
+        assert_eq!(allocator.assign("cluster-u3", 2), "2,3");
+        // Releasing an unknown service is a no-op.
+        allocator.release("cluster-u4");
+        assert_eq!(allocator.assign("cluster-u2", 0), "4,5");
+    }
+
+    #[mz_ore::test]
+    fn cpu_allocator_new() {
+        let allocator =
+            CpuAllocator::new(&ProcessOrchestratorCpuAffinity::Cpus(vec![1, 3])).unwrap();
+        assert_eq!(allocator.cpu_sets, ["1,3"]);
+        assert_eq!(allocator.assign("cluster-u1", 0), "1,3");
+        assert_eq!(allocator.assign("cluster-u1", 1), "1,3");
+
+        assert!(CpuAllocator::new(&ProcessOrchestratorCpuAffinity::Cpus(vec![])).is_err());
+    }
+
+    #[mz_ore::test]
+    fn test_shell_quote() {
+        assert_eq!(shell_quote(""), "''");
+        assert_eq!(shell_quote("a b"), "'a b'");
+        assert_eq!(shell_quote("$HOME"), "'$HOME'");
+        assert_eq!(shell_quote("it's"), r"'it'\''s'");
+    }
+
+    #[mz_ore::test]
+    fn test_interpolate_command() {
+        let ports = BTreeMap::from([
+            ("sql".to_string(), "/run/sql".to_string()),
+            ("http".to_string(), "/run/http".to_string()),
+        ]);
+        assert_eq!(
+            interpolate_command("--name=%N", "cluster-u1", &ports),
+            "--name=cluster-u1"
+        );
+        assert_eq!(
+            interpolate_command("%P:sql,%P:http,%P:other", "cluster-u1", &ports),
+            "/run/sql,/run/http,%P:other"
+        );
+        assert_eq!(interpolate_command("plain", "cluster-u1", &ports), "plain");
+    }
+
+    #[mz_ore::test]
+    fn test_direct_process_launcher() {
+        let args = ["--listen-addr=/tmp/mz/controller-0".to_string()];
+        let listen_addrs = BTreeMap::new();
+        let command = |wrapper: &[String], cpu_list: Option<&str>| {
+            let cmd = DirectProcessLauncher.command(&ProcessLaunch {
+                image: OsStr::new("/build/clusterd"),
+                args: &args,
+                wrapper,
+                full_id: "cluster-u1-replica-u2",
+                name: "cluster-u1-replica-u2-0",
+                listen_addrs: &listen_addrs,
+                memory_limit: None,
+                cpu_limit: None,
+                cpu_list,
+            });
+            let cmd = cmd.as_std();
+            let mut command = vec![cmd.get_program().to_string_lossy().into_owned()];
+            command.extend(cmd.get_args().map(|arg| arg.to_string_lossy().into_owned()));
+            command
+        };
+
+        // Unwrapped processes are executed directly, with their name as
+        // `argv[0]`.
+        assert_eq!(
+            command(&[], None),
+            ["/build/clusterd", "--listen-addr=/tmp/mz/controller-0"]
+        );
+        // Pinned processes are renamed by a shell that `taskset` executes.
+        assert_eq!(
+            command(&[], Some("0-3")),
+            [
+                "taskset",
+                "--cpu-list",
+                "0-3",
+                "bash",
+                "-c",
+                r#"exec -a "$0" "$@""#,
+                "cluster-u1-replica-u2-0",
+                "/build/clusterd",
+                "--listen-addr=/tmp/mz/controller-0",
+            ]
+        );
+        // Wrapped processes are executed by the wrapper, so they keep the
+        // name the wrapper gives them.
+        let wrapper = ["rr".to_string(), "record".to_string()];
+        assert_eq!(
+            command(&wrapper, None),
+            [
+                "rr",
+                "record",
+                "/build/clusterd",
+                "--listen-addr=/tmp/mz/controller-0",
+            ]
+        );
+        assert_eq!(
+            command(&wrapper, Some("0-3")),
+            [
+                "taskset",
+                "--cpu-list",
+                "0-3",
+                "rr",
+                "record",
+                "/build/clusterd",
+                "--listen-addr=/tmp/mz/controller-0",
+            ]
+        );
+    }
+
+    #[mz_ore::test]
+    #[cfg_attr(miri, ignore)] // inspects other processes
+    fn test_process_from_pid_file_contents() {
+        let pid = Pid::from_u32(std::process::id());
+        let mut system = System::new();
+        system.refresh_process_specifics(pid, ProcessRefreshKind::new());
+        let start_time = system.process(pid).unwrap().start_time();
+
+        let contents = format!("{pid}\n{start_time}\n");
+        let process = process_from_pid_file_contents(&mut system, &contents).unwrap();
+        assert_eq!(process.pid(), pid);
+
+        // A different start time indicates that the PID was reused.
+        let contents = format!("{pid}\n{}\n", start_time + 1);
+        assert!(process_from_pid_file_contents(&mut system, &contents).is_none());
+
+        for contents in ["", "garbage", &format!("{pid}"), &format!("{pid}\nnow\n")] {
+            assert!(
+                process_from_pid_file_contents(&mut system, contents).is_none(),
+                "contents {contents:?}"
+            );
+        }
+    }
+
+    #[mz_ore::test]
+    fn test_core_dump_location() {
+        let pid = Pid::from_u32(42);
+        let cwd = Path::new("/home/materialize");
+        // A process in private directories runs in its run directory.
+        let run_dir = cwd.join("mzdata/environment-id-0/cluster-u1-replica-u1");
+
+        let path = |core_dump| match core_dump {
+            CoreDump::File(path) => path,
+            CoreDump::Coredumpctl => panic!("unexpected coredumpctl core dump"),
+        };
+        for (pattern, uses_pid, expected) in [
+            ("core", false, run_dir.join("core")),
+            ("core", true, run_dir.join("core.42")),
+            ("core.%p", true, run_dir.join("core.42")),
+            ("cores/%%p-%p", false, run_dir.join("cores/%p-42")),
+            ("/var/crash/core.%p", false, PathBuf::from("/var/crash/core.42")),
+        ] {
+            let core_dump = core_dump_location(pattern, uses_pid, pid, &run_dir).unwrap();
+            assert_eq!(path(core_dump), expected, "pattern {pattern:?}");
+        }
+        // Processes without private directories run in the working directory
+        // of this process.
+        let core_dump = core_dump_location("core.%p", false, pid, cwd).unwrap();
+        assert_eq!(path(core_dump), cwd.join("core.42"));
+
+        assert!(matches!(
+            core_dump_location("|/lib/systemd/systemd-coredump %P", false, pid, cwd),
+            Ok(CoreDump::Coredumpctl)
+        ));
+        assert!(core_dump_location("core.%e", false, pid, cwd).is_err());
+    }
+
+    #[mz_ore::test(tokio::test)]
+    #[cfg_attr(miri, ignore)] // unsupported operation: can't call foreign functions
+    #[cfg(target_os = "linux")]
+    async fn test_check_socket_conflicts() {
+        let dir = tempfile::tempdir().unwrap();
+        let run_dir = dir.path();
+        let ports = ["internal-http".to_string()];
+
+        // Free sockets don't conflict.
+        check_socket_conflicts("cluster-u1", run_dir, &ports, 1).unwrap();
+
+        // A socket held by a process that isn't recorded in the PID file does.
+        let path = socket_path(run_dir, "internal-http", 0);
+        let _listener = std::os::unix::net::UnixListener::bind(&path).unwrap();
+        let error = check_socket_conflicts("cluster-u1", run_dir, &ports, 1).unwrap_err();
+        assert!(
+            error.to_string().contains(&std::process::id().to_string()),
+            "{error}"
+        );
+        // The sockets of other services are unaffected.
+        check_socket_conflicts("cluster-u1", &run_dir.join("other"), &ports, 1).unwrap();
+
+        // A socket held while the recorded process runs is ours.
+        write_pid_file(&run_dir.join("0.pid"), Pid::from_u32(std::process::id()))
+            .await
+            .unwrap();
+        check_socket_conflicts("cluster-u1", run_dir, &ports, 1).unwrap();
+    }
+
+    #[mz_ore::test(tokio::test)]
+    #[cfg_attr(miri, ignore)] // unsupported operation: can't call foreign functions
+    async fn test_is_stale_run_dir() {
+        let dir = tempfile::tempdir().unwrap();
+        let run_dir = dir.path().join("cluster-u1");
+        fs::create_dir(&run_dir).await.unwrap();
+
+        // Recently modified directories are fresh.
+        assert!(!is_stale_run_dir(&run_dir, Duration::from_secs(3600))
+            .await
+            .unwrap());
+        // Old directories without live processes are stale.
+        assert!(is_stale_run_dir(&run_dir, Duration::ZERO).await.unwrap());
+
+        // A PID file that refers to a live process keeps the directory fresh.
+        write_pid_file(&run_dir.join("0.pid"), Pid::from_u32(std::process::id()))
+            .await
+            .unwrap();
+        assert!(!is_stale_run_dir(&run_dir, Duration::ZERO).await.unwrap());
+
+        // Stale PID files don't.
+        fs::write(run_dir.join("0.pid"), "1\n0\n").await.unwrap();
+        assert!(is_stale_run_dir(&run_dir, Duration::ZERO).await.unwrap());
+    }
+}
@@ -25,8 +25,8 @@ use mz_build_info::BuildInfo;
 #[cfg(feature = "tokio-console")]
 use mz_orchestrator::ServicePort;
 use mz_orchestrator::{
-    NamespacedOrchestrator, Orchestrator, Service, ServiceConfig, ServiceEvent,
-    ServiceProcessMetrics,
+    NamespacedOrchestrator, Orchestrator, Service, ServiceAddressEvent, ServiceConfig,
+    ServiceEvent, ServiceProcessMetrics,
 };
 use mz_ore::cli::KeyValueArg;
 use mz_ore::metrics::MetricsRegistry;
@@ -512,6 +512,12 @@ impl NamespacedOrchestrator for NamespacedTracingOrchestrator {
         self.inner.watch_services()
     }
 
+    fn watch_service_addresses(
+        &self,
+    ) -> BoxStream<'static, Result<ServiceAddressEvent, anyhow::Error>> {
+        self.inner.watch_service_addresses()
+    }
+
     fn update_scheduling_config(
         &self,
         config: mz_orchestrator::scheduling_config::ServiceSchedulingConfig,
@@ -72,6 +72,14 @@ pub trait NamespacedOrchestrator: fmt::Debug + Send + Sync {
     /// Watch for status changes of all known services.
     fn watch_services(&self) -> BoxStream<'static, Result<ServiceEvent, anyhow::Error>>;
 
+    /// Watch for changes to the addresses of all known services.
+    ///
+    /// Orchestrators whose service addresses never change after creation may
+    /// return a stream that never yields.
+    fn watch_service_addresses(
+        &self,
+    ) -> BoxStream<'static, Result<ServiceAddressEvent, anyhow::Error>>;
+
     /// Gets resource usage metrics for all processes associated with a service.
     ///
     /// Returns `Err` if the entire process failed. Returns `Ok(v)` otherwise,
@@ -95,16 +103,33 @@ pub struct ServiceEvent {
     pub time: DateTime<Utc>,
 }
 
+/// An event describing a change to the addresses of an orchestrated service
+/// process.
+#[derive(Debug, Clone, Serialize)]
+pub struct ServiceAddressEvent {
+    pub service_id: String,
+    pub process_id: u64,
+    /// The address of each named port of the process.
+    pub addresses: BTreeMap<String, String>,
+    /// The address of the TCP proxy for each named port of the process, if
+    /// the orchestrator proxies ports over TCP.
+    pub proxy_addresses: BTreeMap<String, String>,
+    pub time: DateTime<Utc>,
+}
+
 /// Why the service is not ready, if known
 #[derive(Debug, Clone, Copy, Serialize, Eq, PartialEq)]
 pub enum OfflineReason {
     OomKilled,
+    /// The service restarted too often and is no longer being restarted.
+    CrashLooping,
 }
 
 impl fmt::Display for OfflineReason {
     fn fmt(&self, f: &mut fmt::Formatter) -> fmt::Result {
         match self {
             OfflineReason::OomKilled => f.write_str("oom-killed"),
+            OfflineReason::CrashLooping => f.write_str("crash-looping"),
         }
     }
 }
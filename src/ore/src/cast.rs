@@ -268,6 +268,7 @@ cast_lossy!(f64, i64);
 cast_lossy!(u64, f64);
 cast_lossy!(f64, u64);
 cast_lossy!(f64, u32);
+cast_lossy!(f64, u8);
 
 #[crate::test]
 fn test_try_cast_from() {
@@ -706,3 +706,6 @@ pub const TABLE_MZ_PENDING_CLUSTER_REPLICAS_OID: u32 = 16983;
 pub const ROLE_MZ_ANALYTICS_OID: u32 = 16984;
 pub const CONNECTION_MZ_ANALYTICS_OID: u32 = 16985;
 pub const VIEW_MZ_MATERIALIZATION_DEPENDENCIES_OID: u32 = 16986;
+pub const TABLE_MZ_TIMELINE_TIMESTAMPS_OID: u32 = 16987;
+pub const TABLE_MZ_READ_HOLDS_OID: u32 = 16988;
+pub const TABLE_MZ_READ_HOLD_WAITS_OID: u32 = 16989;
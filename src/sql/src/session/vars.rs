@@ -378,6 +378,8 @@ impl SessionVars {
             &SERVER_VERSION_NUM,
             &SQL_SAFE_UPDATES,
             &REAL_TIME_RECENCY,
+            &DEFAULT_AS_OF_LAG,
+            &AS_OF_OUTSIDE_RETENTION,
             &EMIT_PLAN_INSIGHTS_NOTICE,
             &EMIT_TIMESTAMP_NOTICE,
             &EMIT_TRACE_ID_NOTICE,
@@ -761,6 +763,17 @@ impl SessionVars {
         self.expect_value(&REAL_TIME_RECENCY_TIMEOUT)
     }
 
+    /// Returns the value of the `default_as_of_lag` configuration parameter.
+    pub fn default_as_of_lag(&self) -> &Duration {
+        self.expect_value(&DEFAULT_AS_OF_LAG)
+    }
+
+    /// Returns the value of the `as_of_outside_retention` configuration
+    /// parameter.
+    pub fn as_of_outside_retention(&self) -> AsOfOutsideRetention {
+        *self.expect_value(&AS_OF_OUTSIDE_RETENTION)
+    }
+
     /// Returns the value of `emit_plan_insights_notice` configuration parameter.
     pub fn emit_plan_insights_notice(&self) -> bool {
         *self.expect_value(&EMIT_PLAN_INSIGHTS_NOTICE)
@@ -1105,6 +1118,22 @@ impl DropConnection {
     }
 }
 
+/// The names of the system variables that confirm changes to dangerous
+/// dyncfgs, keyed by the name of the dyncfg they confirm.
+///
+/// See [`SystemVars::dyncfg_updates`].
+static DYNCFG_CONFIRMATION_VARS: Lazy<BTreeMap<&'static str, &'static str>> = Lazy::new(|| {
+    mz_dyncfgs::all_dyncfgs()
+        .entries()
+        .filter(|cfg| cfg.dangerous())
+        .map(|cfg| {
+            // Computed once per process, so leaking the names is fine.
+            let confirmation = mz_dyncfg::confirmation_name(cfg.name());
+            (cfg.name(), &*Box::leak(confirmation.into_boxed_str()))
+        })
+        .collect()
+});
+
 /// On disk variables.
 ///
 /// See the [`crate::session::vars`] module documentation for more details on the
@@ -1293,9 +1322,15 @@ impl SystemVars {
                 ConfigVal::Bool(default) => {
                     VarDefinition::new_runtime(cfg.name(), *default, cfg.desc(), false)
                 }
+                ConfigVal::U8(default) => {
+                    VarDefinition::new_runtime(cfg.name(), *default, cfg.desc(), false)
+                }
                 ConfigVal::U32(default) => {
                     VarDefinition::new_runtime(cfg.name(), *default, cfg.desc(), false)
                 }
+                ConfigVal::I64(default) => {
+                    VarDefinition::new_runtime(cfg.name(), *default, cfg.desc(), false)
+                }
                 ConfigVal::Usize(default) => {
                     VarDefinition::new_runtime(cfg.name(), *default, cfg.desc(), false)
                 }
@@ -1311,11 +1346,26 @@ impl SystemVars {
                 ConfigVal::Duration(default) => {
                     VarDefinition::new_runtime(cfg.name(), default.clone(), cfg.desc(), false)
                 }
+                ConfigVal::Bytes(default) => VarDefinition::new_runtime(
+                    cfg.name(),
+                    ByteSize::b(default.as_u64()),
+                    cfg.desc(),
+                    false,
+                ),
                 ConfigVal::Json(default) => {
                     VarDefinition::new_runtime(cfg.name(), default.clone(), cfg.desc(), false)
                 }
             })
             .collect();
+        let dyncfg_confirmation_vars = DYNCFG_CONFIRMATION_VARS.values().map(|name| {
+            VarDefinition::new_runtime(
+                name,
+                false,
+                "Confirms changes to the dangerous dyncfg named by this variable, \
+                 without the `.confirm` suffix.",
+                false,
+            )
+        });
 
         let vars: BTreeMap<_, _> = system_vars
             .into_iter()
@@ -1326,6 +1376,7 @@ impl SystemVars {
             .cloned()
             // Include Persist configs.
             .chain(dyncfg_vars)
+            .chain(dyncfg_confirmation_vars)
             .map(|var| (var.name, SystemVar::new(var)))
             .collect();
 
@@ -1965,13 +2016,20 @@ impl SystemVars {
         ))
     }
 
+    /// Returns the values of the dyncfgs in this set as [`ConfigUpdates`].
+    ///
+    /// A change to a dangerous dyncfg is confirmed, and so applied, only if
+    /// the matching `<name>.confirm` variable is set. Otherwise it is staged,
+    /// see [`ConfigUpdates::confirm`].
     pub fn dyncfg_updates(&self) -> ConfigUpdates {
-        let mut updates = ConfigUpdates::default();
+        let mut updates = ConfigUpdates::default().with_source("system_vars");
         for entry in self.dyncfgs.entries() {
             let name = UncasedStr::new(entry.name());
             let val = match entry.val() {
                 ConfigVal::Bool(_) => ConfigVal::from(*self.expect_config_value::<bool>(name)),
+                ConfigVal::U8(_) => ConfigVal::from(*self.expect_config_value::<u8>(name)),
                 ConfigVal::U32(_) => ConfigVal::from(*self.expect_config_value::<u32>(name)),
+                ConfigVal::I64(_) => ConfigVal::from(*self.expect_config_value::<i64>(name)),
                 ConfigVal::Usize(_) => ConfigVal::from(*self.expect_config_value::<usize>(name)),
                 ConfigVal::OptUsize(_) => {
                     ConfigVal::from(*self.expect_config_value::<Option<usize>>(name))
@@ -1983,11 +2041,22 @@ impl SystemVars {
                 ConfigVal::Duration(_) => {
                     ConfigVal::from(*self.expect_config_value::<Duration>(name))
                 }
+                ConfigVal::Bytes(mut bytes) => {
+                    bytes.0 = self.expect_config_value::<ByteSize>(name).as_bytes();
+                    ConfigVal::Bytes(bytes)
+                }
                 ConfigVal::Json(_) => {
                     ConfigVal::from(self.expect_config_value::<serde_json::Value>(name).clone())
                 }
             };
             updates.add_dynamic(entry.name(), val);
+            // A change to a dangerous config is only applied if its
+            // `<name>.confirm` variable is also set, otherwise it is staged.
+            if let Some(confirmation) = DYNCFG_CONFIRMATION_VARS.get(entry.name()) {
+                if *self.expect_config_value::<bool>(UncasedStr::new(confirmation)) {
+                    updates.confirm_dynamic(entry.name());
+                }
+            }
         }
         updates.apply(&self.dyncfgs);
         updates
@@ -2264,6 +2333,7 @@ impl SystemVars {
     /// Returns whether the named variable is a dyncfg configuration parameter.
     fn is_dyncfg_var(&self, name: &str) -> bool {
         self.dyncfgs.entries().any(|e| name == e.name())
+            || DYNCFG_CONFIRMATION_VARS.values().any(|c| name == *c)
     }
 }
 
@@ -43,8 +43,8 @@ use crate::session::vars::constraints::{
 use crate::session::vars::errors::VarError;
 use crate::session::vars::polyfill::{lazy_value, value, LazyValueFn};
 use crate::session::vars::value::{
-    ClientEncoding, ClientSeverity, Failpoints, IntervalStyle, IsolationLevel, TimeZone, Value,
-    DEFAULT_DATE_STYLE,
+    AsOfOutsideRetention, ClientEncoding, ClientSeverity, Failpoints, IntervalStyle,
+    IsolationLevel, TimeZone, Value, DEFAULT_DATE_STYLE,
 };
 use crate::session::vars::{FeatureFlag, Var, VarInput, VarParseError};
 use crate::{DEFAULT_SCHEMA, WEBHOOK_CONCURRENCY_LIMIT};
@@ -1241,6 +1241,24 @@ pub static REAL_TIME_RECENCY_TIMEOUT: VarDefinition = VarDefinition::new(
 )
 .with_feature_flag(&ALLOW_REAL_TIME_RECENCY);
 
+pub static DEFAULT_AS_OF_LAG: VarDefinition = VarDefinition::new(
+    "default_as_of_lag",
+    value!(Duration; Duration::ZERO),
+    "Sets how far in the past SELECTs without an AS OF clause read under serializable \
+    isolation, as long as the inputs are still readable at that time. A value of zero \
+    disables the lag (Materialize).",
+    true,
+);
+
+pub static AS_OF_OUTSIDE_RETENTION: VarDefinition = VarDefinition::new(
+    "as_of_outside_retention",
+    value!(AsOfOutsideRetention; AsOfOutsideRetention::Error),
+    "Sets what to do with queries whose AS OF is before the earliest readable time of \
+    their inputs: 'error' rejects them, while 'nearest' answers them at the earliest readable \
+    time and reports the adjustment in a notice (Materialize).",
+    true,
+);
+
 pub static EMIT_PLAN_INSIGHTS_NOTICE: VarDefinition = VarDefinition::new(
     "emit_plan_insights_notice",
     value!(bool; false),
@@ -1032,6 +1032,71 @@ impl Value for ClientEncoding {
     }
 }
 
+/// What to do with queries whose `AS OF` lies before the earliest time at
+/// which their inputs are readable.
+#[derive(Clone, Copy, PartialEq, Eq, Debug)]
+pub enum AsOfOutsideRetention {
+    /// Reject the query.
+    Error,
+    /// Answer the query at the nearest readable time, and report the
+    /// adjustment in a notice.
+    Nearest,
+}
+
+impl AsOfOutsideRetention {
+    pub fn as_str(&self) -> &'static str {
+        match self {
+            Self::Error => "error",
+            Self::Nearest => "nearest",
+        }
+    }
+
+    fn valid_values() -> Vec<&'static str> {
+        vec![Self::Error.as_str(), Self::Nearest.as_str()]
+    }
+}
+
+impl fmt::Display for AsOfOutsideRetention {
+    fn fmt(&self, f: &mut std::fmt::Formatter<'_>) -> std::fmt::Result {
+        f.write_str(self.as_str())
+    }
+}
+
+impl Value for AsOfOutsideRetention {
+    fn type_name() -> Cow<'static, str>
+    where
+        Self: Sized,
+    {
+        "string".into()
+    }
+
+    fn parse(input: VarInput<'_>) -> Result<Self, VarParseError>
+    where
+        Self: Sized,
+    {
+        let s = extract_single_value(input)?;
+        let s = UncasedStr::new(s);
+        if s == Self::Error.as_str() {
+            Ok(Self::Error)
+        } else if s == Self::Nearest.as_str() {
+            Ok(Self::Nearest)
+        } else {
+            Err(VarParseError::ConstrainedParameter {
+                invalid_values: input.to_vec(),
+                valid_values: Some(AsOfOutsideRetention::valid_values()),
+            })
+        }
+    }
+
+    fn box_clone(&self) -> Box<dyn Value> {
+        Box::new(self.clone())
+    }
+
+    fn format(&self) -> String {
+        self.as_str().into()
+    }
+}
+
 #[derive(Clone, Copy, PartialEq, Eq, Debug)]
 pub enum IntervalStyle {
     Postgres,
@@ -1118,6 +1183,8 @@ macro_rules! impl_value_for_simple {
 }
 
 impl_value_for_simple!(i32, "integer");
+impl_value_for_simple!(i64, "64-bit integer");
+impl_value_for_simple!(u8, "8-bit unsigned integer");
 impl_value_for_simple!(u32, "unsigned integer");
 impl_value_for_simple!(u64, "64-bit unsigned integer");
 impl_value_for_simple!(usize, "unsigned integer");
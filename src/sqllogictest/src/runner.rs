@@ -25,7 +25,7 @@
 //!       compare to expected results
 //!       if wrong, record the error
 
-use std::collections::BTreeMap;
+use std::collections::{BTreeMap, BTreeSet};
 use std::error::Error;
 use std::fs::{File, OpenOptions};
 use std::io::{Read, Seek, SeekFrom, Write};
@@ -959,11 +959,13 @@ impl<'a> RunnerInner<'a> {
             )
         };
 
+        let metrics_registry = MetricsRegistry::new();
         let secrets_dir = temp_dir.path().join("secrets");
         let orchestrator = Arc::new(
             ProcessOrchestrator::new(ProcessOrchestratorConfig {
                 image_dir: env::current_exe()?.parent().unwrap().to_path_buf(),
                 suppress_output: false,
+                pty: false,
                 environment_id: environment_id.to_string(),
                 secrets_dir: secrets_dir.clone(),
                 command_wrapper: config
@@ -973,11 +975,23 @@ impl<'a> RunnerInner<'a> {
                 propagate_crashes: true,
                 tcp_proxy: None,
                 scratch_directory: scratch_dir.path().to_path_buf(),
+                ssh: None,
+                stale_metadata_cleanup: None,
+                cpu_affinity: None,
+                disk_limit_enforcement: None,
+                launchers: BTreeMap::new(),
+                clock_skew: None,
+                sidecars: BTreeMap::new(),
+                profiling: None,
+                crash_loop: None,
+                crash_debugging: None,
+                process_groups: None,
+                private_dirs: BTreeSet::new(),
+                metrics_registry: metrics_registry.clone(),
             })
             .await?,
         );
         let now = SYSTEM_TIME.clone();
-        let metrics_registry = MetricsRegistry::new();
 
         let persist_config = PersistConfig::new(
             &mz_environmentd::BUILD_INFO,
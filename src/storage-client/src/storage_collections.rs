@@ -44,7 +44,7 @@ use mz_storage_types::connections::ConnectionContext;
 use mz_storage_types::controller::{CollectionMetadata, StorageError, TxnsCodecRow};
 use mz_storage_types::dyncfgs::STORAGE_DOWNGRADE_SINCE_DURING_FINALIZATION;
 use mz_storage_types::parameters::StorageParameters;
-use mz_storage_types::read_holds::{ReadHold, ReadHoldError};
+use mz_storage_types::read_holds::{ReadCapabilities, ReadHold, ReadHoldError};
 use mz_storage_types::read_policy::ReadPolicy;
 use mz_storage_types::sources::{
     ExportReference, GenericSourceConnection, IngestionDescription, SourceData, SourceDesc,
@@ -54,7 +54,6 @@ use mz_txn_wal::metrics::Metrics as TxnMetrics;
 use mz_txn_wal::txn_read::{DataSnapshot, TxnsRead};
 use mz_txn_wal::txns::TxnsHandle;
 use timely::order::TotalOrder;
-use timely::progress::frontier::MutableAntichain;
 use timely::progress::{Antichain, ChangeBatch, Timestamp as TimelyTimestamp};
 use timely::PartialOrder;
 use tokio::sync::{mpsc, oneshot};
@@ -767,9 +766,9 @@ where
         let (deps, collection_implied_capability) = match self_collections.get(&id) {
             Some(CollectionState {
                 storage_dependencies: deps,
-                implied_capability,
+                read_capabilities,
                 ..
-            }) => (deps.clone(), implied_capability),
+            }) => (deps.clone(), read_capabilities.implied_capability()),
             _ => return Ok(()),
         };
 
@@ -780,11 +779,11 @@ where
 
             mz_ore::soft_assert_or_log!(
                 PartialOrder::less_equal(
-                    &dep_collection.implied_capability,
+                    dep_collection.read_capabilities.implied_capability(),
                     collection_implied_capability
                 ),
                 "dependency since ({dep}@{:?}) cannot be in advance of dependent's since ({id}@{:?})",
-                dep_collection.implied_capability,
+                dep_collection.read_capabilities.implied_capability(),
                 collection_implied_capability,
             );
         }
@@ -854,8 +853,13 @@ where
             .iter_mut()
             .filter(|(id, _c)| id.is_user())
             .map(|(id, c)| {
-                let updates = c.read_capabilities.updates().cloned().collect_vec();
-                (*id, c.implied_capability.clone(), updates)
+                let updates = c
+                    .read_capabilities
+                    .capabilities()
+                    .updates()
+                    .cloned()
+                    .collect_vec();
+                (*id, c.read_capabilities.implied_capability().clone(), updates)
             })
             .collect_vec();
 
@@ -881,8 +885,13 @@ where
             .iter_mut()
             .filter(|(id, _c)| id.is_user())
             .map(|(id, c)| {
-                let updates = c.read_capabilities.updates().cloned().collect_vec();
-                (*id, c.implied_capability.clone(), updates)
+                let updates = c
+                    .read_capabilities
+                    .capabilities()
+                    .updates()
+                    .cloned()
+                    .collect_vec();
+                (*id, c.read_capabilities.implied_capability().clone(), updates)
             })
             .collect_vec();
 
@@ -945,19 +954,12 @@ where
                 }
             };
 
-            let mut new_read_capability = policy.frontier(collection.write_frontier.borrow());
-
-            if PartialOrder::less_equal(&collection.implied_capability, &new_read_capability) {
-                let mut update = ChangeBatch::new();
-                update.extend(new_read_capability.iter().map(|time| (time.clone(), 1)));
-                std::mem::swap(&mut collection.implied_capability, &mut new_read_capability);
-                update.extend(new_read_capability.iter().map(|time| (time.clone(), -1)));
-                if !update.is_empty() {
-                    read_capability_changes.insert(id, update);
-                }
+            let update = collection
+                .read_capabilities
+                .set_read_policy(policy, collection.write_frontier.borrow());
+            if !update.is_empty() {
+                read_capability_changes.insert(id, update);
             }
-
-            collection.read_policy = policy;
         }
 
         for (id, changes) in read_capability_changes.iter() {
@@ -1013,30 +1015,8 @@ where
                 }
             };
 
-            let current_read_capabilities = collection.read_capabilities.frontier().to_owned();
-            for (time, diff) in update.iter() {
-                assert!(
-                    collection.read_capabilities.count_for(time) + diff >= 0,
-                    "update {:?} for collection {id} would lead to negative \
-                        read capabilities, read capabilities before applying: {:?}",
-                    update,
-                    collection.read_capabilities
-                );
-
-                if collection.read_capabilities.count_for(time) + diff > 0 {
-                    assert!(
-                        current_read_capabilities.less_equal(time),
-                        "update {:?} for collection {id} is trying to \
-                            install read capabilities before the current \
-                            frontier of read capabilities, read capabilities before applying: {:?}",
-                        update,
-                        collection.read_capabilities
-                    );
-                }
-            }
-
-            let changes = collection.read_capabilities.update_iter(update.drain());
-            update.extend(changes);
+            let mut changes = collection.read_capabilities.apply(id, &mut update);
+            update.extend(changes.drain());
 
             if id.is_user() {
                 trace!(
@@ -1181,7 +1161,7 @@ where
                     .map(|c| CollectionFrontiers {
                         id: id.clone(),
                         write_frontier: c.write_frontier.clone(),
-                        implied_capability: c.implied_capability.clone(),
+                        implied_capability: c.read_capabilities.implied_capability().clone(),
                         read_capabilities: c.read_capabilities.frontier().to_owned(),
                     })
                     .ok_or(StorageError::IdentifierMissing(id))
@@ -1200,7 +1180,7 @@ where
             .map(|(id, c)| CollectionFrontiers {
                 id: id.clone(),
                 write_frontier: c.write_frontier.clone(),
-                implied_capability: c.implied_capability.clone(),
+                implied_capability: c.read_capabilities.implied_capability().clone(),
                 read_capabilities: c.read_capabilities.frontier().to_owned(),
             })
             .collect_vec();
@@ -1550,7 +1530,10 @@ where
                     let dependency_collection = self_collections
                         .get(dep)
                         .ok_or(StorageError::IdentifierMissing(*dep))?;
-                    let dependency_since = dependency_collection.implied_capability.clone();
+                    let dependency_since = dependency_collection
+                        .read_capabilities
+                        .implied_capability()
+                        .clone();
 
                     // If an item has a dependency, its initial since must be
                     // advanced as far as its dependency, i.e. a dependency's
@@ -1859,8 +1842,13 @@ where
             .iter_mut()
             .filter(|(id, _c)| id.is_user())
             .map(|(id, c)| {
-                let updates = c.read_capabilities.updates().cloned().collect_vec();
-                (*id, c.implied_capability.clone(), updates)
+                let updates = c
+                    .read_capabilities
+                    .capabilities()
+                    .updates()
+                    .cloned()
+                    .collect_vec();
+                (*id, c.read_capabilities.implied_capability().clone(), updates)
             })
             .collect_vec();
 
@@ -1872,8 +1860,13 @@ where
             .iter_mut()
             .filter(|(id, _c)| id.is_user())
             .map(|(id, c)| {
-                let updates = c.read_capabilities.updates().cloned().collect_vec();
-                (*id, c.implied_capability.clone(), updates)
+                let updates = c
+                    .read_capabilities
+                    .capabilities()
+                    .updates()
+                    .cloned()
+                    .collect_vec();
+                (*id, c.read_capabilities.implied_capability().clone(), updates)
             })
             .collect_vec();
 
@@ -2057,20 +2050,13 @@ struct CollectionState<T> {
     /// Description with which the collection was created
     pub description: CollectionDescription<T>,
 
-    /// Accumulation of read capabilities for the collection.
+    /// Read capabilities for the collection.
     ///
-    /// This accumulation will always contain `self.implied_capability`, but may
-    /// also contain capabilities held by others who have read dependencies on
-    /// this collection.
-    pub read_capabilities: MutableAntichain<T>,
-
-    /// The implicit capability associated with collection creation.  This
+    /// These always contain the implied capability of the collection, which
     /// should never be less than the since of the associated persist
-    /// collection.
-    pub implied_capability: Antichain<T>,
-
-    /// The policy to use to downgrade `self.implied_capability`.
-    pub read_policy: ReadPolicy<T>,
+    /// collection, but may also contain capabilities held by others who have
+    /// read dependencies on this collection.
+    pub read_capabilities: ReadCapabilities<T>,
 
     /// Storage identifiers on which this collection depends.
     pub storage_dependencies: Vec<GlobalId>,
@@ -2091,15 +2077,9 @@ impl<T: TimelyTimestamp> CollectionState<T> {
         storage_dependencies: Vec<GlobalId>,
         metadata: CollectionMetadata,
     ) -> Self {
-        let mut read_capabilities = MutableAntichain::new();
-        read_capabilities.update_iter(since.iter().map(|time| (time.clone(), 1)));
         Self {
             description,
-            read_capabilities,
-            implied_capability: since.clone(),
-            read_policy: ReadPolicy::NoPolicy {
-                initial_since: since,
-            },
+            read_capabilities: ReadCapabilities::new(since),
             storage_dependencies,
             write_frontier,
             collection_metadata: metadata,
@@ -2349,29 +2329,22 @@ where
                 collection.write_frontier.clone_from(new_upper);
             }
 
-            let mut new_read_capability = collection
-                .read_policy
-                .frontier(collection.write_frontier.borrow());
+            let update = collection
+                .read_capabilities
+                .advance_implied_capability(collection.write_frontier.borrow());
 
             if id.is_user() {
                 trace!(
                     %id,
-                    implied_capability = ?collection.implied_capability,
-                    policy = ?collection.read_policy,
+                    implied_capability = ?collection.read_capabilities.implied_capability(),
+                    policy = ?collection.read_capabilities.read_policy(),
                     write_frontier = ?collection.write_frontier,
-                    ?new_read_capability,
+                    ?update,
                     "update_write_frontiers");
             }
 
-            if PartialOrder::less_equal(&collection.implied_capability, &new_read_capability) {
-                let mut update = ChangeBatch::new();
-                update.extend(new_read_capability.iter().map(|time| (time.clone(), 1)));
-                std::mem::swap(&mut collection.implied_capability, &mut new_read_capability);
-                update.extend(new_read_capability.iter().map(|time| (time.clone(), -1)));
-
-                if !update.is_empty() {
-                    read_capability_changes.insert(*id, update);
-                }
+            if !update.is_empty() {
+                read_capability_changes.insert(*id, update);
             }
         }
 
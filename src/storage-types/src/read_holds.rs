@@ -11,10 +11,13 @@ use std::fmt::Debug;
 
 use mz_repr::GlobalId;
 use thiserror::Error;
+use timely::progress::frontier::{AntichainRef, MutableAntichain};
 use timely::progress::{Antichain, ChangeBatch, Timestamp as TimelyTimestamp};
 use timely::PartialOrder;
 use tokio::sync::mpsc::UnboundedSender;
 
+use crate::read_policy::ReadPolicy;
+
 /// Token that represents a hold on a collection. This prevents the since of the
 /// collection from progressing beyond the hold. In other words, it cannot
 /// become true that our hold is `less_than` the since.
@@ -181,6 +184,149 @@ impl<T: TimelyTimestamp> Drop for ReadHold<T> {
     }
 }
 
+/// Checks `update` against `read_capabilities`, the accumulation of the
+/// capabilities held by all [ReadHold]s given out for collection `id`, then
+/// applies it and returns the resulting changes to its frontier. `update` is
+/// drained.
+///
+/// This only shares the sanity checks that the storage collections and the
+/// compute controller run on read capability changes, so that they report
+/// invalid updates the same way. Each still maintains its own capabilities.
+///
+/// # Panics
+/// Panics when the update would make a capability negative, or would install
+/// a capability before the current frontier, which would let the since of
+/// the collection regress. Either means that a hold was released twice or
+/// forged, and continuing would leave the collection with a corrupted, and
+/// usually stuck, read frontier.
+pub fn apply_read_capability_changes<T: TimelyTimestamp>(
+    id: GlobalId,
+    read_capabilities: &mut MutableAntichain<T>,
+    update: &mut ChangeBatch<T>,
+) -> ChangeBatch<T> {
+    let read_frontier = read_capabilities.frontier();
+    for (time, diff) in update.iter() {
+        let count = read_capabilities.count_for(time) + diff;
+        assert!(
+            count >= 0,
+            "invalid read capabilities update: negative capability \
+             (id={id:?}, read_capabilities={read_capabilities:?}, update={update:?})",
+        );
+        assert!(
+            count == 0 || read_frontier.less_equal(time),
+            "invalid read capabilities update: frontier regression \
+             (id={id:?}, read_capabilities={read_capabilities:?}, update={update:?})",
+        );
+    }
+
+    let mut changes = ChangeBatch::new();
+    changes.extend(read_capabilities.update_iter(update.drain()));
+    changes
+}
+
+/// The read capabilities of a collection, as maintained by the controller that
+/// owns the collection.
+///
+/// This is the accumulation of the capabilities held by all [ReadHold]s given
+/// out for the collection, together with the implied capability that the
+/// collection holds on itself. The implied capability is kept at the frontier
+/// its read policy computes from the write frontier of the collection, and it
+/// only ever advances.
+///
+/// The methods that move the implied capability don't apply the change to the
+/// accumulation themselves. They return it, so the caller can apply it with
+/// [ReadCapabilities::apply] along with the changes to the capabilities of the
+/// collections that depend on this one.
+#[derive(Debug)]
+pub struct ReadCapabilities<T> {
+    /// Accumulation of the read capabilities of the collection.
+    ///
+    /// This always contains `implied_capability`.
+    capabilities: MutableAntichain<T>,
+    /// The implicit capability associated with the collection.
+    implied_capability: Antichain<T>,
+    /// The policy to use to downgrade `implied_capability`.
+    read_policy: ReadPolicy<T>,
+}
+
+impl<T: TimelyTimestamp> ReadCapabilities<T> {
+    /// Creates the read capabilities of a collection whose since is `since`,
+    /// which are held at `since` until a read policy is set.
+    pub fn new(since: Antichain<T>) -> Self {
+        Self {
+            capabilities: MutableAntichain::from(since.clone()),
+            implied_capability: since.clone(),
+            read_policy: ReadPolicy::NoPolicy {
+                initial_since: since,
+            },
+        }
+    }
+
+    /// Returns the frontier of the read capabilities, i.e. the since that the
+    /// collection may be compacted to.
+    pub fn frontier(&self) -> AntichainRef<T> {
+        self.capabilities.frontier()
+    }
+
+    /// Returns whether the collection holds no capabilities at all, which is
+    /// the case once it has been dropped.
+    pub fn is_empty(&self) -> bool {
+        self.capabilities.is_empty()
+    }
+
+    /// Returns the accumulation of the read capabilities.
+    pub fn capabilities(&self) -> &MutableAntichain<T> {
+        &self.capabilities
+    }
+
+    /// Returns the implied capability of the collection.
+    pub fn implied_capability(&self) -> &Antichain<T> {
+        &self.implied_capability
+    }
+
+    /// Returns the read policy of the collection.
+    pub fn read_policy(&self) -> &ReadPolicy<T> {
+        &self.read_policy
+    }
+
+    /// Sets the read policy of the collection and returns the change to the
+    /// implied capability that the new policy causes at `write_frontier`.
+    ///
+    /// A policy that computes a frontier before the implied capability doesn't
+    /// move it back, it only keeps it from advancing further.
+    pub fn set_read_policy(
+        &mut self,
+        read_policy: ReadPolicy<T>,
+        write_frontier: AntichainRef<T>,
+    ) -> ChangeBatch<T> {
+        self.read_policy = read_policy;
+        self.advance_implied_capability(write_frontier)
+    }
+
+    /// Advances the implied capability to the frontier the read policy
+    /// computes at `write_frontier`, if that is in advance of it, and returns
+    /// the change to the implied capability.
+    pub fn advance_implied_capability(
+        &mut self,
+        write_frontier: AntichainRef<T>,
+    ) -> ChangeBatch<T> {
+        let mut new_implied_capability = self.read_policy.frontier(write_frontier);
+        let mut update = ChangeBatch::new();
+        if PartialOrder::less_equal(&self.implied_capability, &new_implied_capability) {
+            update.extend(new_implied_capability.iter().map(|time| (time.clone(), 1)));
+            std::mem::swap(&mut self.implied_capability, &mut new_implied_capability);
+            update.extend(new_implied_capability.iter().map(|time| (time.clone(), -1)));
+        }
+        update
+    }
+
+    /// Applies `update` to the read capabilities of the collection identified
+    /// by `id`, see [apply_read_capability_changes].
+    pub fn apply(&mut self, id: GlobalId, update: &mut ChangeBatch<T>) -> ChangeBatch<T> {
+        apply_read_capability_changes(id, &mut self.capabilities, update)
+    }
+}
+
 #[derive(Error, Debug)]
 pub enum ReadHoldError {
     #[error("collection does not exist: {0}")]
@@ -188,3 +334,87 @@ pub enum ReadHoldError {
     #[error("desired read hold frontier is not beyond the since of collection: {0}")]
     SinceViolation(GlobalId),
 }
+
+#[cfg(test)]
+mod tests {
+    use super::*;
+
+    fn capabilities(times: &[u64]) -> MutableAntichain<u64> {
+        let mut read_capabilities = MutableAntichain::new();
+        read_capabilities.update_iter(times.iter().map(|t| (*t, 1)));
+        read_capabilities
+    }
+
+    #[mz_ore::test]
+    fn read_capability_changes() {
+        let id = GlobalId::User(1);
+        let mut read_capabilities = capabilities(&[1, 3]);
+
+        // Downgrading a hold that isn't the least one doesn't change the
+        // frontier.
+        let mut update = ChangeBatch::new_from(3, -1);
+        update.update(4, 1);
+        let mut changes = apply_read_capability_changes(id, &mut read_capabilities, &mut update);
+        assert!(update.is_empty());
+        assert!(changes.is_empty());
+
+        // Downgrading the least one does.
+        let mut update = ChangeBatch::new_from(1, -1);
+        update.update(2, 1);
+        changes = apply_read_capability_changes(id, &mut read_capabilities, &mut update);
+        assert_eq!(changes.into_inner(), vec![(1, -1), (2, 1)]);
+        assert_eq!(
+            read_capabilities.frontier().to_owned(),
+            Antichain::from_elem(2)
+        );
+    }
+
+    #[mz_ore::test]
+    #[should_panic(expected = "negative capability")]
+    fn read_capability_changes_negative() {
+        let mut read_capabilities = capabilities(&[1]);
+        let mut update = ChangeBatch::new_from(1, -2);
+        apply_read_capability_changes(GlobalId::User(1), &mut read_capabilities, &mut update);
+    }
+
+    #[mz_ore::test]
+    #[should_panic(expected = "frontier regression")]
+    fn read_capability_changes_regression() {
+        let mut read_capabilities = capabilities(&[2]);
+        let mut update = ChangeBatch::new_from(1, 1);
+        apply_read_capability_changes(GlobalId::User(1), &mut read_capabilities, &mut update);
+    }
+
+    #[mz_ore::test]
+    fn read_capabilities_implied_capability() {
+        let id = GlobalId::User(1);
+        let mut read_capabilities = ReadCapabilities::new(Antichain::from_elem(1u64));
+        let write_frontier = Antichain::from_elem(10);
+
+        // Without a policy, the implied capability stays at the initial since.
+        let update = read_capabilities.advance_implied_capability(write_frontier.borrow());
+        assert!(update.is_empty());
+
+        // Setting a policy moves the implied capability, but only once the
+        // change has been applied does the frontier follow.
+        let policy = ReadPolicy::ValidFrom(Antichain::from_elem(5));
+        let mut update = read_capabilities.set_read_policy(policy, write_frontier.borrow());
+        assert_eq!(read_capabilities.implied_capability(), &Antichain::from_elem(5));
+        assert_eq!(read_capabilities.frontier().to_owned(), Antichain::from_elem(1));
+        let changes = read_capabilities.apply(id, &mut update);
+        assert_eq!(changes.into_inner(), vec![(1, -1), (5, 1)]);
+        assert_eq!(read_capabilities.frontier().to_owned(), Antichain::from_elem(5));
+
+        // A policy that would move the implied capability back leaves it
+        // where it is.
+        let policy = ReadPolicy::ValidFrom(Antichain::from_elem(3));
+        let update = read_capabilities.set_read_policy(policy, write_frontier.borrow());
+        assert!(update.is_empty());
+        assert_eq!(read_capabilities.implied_capability(), &Antichain::from_elem(5));
+
+        // Releasing the implied capability empties the capabilities.
+        let mut update = ChangeBatch::new_from(5, -1);
+        read_capabilities.apply(id, &mut update);
+        assert!(read_capabilities.is_empty());
+    }
+}